@@ -0,0 +1,58 @@
+use gaymwtf_core::core::ui::*;
+use macroquad::prelude::*;
+use std::fs;
+
+const SAVE_DIR: &str = "saves";
+
+/// Lists the world directories under the save folder, sorted by name.
+fn list_saves() -> Vec<String> {
+    let mut saves: Vec<String> = fs::read_dir(SAVE_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    saves.sort();
+    saves
+}
+
+#[macroquad::main("Load Game Example")]
+async fn main() {
+    let screen_center = vec2(screen_width() / 2.0, screen_height() / 2.0);
+
+    let mut title = Label::new("LOAD GAME", vec2(screen_center.x, 60.0), 40, WHITE);
+    title.set_alignment(Alignment::Center);
+
+    let mut status = Label::new("", vec2(screen_center.x, screen_height() - 60.0), 20, LIGHTGRAY);
+    status.set_alignment(Alignment::Center);
+
+    let mut list = ScrollList::new(Rect::new(screen_center.x - 150.0, 130.0, 300.0, 300.0));
+    list.set_spacing(4.0);
+
+    let saves = list_saves();
+    if saves.is_empty() {
+        status.set_text(&format!("No saves found in '{}'", SAVE_DIR));
+    }
+    for save in &saves {
+        list.add_row(Box::new(Label::new(save, Vec2::ZERO, 28, WHITE)));
+    }
+
+    loop {
+        list.update();
+
+        if let Some(index) = list.clicked_row() {
+            list.reset_click();
+            status.set_text(&format!("Selected '{}/{}'", SAVE_DIR, saves[index]));
+        }
+
+        clear_background(Color::new(0.1, 0.1, 0.2, 1.0));
+        title.draw();
+        list.draw();
+        status.draw();
+
+        next_frame().await;
+    }
+}
@@ -31,8 +31,8 @@ impl ToggleButton {
         }
     }
 
-    fn draw(&self) {
-        self.button.draw();
+    fn draw_batched(&self, batch: &mut DrawBatch) {
+        self.button.draw_batched(batch);
     }
 
     fn is_on(&self) -> bool {
@@ -52,8 +52,11 @@ impl MainMenu {
     fn new() -> Self {
         let screen_center = Vec2::new(screen_width() / 2.0, screen_height() / 2.0);
         
+        let mut title = Label::new("MAIN MENU", vec2(screen_center.x, 100.0), 40, WHITE);
+        title.set_alignment(Alignment::Center);
+        
         Self {
-            title: Label::new("MAIN MENU", vec2(screen_center.x - 100.0, 100.0), 40, WHITE),
+            title,
             start_button: Button::new("Start Game", Rect::new(screen_center.x - 100.0, screen_center.y - 50.0, 200.0, 40.0)),
             options_button: Button::new("Options", Rect::new(screen_center.x - 100.0, screen_center.y + 10.0, 200.0, 40.0)),
             quit_button: Button::new("Quit", Rect::new(screen_center.x - 100.0, screen_center.y + 70.0, 200.0, 40.0)),
@@ -85,13 +88,14 @@ impl Menu for MainMenu {
         MenuAction::None
     }
 
-    fn draw(&mut self, _batch: &mut DrawBatch) {
+    fn draw(&mut self, batch: &mut DrawBatch) {
         clear_background(Color::new(0.1, 0.1, 0.2, 1.0));
         
-        self.title.draw();
-        self.start_button.draw();
-        self.options_button.draw();
-        self.quit_button.draw();
+        self.title.draw_batched(batch);
+        self.start_button.draw_batched(batch);
+        self.options_button.draw_batched(batch);
+        self.quit_button.draw_batched(batch);
+        batch.draw();
     }
 
     fn name(&self) -> &str {
@@ -104,20 +108,28 @@ struct OptionsMenu {
     title: Label,
     sound_toggle: ToggleButton,
     back_button: Button,
+    hint: Label,
 }
 
 impl OptionsMenu {
     fn new() -> Self {
         let screen_center = vec2(screen_width() / 2.0, screen_height() / 2.0);
         
+        let mut title = Label::new("Options", vec2(screen_center.x, 100.0), 40, WHITE);
+        title.set_alignment(Alignment::Center);
+        
+        let mut hint = Label::new("", vec2(screen_center.x, screen_height() - 50.0), 20, LIGHTGRAY);
+        hint.set_alignment(Alignment::Center);
+        
         Self {
-            title: Label::new("Options", vec2(screen_center.x - 80.0, 100.0), 40, WHITE),
+            title,
             sound_toggle: ToggleButton::new(
                 Rect::new(screen_center.x - 100.0, screen_center.y - 50.0, 200.0, 40.0),
                 "Sound: ON",
                 "Sound: OFF"
             ),
             back_button: Button::new("Back", Rect::new(screen_center.x - 100.0, screen_center.y + 50.0, 200.0, 40.0)),
+            hint,
         }
     }
 }
@@ -139,26 +151,20 @@ impl Menu for OptionsMenu {
         MenuAction::None
     }
 
-    fn draw(&mut self, _batch: &mut DrawBatch) {
+    fn draw(&mut self, batch: &mut DrawBatch) {
         clear_background(Color::new(0.1, 0.2, 0.1, 1.0));
         
-        self.title.draw();
-        self.sound_toggle.draw();
-        self.back_button.draw();
+        self.title.draw_batched(batch);
+        self.sound_toggle.draw_batched(batch);
+        self.back_button.draw_batched(batch);
         
-        let hint = if self.sound_toggle.is_on() {
+        self.hint.set_text(if self.sound_toggle.is_on() {
             "Sound is currently enabled!"
         } else {
             "Sound is currently disabled"
-        };
-        
-        draw_text(
-            hint,
-            screen_width() / 2.0 - 100.0,
-            screen_height() - 50.0,
-            20.0,
-            LIGHTGRAY,
-        );
+        });
+        self.hint.draw_batched(batch);
+        batch.draw();
     }
 
     fn name(&self) -> &str {
@@ -198,6 +204,7 @@ async fn main() {
             MenuAction::Quit => {
                 break;
             }
+            MenuAction::Custom { .. } => {}
         }
         
         if let Some(menu) = menus.get_mut(&current_menu) {
@@ -1,6 +1,7 @@
 use macroquad::prelude::*;
 use gaymwtf_core::{
-    Tile, TileRegistry, Object, ObjectRegistry, Biome, BiomeRegistry, Chunk, World, DrawBatch, TILE_SIZE, CHUNK_SIZE, CHUNK_PIXELS
+    Tile, TileRegistry, Object, ObjectRegistry, Biome, BiomeRegistry, Chunk, World, DrawBatch,
+    WorldGenerator, GenContext, TileAnimation, SeededRng, TILE_SIZE, CHUNK_SIZE
 };
 
 // --- Concrete Tile Implementations ---
@@ -19,7 +20,6 @@ impl Tile for Air {
     fn set_pos(&mut self, pos: Vec2) { self.pos = pos; }
     fn set_size(&mut self, size: Vec2) { self.size = size; }
 
-    fn tick(&mut self, _dt: f32, _world: &mut World) {}
     fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) { }
 
     fn clone_box(&self) -> Box<dyn Tile> { Box::new(self.clone()) }
@@ -40,9 +40,57 @@ impl Tile for Stone {
     fn set_pos(&mut self, pos: Vec2) { self.pos = pos; }
     fn set_size(&mut self, size: Vec2) { self.size = size; }
 
-    fn tick(&mut self, _dt: f32, _world: &mut World) {}
     fn draw(&self, batch: &mut DrawBatch, pos: Vec2) {
-        batch.add(self.texture.clone(), pos, TILE_SIZE, None);
+        batch.add(self.texture.clone(), pos, 1.0, None);
+    }
+
+    fn clone_box(&self) -> Box<dyn Tile> { Box::new(self.clone()) }
+}
+
+#[derive(Clone)]
+struct Water {
+    pos: Vec2,
+    size: Vec2,
+    animation: TileAnimation,
+}
+
+impl Tile for Water {
+    fn get_type_tag(&self) -> &'static str { "water" }
+    fn get_pos(&self) -> Vec2 { self.pos }
+    fn get_size(&self) -> Vec2 { vec2(TILE_SIZE, TILE_SIZE) }
+
+    fn set_pos(&mut self, pos: Vec2) { self.pos = pos; }
+    fn set_size(&mut self, size: Vec2) { self.size = size; }
+
+    fn tick(&mut self, dt: f32, _world: &mut World) -> bool {
+        self.animation.advance(dt);
+        false
+    }
+    fn draw(&self, batch: &mut DrawBatch, pos: Vec2) {
+        if let Some(frame) = self.animation.current_frame() {
+            batch.add(frame.clone(), pos, 1.0, None);
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn Tile> { Box::new(self.clone()) }
+}
+
+#[derive(Clone)]
+struct Tree {
+    pos: Vec2,
+    texture: Texture2D,
+}
+
+impl Tile for Tree {
+    fn get_type_tag(&self) -> &'static str { "tree" }
+    fn get_pos(&self) -> Vec2 { self.pos }
+    fn get_size(&self) -> Vec2 { vec2(TILE_SIZE, TILE_SIZE) }
+
+    fn set_pos(&mut self, pos: Vec2) { self.pos = pos; }
+
+    fn may_pass(&self) -> bool { false }
+    fn draw(&self, batch: &mut DrawBatch, pos: Vec2) {
+        batch.add(self.texture.clone(), pos, 1.0, None);
     }
 
     fn clone_box(&self) -> Box<dyn Tile> { Box::new(self.clone()) }
@@ -84,10 +132,10 @@ impl Object for Mob {
     fn set_velocity(&mut self, velocity: Vec2) { self.velocity = velocity; }
 
     fn draw(&self, batch: &mut DrawBatch) {
-        batch.add(self.texture.clone(), self.pos, TILE_SIZE, Some(self.size));
+        batch.add(self.texture.clone(), self.pos, 1.0, Some(self.size));
     }
 
-    fn tick(&mut self, dt: f32, _world: &mut World) {
+    fn tick(&mut self, dt: f32, _world: &mut World) -> bool {
         self.move_timer += dt;
         self.direction_change_timer += dt;
 
@@ -107,6 +155,7 @@ impl Object for Mob {
         }
 
         self.pos += self.velocity;
+        self.velocity != Vec2::ZERO
     }
 
     fn clone_box(&self) -> Box<dyn Object> { Box::new(self.clone()) }
@@ -119,39 +168,77 @@ struct Plains;
 
 impl Biome for Plains {
     fn get_type_tag(&self) -> &'static str { "plains" }
-    fn is_suitable(&self, _height: f64, _moisture: f64, _temperature: f64) -> bool { true }
+    fn is_suitable(&self, height: f64, _moisture: f64, _temperature: f64) -> bool { height < 0.5 }
     fn get_ground_tile_type(&self) -> &'static str { "stone" }
     fn get_spawnable_objects(&self) -> Vec<(&'static str, f32)> { vec![("mob", 0.05)] }
     fn clone_box(&self) -> Box<dyn Biome> { Box::new(self.clone()) }
 }
 
-fn generate_chunk(pos: Vec2, tile_registry: &TileRegistry, biome_registry: &BiomeRegistry, object_registry: &ObjectRegistry) -> Chunk {
-    let mut chunk = Chunk::new(pos);
-    let biome = biome_registry.find_biome(0.0, 0.0, 0.0).unwrap(); 
+#[derive(Clone)]
+struct Forest {
+    tree: Tree,
+}
 
-    let chunk_world_pos = pos * CHUNK_PIXELS;
+impl Biome for Forest {
+    fn get_type_tag(&self) -> &'static str { "forest" }
+    fn is_suitable(&self, height: f64, _moisture: f64, _temperature: f64) -> bool { height >= 0.5 }
+    fn get_ground_tile_type(&self) -> &'static str { "stone" }
+    fn get_spawnable_objects(&self) -> Vec<(&'static str, f32)> { vec![] }
+
+    fn decorate(&self, chunk: &mut Chunk, rng_seed: u64) {
+        let mut rng = SeededRng::new(rng_seed);
+        let clusters = rng.next_range(2, 6);
+        for _ in 0..clusters {
+            let left = rng.next_range(0, CHUNK_SIZE as i32 - 2) as usize;
+            let top = rng.next_range(0, CHUNK_SIZE as i32 - 2) as usize;
+            for y in top..top + 3 {
+                for x in left..left + 3 {
+                    let _ = chunk.set_tile(x, y, Box::new(self.tree.clone()));
+                }
+            }
+        }
+    }
 
-    for y in 0..CHUNK_SIZE {
-        for x in 0..CHUNK_SIZE {
-            let tile_type = biome.get_ground_tile_type();
-            let mut tile = tile_registry.create_tile_by_id(tile_type).unwrap();
+    fn clone_box(&self) -> Box<dyn Biome> { Box::new(self.clone()) }
+}
+
+// --- World Generator ---
+
+struct FlatGenerator;
+
+impl WorldGenerator for FlatGenerator {
+    fn generate(&self, pos: Vec2, ctx: &GenContext) -> Chunk {
+        let mut chunk = ctx.new_chunk(pos);
+        let height = gaymwtf_core::utils::noise::sample(ctx.seed, pos.x as f64 * 0.5, pos.y as f64 * 0.5);
+        // Heights no registered biome accepts fall back to plains
+        let biome: &dyn Biome = ctx.biome_registry.find_biome(height, 0.0, 0.0).unwrap_or(&Plains);
+
+        let chunk_world_pos = pos * ctx.config.chunk_pixels();
+        let tile_size = ctx.config.tile_size;
+
+        for y in 0..ctx.config.chunk_size {
+            for x in 0..ctx.config.chunk_size {
+                let tile_type = if (x + y) % 7 == 0 { "water" } else { biome.get_ground_tile_type() };
+                let mut tile = ctx.tile_registry.create_tile_by_id(tile_type).unwrap();
 
-            let tile_pos = chunk_world_pos + vec2(x as f32 * TILE_SIZE, y as f32 * TILE_SIZE);
-            tile.set_pos(tile_pos);
-            chunk.tiles.push(tile); 
+                let tile_pos = chunk_world_pos + vec2(x as f32, y as f32) * tile_size;
+                tile.set_pos(tile_pos);
+                chunk.tiles.push(tile); 
 
-            for (object_type, chance) in biome.get_spawnable_objects() {
-                let should_spawn = ((x + y * CHUNK_SIZE) as f32 % 100.0) / 100.0 < chance;
-                if should_spawn {
-                    if let Some(mut obj) = object_registry.create_object_by_id(object_type) {
-                        obj.set_pos(tile_pos);
-                        chunk.objects.push(obj);
+                for (object_type, chance) in biome.get_spawnable_objects() {
+                    let should_spawn = ctx.roll(pos, x, y) < chance;
+                    if should_spawn {
+                        if let Some(mut obj) = ctx.object_registry.create_object_by_id(object_type) {
+                            obj.set_pos(tile_pos);
+                            chunk.objects.push(obj);
+                        }
                     }
                 }
             }
         }
+        ctx.decorate(&mut chunk, biome);
+        chunk
     }
-    chunk
 }
 
 async fn setup() -> World {
@@ -161,6 +248,19 @@ async fn setup() -> World {
     let stone_texture = Texture2D::from_rgba8(16, 16, &[128; 16 * 16 * 4]);
     tile_registry.register(Stone { pos: Vec2::ZERO, size: Vec2::new(TILE_SIZE, TILE_SIZE), texture: stone_texture });
 
+    let water_frames = (0..4)
+        .map(|frame| {
+            let blue = 160 + frame * 30;
+            let pixels: Vec<u8> = [40, 80, blue, 255].repeat(16 * 16);
+            Texture2D::from_rgba8(16, 16, &pixels)
+        })
+        .collect();
+    let water_animation = TileAnimation::new(water_frames, 0.25);
+    tile_registry.register(Water { pos: Vec2::ZERO, size: Vec2::new(TILE_SIZE, TILE_SIZE), animation: water_animation });
+
+    let tree = Tree { pos: Vec2::ZERO, texture: Texture2D::from_rgba8(16, 16, &[30, 110, 40, 255].repeat(16 * 16)) };
+    tile_registry.register(tree.clone());
+
     let mut object_registry = ObjectRegistry::new();
 
     let mob_texture = Texture2D::from_rgba8(16, 16, &[255; 16 * 16 * 4]);
@@ -168,16 +268,11 @@ async fn setup() -> World {
 
     let mut biome_registry = BiomeRegistry::new();
     biome_registry.register(Plains);
+    biome_registry.register(Forest { tree });
 
     let mut world = World::new("test-world", tile_registry, object_registry, biome_registry);
-
-    for y in -2..=2 {
-        for x in -2..=2 {
-            let chunk_pos = vec2(x as f32, y as f32);
-            let chunk = generate_chunk(chunk_pos, &world.tile_registry, &world.biome_registry, &world.object_registry);
-            world.add_chunk(chunk);
-        }
-    }
+    world.set_generator(Box::new(FlatGenerator));
+    world.set_stats_timing(true);
 
     world
 }
@@ -208,6 +303,24 @@ async fn main() {
         draw_text(&format!("FPS: {}", get_fps()), 10.0, 20.0, 20.0, WHITE);
         draw_text(&format!("Chunks: {}", world.chunks.len()), 10.0, 40.0, 20.0, WHITE);
 
+        let stats = world.stats();
+        draw_text(
+            &format!(
+                "Objects: {}  Tiles: {}  Pairs: {}",
+                stats.active_objects, stats.visible_tiles, stats.collision_pairs
+            ),
+            10.0, 60.0, 20.0, WHITE,
+        );
+        draw_text(
+            &format!(
+                "Tick: {:.2}ms  Collision: {:.2}ms  Draw: {:.2}ms",
+                stats.tick_time.as_secs_f64() * 1000.0,
+                stats.collision_time.as_secs_f64() * 1000.0,
+                stats.draw_time.as_secs_f64() * 1000.0
+            ),
+            10.0, 80.0, 20.0, WHITE,
+        );
+
         next_frame().await
     }
 }
\ No newline at end of file
@@ -1,3 +1,8 @@
+use macroquad::math::Vec2;
+
+use crate::utils::hash::hash2;
+use crate::Chunk;
+
 /// Represents a biome in the game world.
 ///
 /// A biome defines the environmental characteristics of a region, including
@@ -20,15 +25,93 @@ pub trait Biome: Send + Sync {
     
     /// Returns a list of object types that can spawn in this biome.
     fn get_spawnable_objects(&self) -> Vec<(&'static str, f32)>;
+
+    /// Returns the relative weight of this biome when several biomes are suitable.
+    ///
+    /// Used by `BiomeRegistry::find_biome_weighted`. Defaults to `1.0`.
+    fn get_weight(&self) -> f32 {
+        1.0
+    }
+
+    /// Stamps decorations and structures into a freshly generated chunk.
+    ///
+    /// Called by `GenContext::decorate` after the ground has been filled, so a
+    /// biome can place multi-tile structures such as trees or rock clusters with
+    /// `Chunk::set_tile`. The chunk covers the world area starting at
+    /// `chunk.pos * chunk.config().chunk_pixels()`. Does nothing by default.
+    ///
+    /// - `chunk`: The chunk to decorate.
+    /// - `rng_seed`: Seed derived from the world seed and the chunk position, pass it
+    ///   to `SeededRng::new` so the same chunk is always decorated the same way.
+    fn decorate(&self, _chunk: &mut Chunk, _rng_seed: u64) {}
     
     /// Creates a boxed clone of this biome.
     fn clone_box(&self) -> Box<dyn Biome>;
 }
 
+/// Environmental ranges a biome accepts, used instead of `Biome::is_suitable`.
+///
+/// Each range is inclusive and given as `(min, max)` on the same 0.0 to 1.0
+/// scale as the values passed to `BiomeRegistry::find_biome`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiomeBounds {
+    /// Accepted height range.
+    pub height_range: (f64, f64),
+    /// Accepted moisture range.
+    pub moisture_range: (f64, f64),
+    /// Accepted temperature range.
+    pub temperature_range: (f64, f64),
+}
+
+impl BiomeBounds {
+    /// Checks whether the given conditions lie within all three ranges.
+    ///
+    /// - `height`: The height value at the location.
+    /// - `moisture`: The moisture level at the location.
+    /// - `temperature`: The temperature at the location.
+    pub fn contains(&self, height: f64, moisture: f64, temperature: f64) -> bool {
+        in_range(height, self.height_range)
+            && in_range(moisture, self.moisture_range)
+            && in_range(temperature, self.temperature_range)
+    }
+
+    /// Returns the distance from the given conditions to the nearest point inside the bounds.
+    ///
+    /// - `height`: The height value at the location.
+    /// - `moisture`: The moisture level at the location.
+    /// - `temperature`: The temperature at the location.
+    ///
+    /// Returns `0.0` if the conditions lie within the bounds.
+    pub fn distance(&self, height: f64, moisture: f64, temperature: f64) -> f64 {
+        let dh = range_distance(height, self.height_range);
+        let dm = range_distance(moisture, self.moisture_range);
+        let dt = range_distance(temperature, self.temperature_range);
+        (dh * dh + dm * dm + dt * dt).sqrt()
+    }
+}
+
+/// Checks whether a value lies within an inclusive range.
+fn in_range(value: f64, range: (f64, f64)) -> bool {
+    value >= range.0 && value <= range.1
+}
+
+/// Returns how far a value lies outside an inclusive range, `0.0` if inside.
+fn range_distance(value: f64, range: (f64, f64)) -> f64 {
+    if value < range.0 {
+        range.0 - value
+    } else if value > range.1 {
+        value - range.1
+    } else {
+        0.0
+    }
+}
+
 /// A registry for managing different biome types.
 pub struct BiomeRegistry {
     /// Collection of registered biome prototypes.
     prototypes: Vec<Box<dyn Biome>>,
+    /// Environmental ranges of each prototype, `None` for biomes using `is_suitable`.
+    bounds: Vec<Option<BiomeBounds>>,
 }
 
 impl Default for BiomeRegistry {
@@ -42,6 +125,7 @@ impl BiomeRegistry {
     pub fn new() -> Self {
         Self {
             prototypes: Vec::new(),
+            bounds: Vec::new(),
         }
     }
 
@@ -50,6 +134,24 @@ impl BiomeRegistry {
     /// - `biome`: The biome instance to register.
     pub fn register<B: Biome + 'static>(&mut self, biome: B) {
         self.prototypes.push(Box::new(biome));
+        self.bounds.push(None);
+    }
+
+    /// Registers a new biome type whose suitability is given by environmental ranges.
+    ///
+    /// - `biome`: The biome instance to register.
+    /// - `bounds`: The ranges the biome accepts. `Biome::is_suitable` is not consulted.
+    pub fn register_bounded<B: Biome + 'static>(&mut self, biome: B, bounds: BiomeBounds) {
+        self.prototypes.push(Box::new(biome));
+        self.bounds.push(Some(bounds));
+    }
+
+    /// Checks whether the biome at the given index is suitable for the given conditions.
+    fn is_suitable(&self, index: usize, height: f64, moisture: f64, temperature: f64) -> bool {
+        match &self.bounds[index] {
+            Some(bounds) => bounds.contains(height, moisture, temperature),
+            None => self.prototypes[index].is_suitable(height, moisture, temperature),
+        }
     }
 
     /// Finds the most suitable biome for the given environmental conditions.
@@ -58,13 +160,126 @@ impl BiomeRegistry {
     /// - `moisture`: The moisture level (0.0 to 1.0) at the location.
     /// - `temperature`: The temperature (0.0 to 1.0) at the location.
     ///
-    /// Returns a reference to the most suitable biome, or `None` if no suitable biome is found.
+    /// If no biome is suitable, the biome registered with `register_bounded` whose
+    /// ranges lie closest to the conditions is returned instead.
+    ///
+    /// Returns a reference to the most suitable biome, or `None` if no suitable biome is found
+    /// and no bounded biome is registered.
     pub fn find_biome(&self, height: f64, moisture: f64, temperature: f64) -> Option<&dyn Biome> {
-        for biome in &self.prototypes {
-            if biome.is_suitable(height, moisture, temperature) {
+        for (index, biome) in self.prototypes.iter().enumerate() {
+            if self.is_suitable(index, height, moisture, temperature) {
                 return Some(biome.as_ref());
             }
         }
-        None
+
+        self.bounds
+            .iter()
+            .enumerate()
+            .filter_map(|(index, bounds)| bounds.map(|bounds| (index, bounds.distance(height, moisture, temperature))))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| self.prototypes[index].as_ref())
+    }
+
+    /// Picks one of the biomes suitable for the given conditions, weighted by `Biome::get_weight`.
+    ///
+    /// - `height`: The height value (0.0 to 1.0) at the location.
+    /// - `moisture`: The moisture level (0.0 to 1.0) at the location.
+    /// - `temperature`: The temperature (0.0 to 1.0) at the location.
+    /// - `pos`: The location, used to seed the pick so the same location always gets the same biome.
+    /// - `seed`: The world seed, e.g. `GenContext::seed`, so each world picks differently.
+    ///
+    /// Unlike `find_biome`, the result does not depend on registration order.
+    ///
+    /// Returns a reference to the picked biome, or `None` if no suitable biome is found.
+    pub fn find_biome_weighted(&self, height: f64, moisture: f64, temperature: f64, pos: Vec2, seed: u64) -> Option<&dyn Biome> {
+        let suitable: Vec<&dyn Biome> = self.prototypes
+            .iter()
+            .enumerate()
+            .filter(|(index, biome)| self.is_suitable(*index, height, moisture, temperature) && biome.get_weight() > 0.0)
+            .map(|(_, biome)| biome.as_ref())
+            .collect();
+
+        let total_weight: f32 = suitable.iter().map(|biome| biome.get_weight()).sum();
+        if suitable.is_empty() || total_weight <= 0.0 {
+            return None;
+        }
+
+        let roll = hash2(seed, pos.x.floor() as i32, pos.y.floor() as i32) as f32 / u32::MAX as f32 * total_weight;
+        let mut accumulated = 0.0;
+        for biome in &suitable {
+            accumulated += biome.get_weight();
+            if roll < accumulated {
+                return Some(*biome);
+            }
+        }
+        suitable.last().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A biome that is suitable everywhere
+    #[derive(Clone)]
+    struct Weighted {
+        tag: &'static str,
+        weight: f32,
+    }
+
+    impl Biome for Weighted {
+        fn get_type_tag(&self) -> &'static str {
+            self.tag
+        }
+
+        fn is_suitable(&self, _height: f64, _moisture: f64, _temperature: f64) -> bool {
+            true
+        }
+
+        fn get_ground_tile_type(&self) -> &'static str {
+            "grass"
+        }
+
+        fn get_spawnable_objects(&self) -> Vec<(&'static str, f32)> {
+            Vec::new()
+        }
+
+        fn get_weight(&self) -> f32 {
+            self.weight
+        }
+
+        fn clone_box(&self) -> Box<dyn Biome> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn registry() -> BiomeRegistry {
+        let mut registry = BiomeRegistry::new();
+        registry.register(Weighted { tag: "rare", weight: 1.0 });
+        registry.register(Weighted { tag: "common", weight: 3.0 });
+        registry.register(Weighted { tag: "never", weight: 0.0 });
+        registry
+    }
+
+    fn picks(registry: &BiomeRegistry, seed: u64) -> Vec<&'static str> {
+        (0..4000)
+            .map(|index| Vec2::new((index % 64) as f32, (index / 64) as f32))
+            .map(|pos| registry.find_biome_weighted(0.5, 0.5, 0.5, pos, seed).unwrap().get_type_tag())
+            .collect()
+    }
+
+    #[test]
+    fn weighted_picks_follow_the_weights() {
+        let picks = picks(&registry(), 7);
+        let common = picks.iter().filter(|&&tag| tag == "common").count() as f32 / picks.len() as f32;
+        assert!((common - 0.75).abs() < 0.03, "{common}");
+        assert!(!picks.contains(&"never"));
+    }
+
+    #[test]
+    fn weighted_picks_are_deterministic_per_seed() {
+        let registry = registry();
+        assert_eq!(picks(&registry, 7), picks(&registry, 7));
+        assert_ne!(picks(&registry, 7), picks(&registry, 8));
     }
 }
@@ -1,19 +1,36 @@
-use macroquad::math::{vec2, Vec2};
+use std::collections::HashMap;
+
+use macroquad::math::{vec2, Rect, Vec2};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     core::save::Vec2Save,
     Object, ObjectRegistry, SerializableObject, SerializableTile, Tile, TileRegistry, World,
     log_chunk,
-    DrawBatch, CHUNK_PIXELS, CHUNK_SIZE, TILE_SIZE, OBJECT_ACTIVATION_MARGIN,
+    DrawBatch, WorldConfig, OBJECT_INDEX_THRESHOLD,
 };
 
+/// Runs of `(palette index, length)` over a chunk's tiles, row by row
+type TileRuns = Vec<(u16, u16)>;
+
+/// A tile of a chunk, found by its index in `tiles` or `overlays`
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum TileSlot {
+    /// A tile of the grid
+    Ground(usize),
+    /// An overlay tile
+    Overlay(usize),
+}
+
 /// A fixed-size segment of the game world that contains tiles and objects.
 /// Chunks are used to efficiently manage and render the game world by dividing it into smaller,
 /// more manageable pieces. Each chunk contains its own set of visible tiles and active objects.
 pub struct Chunk {
     /// Collection of all tiles in this chunk
     pub tiles: Vec<Box<dyn Tile>>,
+    /// Additional tiles drawn above `tiles`, paired with their layer.
+    /// Kept sorted by layer; higher layers are drawn on top.
+    pub overlays: Vec<(u8, Box<dyn Tile>)>,
     /// Collection of all objects currently in this chunk
     pub objects: Vec<Box<dyn Object>>,
     /// Position of this chunk in chunk coordinates (not world coordinates)
@@ -24,18 +41,50 @@ pub struct Chunk {
     visible_tiles: Vec<usize>,
     /// Indices of objects that are currently active (in or near the viewport)
     active_objects: Vec<usize>,
+    /// Indices of the active objects still waiting for their tick, last one first
+    tick_queue: Vec<usize>,
+    /// Index of the object taken out for its tick, if any
+    ticking: Option<usize>,
+    /// Grid cell each object was last recorded in, by object index
+    object_cells: Vec<(i32, i32)>,
+    /// Indices of the objects recorded in each grid cell
+    object_grid: HashMap<(i32, i32), Vec<usize>>,
+    /// Whether the object grid must be rebuilt before it is used
+    object_grid_stale: bool,
+    /// Neighbor mask of every tile, filled in by the world for connected tiles
+    pub(crate) neighbor_masks: Vec<u8>,
+    /// Whether the chunk changed since it was last saved
+    dirty: bool,
+    /// Tile size, chunk size and activation margin of the chunk's world
+    config: WorldConfig,
 }
 
 /// Serializable data structure representing a chunk's state.
 /// Used for saving and loading chunk data from disk.
+///
+/// `T` is the representation of a single serialized tile or object:
+/// a JSON `String` for JSON saves and bincode bytes for binary saves.
 #[derive(Serialize, Deserialize)]
-pub struct ChunkData {
+pub struct ChunkData<T = String> {
     /// Position of the chunk in chunk coordinates
     pub pos: Vec2Save,
-    /// Serialized data of all tiles in this chunk
-    pub tiles: Vec<String>,
+    /// Serialized data of all tiles in this chunk, one entry per tile
+    ///
+    /// Only written by older versions; tiles are now stored in `tile_palette`
+    /// and `tile_runs`. Still read when present.
+    #[serde(default = "Vec::new")]
+    pub tiles: Vec<T>,
+    /// Serialized data of all overlay tiles in this chunk, paired with their layer
+    #[serde(default = "Vec::new")]
+    pub overlays: Vec<(u8, T)>,
     /// Serialized data of all objects in this chunk
-    pub objects: Vec<String>,
+    pub objects: Vec<T>,
+    /// Serialized data of each distinct tile in this chunk, positioned at the origin
+    #[serde(default = "Vec::new")]
+    pub tile_palette: Vec<T>,
+    /// Tiles row by row as runs of `(palette index, length)`
+    #[serde(default = "Vec::new")]
+    pub tile_runs: Vec<(u16, u16)>,
 }
 
 impl Chunk {
@@ -43,21 +92,77 @@ impl Chunk {
     /// 
     /// - `pos`: The position of the chunk in chunk coordinates
     /// 
+    /// Uses the default `WorldConfig`.
     pub fn new(pos: Vec2) -> Self {
+        Self::with_config(pos, WorldConfig::default())
+    }
+
+    /// Creates a new, empty chunk at the specified position for a world with the given config
+    /// 
+    /// - `pos`: The position of the chunk in chunk coordinates
+    /// - `config`: Config of the world the chunk belongs to, see `World::config`
+    pub fn with_config(pos: Vec2, config: WorldConfig) -> Self {
         log_chunk!(log::Level::Debug, "Creating new chunk at {:?}", pos);
-        let min = pos * CHUNK_PIXELS;
-        let max = min + vec2(CHUNK_PIXELS, CHUNK_PIXELS);
+        let min = pos * config.chunk_pixels();
+        let max = min + Vec2::splat(config.chunk_pixels());
 
         Self {
-            tiles: Vec::with_capacity(CHUNK_SIZE * CHUNK_SIZE),
+            tiles: Vec::with_capacity(config.chunk_size * config.chunk_size),
+            overlays: Vec::new(),
             objects: Vec::new(),
             pos,
             bounds: (min, max),
             visible_tiles: Vec::new(),
             active_objects: Vec::new(),
+            tick_queue: Vec::new(),
+            ticking: None,
+            object_cells: Vec::new(),
+            object_grid: HashMap::new(),
+            object_grid_stale: true,
+            neighbor_masks: Vec::new(),
+            dirty: true,
+            config,
         }
     }
 
+    /// Returns the tile size, chunk size and activation margin this chunk uses
+    pub fn config(&self) -> &WorldConfig {
+        &self.config
+    }
+
+    /// Returns whether the chunk changed since it was last saved
+    ///
+    /// New chunks start dirty. The flag is set when tiles or objects are changed
+    /// through the chunk and world APIs, when an object's or tile's `tick` returns
+    /// `true`, and when a collision changes an object.
+    /// Mutating the public `tiles` and `objects` vectors directly does not set it;
+    /// call `mark_dirty` after doing so.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Marks the chunk as changed so it is written on the next save
+    ///
+    /// Also makes the next update recheck where every object is, so objects moved
+    /// or replaced by hand are activated correctly.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+        self.object_grid_stale = true;
+    }
+
+    /// Marks the chunk as changed without rechecking where its objects are
+    ///
+    /// Used for changes that do not move objects between cells of the object grid,
+    /// such as a tick or a collision changing an object.
+    pub(crate) fn mark_changed(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Marks the chunk as saved
+    pub fn clear_dirty(&mut self) {
+        self.dirty = false;
+    }
+
     /// Updates the chunk's state
     /// 
     /// - `world`: Reference to the game world
@@ -65,24 +170,165 @@ impl Chunk {
     /// - `screen_size`: Size of the game window
     /// - `dt`: Time elapsed since the last frame in seconds
     pub fn update(&mut self, world: &mut World, camera_pos: Vec2, screen_size: Vec2, dt: f32) {
-        if !self.is_visible(camera_pos, screen_size) {
+        self.update_in(world, screen_rect(camera_pos, screen_size), dt);
+    }
+
+    /// Updates the objects and tiles of this chunk that lie in a world-space area
+    ///
+    /// - `world`: Reference to the game world
+    /// - `area`: Visible area in world coordinates, e.g. `Viewport::bounds`
+    /// - `dt`: Time elapsed since the last frame in seconds
+    pub fn update_in(&mut self, world: &mut World, area: Rect, dt: f32) {
+        if !self.prepare_update(area) {
             return;
         }
 
-        self.update_active_objects(camera_pos, screen_size);
-        self.update_visible_tiles(camera_pos, screen_size);
-
         for &obj_index in &self.active_objects {
             if let Some(obj) = self.objects.get_mut(obj_index) {
-                obj.tick(dt, world);
+                self.dirty |= obj.tick(dt, world);
             }
         }
 
+        self.tick_tiles(world, dt);
+    }
+
+    /// Finds the objects and tiles to update in a world-space area
+    ///
+    /// - `area`: Visible area in world coordinates
+    ///
+    /// Returns `false` if the chunk is not visible in the area and nothing should be updated.
+    /// The active objects and visible tiles are cleared then, so a chunk that left the
+    /// area does not keep reporting those of an earlier update.
+    pub(crate) fn prepare_update(&mut self, area: Rect) -> bool {
+        if !self.is_visible_in(area) {
+            self.active_objects.clear();
+            self.visible_tiles.clear();
+            return false;
+        }
+
+        self.update_active_objects(area);
+        self.update_visible_tiles(area);
+        true
+    }
+
+    /// Queues the objects found active by the last update for `take_next_object`
+    pub(crate) fn queue_object_ticks(&mut self) {
+        self.tick_queue.clear();
+        self.tick_queue.extend(self.active_objects.iter().rev());
+    }
+
+    /// Takes the next queued object out of the chunk so it can be ticked while the
+    /// chunk stays in the world
+    ///
+    /// A placeholder takes the object's place until `restore_object` puts it back,
+    /// so the indices of the other objects stay valid. Objects removed from the
+    /// chunk while queued are skipped; objects added meanwhile are not ticked.
+    ///
+    /// Returns the object, or `None` if no objects are left to tick
+    pub(crate) fn take_next_object(&mut self) -> Option<Box<dyn Object>> {
+        let index = self.tick_queue.pop()?;
+        self.ticking = Some(index);
+        Some(std::mem::replace(&mut self.objects[index], Box::new(Ticking)))
+    }
+
+    /// Puts back the object taken out with `take_next_object`
+    ///
+    /// - `obj`: The object
+    ///
+    /// Returns the object as an error if the placeholder was removed during the
+    /// tick, e.g. by `World::despawn_objects_where`; it then counts as despawned
+    pub(crate) fn restore_object(&mut self, obj: Box<dyn Object>) -> Result<(), Box<dyn Object>> {
+        match self.ticking.take() {
+            Some(index) => {
+                self.objects[index] = obj;
+                Ok(())
+            }
+            None => Err(obj),
+        }
+    }
+
+    /// Ticks the visible tiles and overlays that do not read their neighbors
+    ///
+    /// - `world`: Reference to the game world
+    /// - `dt`: Time elapsed since the last frame in seconds
+    ///
+    /// Returns the tiles that were skipped because their `reads_neighbors` returns `true`
+    pub(crate) fn tick_own_tiles(&mut self, world: &mut World, dt: f32) -> Vec<TileSlot> {
+        self.tick_tiles_where(world, dt, |tile| !tile.reads_neighbors())
+    }
+
+    /// Takes a tile out of the chunk so it can be ticked while the chunk stays in the world
+    ///
+    /// - `slot`: The tile to take
+    ///
+    /// A copy of the tile takes its place until `restore_tile` puts it back, so
+    /// neighbors looking at the slot meanwhile see the tile as it was before its tick.
+    ///
+    /// Returns the tile, or `None` if there is no such tile
+    pub(crate) fn take_tile(&mut self, slot: TileSlot) -> Option<Box<dyn Tile>> {
+        let tile = match slot {
+            TileSlot::Ground(index) => self.tiles.get_mut(index)?,
+            TileSlot::Overlay(index) => &mut self.overlays.get_mut(index)?.1,
+        };
+        let copy = tile.clone_box();
+        Some(std::mem::replace(tile, copy))
+    }
+
+    /// Puts back a tile taken out with `take_tile`
+    ///
+    /// - `slot`: The slot the tile was taken from
+    /// - `tile`: The tile
+    ///
+    /// The tile replaces the copy at its position, so changes made to the copy
+    /// during the tick are lost. If the copy was replaced by a tile of another
+    /// type or removed in the meantime, that change is kept and the tile is dropped.
+    pub(crate) fn restore_tile(&mut self, slot: TileSlot, tile: Box<dyn Tile>) {
+        let (type_tag, pos) = (tile.get_type_tag(), tile.get_pos());
+        let is_copy = |other: &dyn Tile| other.get_type_tag() == type_tag && other.get_pos() == pos;
+        let target = match slot {
+            TileSlot::Ground(index) => self.tiles.get_mut(index).filter(|other| is_copy(other.as_ref())),
+            TileSlot::Overlay(_) => self.overlays.iter_mut().map(|(_, other)| other).find(|other| is_copy(other.as_ref())),
+        };
+        if let Some(target) = target {
+            *target = tile;
+        }
+    }
+
+    /// Ticks the visible tiles and all overlays of this chunk
+    ///
+    /// - `world`: Reference to the game world
+    /// - `dt`: Time elapsed since the last frame in seconds
+    fn tick_tiles(&mut self, world: &mut World, dt: f32) {
+        self.tick_tiles_where(world, dt, |_| true);
+    }
+
+    /// Ticks the visible tiles and all overlays of this chunk that pass a filter
+    ///
+    /// - `world`: Reference to the game world
+    /// - `dt`: Time elapsed since the last frame in seconds
+    /// - `include`: Returns `true` for the tiles to tick
+    ///
+    /// Returns the tiles that were skipped
+    fn tick_tiles_where(&mut self, world: &mut World, dt: f32, include: impl Fn(&dyn Tile) -> bool) -> Vec<TileSlot> {
+        let mut skipped = Vec::new();
         for &tile_index in &self.visible_tiles {
             if let Some(tile) = self.tiles.get_mut(tile_index) {
-                tile.tick(dt, world);
+                if include(tile.as_ref()) {
+                    self.dirty |= tile.tick(dt, world);
+                } else {
+                    skipped.push(TileSlot::Ground(tile_index));
+                }
             }
         }
+
+        for (index, (_, tile)) in self.overlays.iter_mut().enumerate() {
+            if include(tile.as_ref()) {
+                self.dirty |= tile.tick(dt, world);
+            } else {
+                skipped.push(TileSlot::Overlay(index));
+            }
+        }
+        skipped
     }
 
     /// Draws all visible tiles in this chunk
@@ -91,21 +337,72 @@ impl Chunk {
     /// - `screen_size`: Size of the game window
     /// - `batch`: The draw batch to add drawing commands to
     pub fn draw_tiles(&mut self, camera_pos: Vec2, screen_size: Vec2, batch: &mut DrawBatch) {
-        if !self.is_visible(camera_pos, screen_size) {
+        self.draw_tiles_in(screen_rect(camera_pos, screen_size), batch);
+    }
+
+    /// Draws the tiles of this chunk that lie in a world-space area
+    /// 
+    /// - `area`: Visible area in world coordinates, e.g. `Viewport::bounds`
+    /// - `batch`: The draw batch to add drawing commands to
+    pub fn draw_tiles_in(&mut self, area: Rect, batch: &mut DrawBatch) {
+        if !self.is_visible_in(area) {
             return;
         }
 
-        self.update_visible_tiles(camera_pos, screen_size);
+        self.update_visible_tiles(area);
 
         for &tile_index in &self.visible_tiles {
             let tile = &self.tiles[tile_index];
-            tile.draw(batch, tile.get_pos());
+            if tile.is_connected() {
+                let mask = self.neighbor_masks.get(tile_index).copied().unwrap_or(0);
+                tile.draw_connected(batch, tile.get_pos(), mask);
+            } else {
+                tile.draw(batch, tile.get_pos());
+            }
+        }
+    }
+
+    /// Draws the overlay tiles of a single layer that are visible on screen
+    /// 
+    /// - `layer`: The layer to draw
+    /// - `camera_pos`: Current camera position in world coordinates
+    /// - `screen_size`: Size of the game window
+    /// - `batch`: The draw batch to add drawing commands to
+    pub fn draw_overlays(&self, layer: u8, camera_pos: Vec2, screen_size: Vec2, batch: &mut DrawBatch) {
+        self.draw_overlays_in(layer, screen_rect(camera_pos, screen_size), batch);
+    }
+
+    /// Draws the overlay tiles of a single layer that lie in a world-space area
+    /// 
+    /// - `layer`: The layer to draw
+    /// - `area`: Visible area in world coordinates, e.g. `Viewport::bounds`
+    /// - `batch`: The draw batch to add drawing commands to
+    pub fn draw_overlays_in(&self, layer: u8, area: Rect, batch: &mut DrawBatch) {
+        let (screen_min, screen_max) = (area.point(), area.point() + area.size());
+
+        for (_, tile) in self.overlays.iter().filter(|(tile_layer, _)| *tile_layer == layer) {
+            let pos = tile.get_pos();
+            let size = tile.get_size();
+            if pos.x + size.x >= screen_min.x && pos.x <= screen_max.x && pos.y + size.y >= screen_min.y && pos.y <= screen_max.y {
+                tile.draw(batch, pos);
+            }
         }
     }
 
+    /// Returns the distinct overlay layers used in this chunk in ascending order
+    pub fn overlay_layers(&self) -> Vec<u8> {
+        let mut layers: Vec<u8> = self.overlays.iter().map(|(layer, _)| *layer).collect();
+        layers.dedup();
+        layers
+    }
+
     /// Draws all active objects in this chunk
     /// 
     /// - `batch`: The draw batch to add drawing commands to
+    ///
+    /// Active objects are chosen by their position and the activation margin, so
+    /// large objects positioned outside the margin are skipped even if part of them
+    /// is visible. Use `draw_objects_in` to cull by the objects' full bounds instead.
     pub fn draw_objects(&mut self, batch: &mut DrawBatch) {
         for &obj_index in &self.active_objects {
             if let Some(obj) = self.objects.get(obj_index) {
@@ -114,6 +411,36 @@ impl Chunk {
         }
     }
 
+    /// Draws the objects of this chunk whose bounds overlap a world-space area
+    /// 
+    /// - `area`: Visible area in world coordinates, e.g. `Viewport::bounds`
+    /// - `batch`: The draw batch to add drawing commands to
+    pub fn draw_objects_in(&self, area: Rect, batch: &mut DrawBatch) {
+        for obj in self.objects_in(area) {
+            obj.draw(batch);
+        }
+    }
+
+    /// Returns an iterator over the objects whose bounds overlap a world-space area
+    /// 
+    /// - `area`: Area in world coordinates
+    ///
+    /// Objects are tested by their full bounds, independent of which objects are active.
+    pub fn objects_in(&self, area: Rect) -> impl Iterator<Item = &dyn Object> {
+        self.objects
+            .iter()
+            .map(|obj| obj.as_ref())
+            .filter(move |obj| Rect::new(obj.get_pos().x, obj.get_pos().y, obj.get_size().x, obj.get_size().y).overlaps(&area))
+    }
+
+    /// Returns an iterator over the objects that are currently active
+    pub fn iter_active_objects(&self) -> impl Iterator<Item = &dyn Object> {
+        self.active_objects
+            .iter()
+            .filter_map(|&obj_index| self.objects.get(obj_index))
+            .map(|obj| obj.as_ref())
+    }
+
     /// Checks if this chunk is currently visible on screen
     /// 
     /// - `camera_pos`: Current camera position in world coordinates
@@ -121,8 +448,14 @@ impl Chunk {
     /// 
     /// Returns `true` if any part of this chunk is visible on screen
     pub fn is_visible(&self, camera_pos: Vec2, screen_size: Vec2) -> bool {
-        let screen_min = camera_pos - screen_size / 2.0;
-        let screen_max = camera_pos + screen_size / 2.0;
+        self.is_visible_in(screen_rect(camera_pos, screen_size))
+    }
+
+    /// Checks if any part of this chunk lies in a world-space area
+    /// 
+    /// - `area`: Visible area in world coordinates, e.g. `Viewport::bounds`
+    pub fn is_visible_in(&self, area: Rect) -> bool {
+        let (screen_min, screen_max) = (area.point(), area.point() + area.size());
 
         !(self.bounds.1.x < screen_min.x
             || self.bounds.0.x > screen_max.x
@@ -130,28 +463,45 @@ impl Chunk {
             || self.bounds.0.y > screen_max.y)
     }
 
+    /// Returns the minimum and maximum corners of this chunk in world coordinates
+    pub fn bounds(&self) -> (Vec2, Vec2) {
+        self.bounds
+    }
+
+    /// Returns the number of objects considered active during the last update
+    pub fn active_object_count(&self) -> usize {
+        self.active_objects.len()
+    }
+
+    /// Returns the number of tiles considered visible during the last update or draw
+    pub fn visible_tile_count(&self) -> usize {
+        self.visible_tiles.len()
+    }
+
     /// Updates the list of tiles that are currently visible on screen
-    /// 
-    /// - `camera_pos`: Current camera position in world coordinates
-    /// - `screen_size`: Size of the game window
-    fn update_visible_tiles(&mut self, camera_pos: Vec2, screen_size: Vec2) {
+    ///
+    /// - `area`: Visible area in world coordinates
+    ///
+    /// Every tile that overlaps the area is kept, including partially visible
+    /// tiles at its edges.
+    fn update_visible_tiles(&mut self, area: Rect) {
         self.visible_tiles.clear();
-        let screen_min = camera_pos - screen_size / 2.0;
-        let screen_max = camera_pos + screen_size / 2.0;
+        let (screen_min, screen_max) = (area.point(), area.point() + area.size());
 
-        let start_x = ((screen_min.x - self.bounds.0.x) / TILE_SIZE).floor() as i32;
-        let end_x = ((screen_max.x - self.bounds.0.x) / TILE_SIZE).ceil() as i32;
-        let start_y = ((screen_min.y - self.bounds.0.y) / TILE_SIZE).floor() as i32;
-        let end_y = ((screen_max.y - self.bounds.0.y) / TILE_SIZE).ceil() as i32;
+        let (tile_size, chunk_size) = (self.config.tile_size, self.config.chunk_size);
+        let start_x = ((screen_min.x - self.bounds.0.x) / tile_size).floor() as i32;
+        let end_x = ((screen_max.x - self.bounds.0.x) / tile_size).ceil() as i32;
+        let start_y = ((screen_min.y - self.bounds.0.y) / tile_size).floor() as i32;
+        let end_y = ((screen_max.y - self.bounds.0.y) / tile_size).ceil() as i32;
 
-        let start_x = start_x.max(0).min(CHUNK_SIZE as i32 - 1) as usize;
-        let end_x = end_x.max(0).min(CHUNK_SIZE as i32) as usize;
-        let start_y = start_y.max(0).min(CHUNK_SIZE as i32 - 1) as usize;
-        let end_y = end_y.max(0).min(CHUNK_SIZE as i32) as usize;
+        let start_x = start_x.max(0).min(chunk_size as i32 - 1) as usize;
+        let end_x = end_x.max(0).min(chunk_size as i32) as usize;
+        let start_y = start_y.max(0).min(chunk_size as i32 - 1) as usize;
+        let end_y = end_y.max(0).min(chunk_size as i32) as usize;
 
         for y in start_y..end_y {
             for x in start_x..end_x {
-                let index = y * CHUNK_SIZE + x;
+                let index = y * chunk_size + x;
                 if index < self.tiles.len() {
                     self.visible_tiles.push(index);
                 }
@@ -161,32 +511,102 @@ impl Chunk {
 
     /// Updates the list of objects that are currently active (in or near the viewport)
     /// 
-    /// - `camera_pos`: Current camera position in world coordinates
-    /// - `screen_size`: Size of the game window
-    fn update_active_objects(&mut self, camera_pos: Vec2, screen_size: Vec2) {
+    /// - `area`: Visible area in world coordinates
+    ///
+    /// Chunks with at least `OBJECT_INDEX_THRESHOLD` objects look up candidates in a
+    /// grid of `WorldConfig::object_index_cell_size` cells instead of checking every object. Only
+    /// objects ticked since the last update can have moved on their own, so only
+    /// those are moved between cells; the whole grid is rebuilt when objects were
+    /// added or removed, or after `mark_dirty`.
+    fn update_active_objects(&mut self, area: Rect) {
+        let margin = Vec2::splat(self.config.object_activation_margin);
+        let screen_min = area.point() - margin;
+        let screen_max = area.point() + area.size() + margin;
+        let is_active = |obj: &dyn Object| {
+            let pos = obj.get_pos();
+            pos.x >= screen_min.x && pos.x <= screen_max.x && pos.y >= screen_min.y && pos.y <= screen_max.y
+        };
+
+        if self.objects.len() < OBJECT_INDEX_THRESHOLD {
+            self.active_objects.clear();
+            for (index, obj) in self.objects.iter().enumerate() {
+                if is_active(obj.as_ref()) {
+                    self.active_objects.push(index);
+                }
+            }
+            self.object_grid_stale = true;
+            return;
+        }
+
+        self.update_object_grid();
+        let cell_size = self.config.object_index_cell_size();
+        let (min_cell, max_cell) = (object_cell(screen_min, cell_size), object_cell(screen_max, cell_size));
         self.active_objects.clear();
-        let screen_min = camera_pos - screen_size / 2.0 - Vec2::splat(OBJECT_ACTIVATION_MARGIN);
-        let screen_max = camera_pos + screen_size / 2.0 + Vec2::splat(OBJECT_ACTIVATION_MARGIN);
+        for y in min_cell.1..=max_cell.1 {
+            for x in min_cell.0..=max_cell.0 {
+                if let Some(indices) = self.object_grid.get(&(x, y)) {
+                    self.active_objects.extend(
+                        indices.iter().copied().filter(|&index| is_active(self.objects[index].as_ref())),
+                    );
+                }
+            }
+        }
+        self.active_objects.sort_unstable();
+    }
 
-        for (index, obj) in self.objects.iter().enumerate() {
-            let pos = obj.get_pos();
-            if pos.x >= screen_min.x && pos.x <= screen_max.x && pos.y >= screen_min.y && pos.y <= screen_max.y {
-                self.active_objects.push(index);
+    /// Brings the grid of object cells up to date for `update_active_objects`
+    ///
+    /// Moves the previously active objects to their current cells, or rebuilds the
+    /// whole grid if it is stale or the number of objects changed.
+    fn update_object_grid(&mut self) {
+        let cell_size = self.config.object_index_cell_size();
+        if self.object_grid_stale || self.object_cells.len() != self.objects.len() {
+            self.object_grid.clear();
+            self.object_cells.clear();
+            for (index, obj) in self.objects.iter().enumerate() {
+                let cell = object_cell(obj.get_pos(), cell_size);
+                self.object_cells.push(cell);
+                self.object_grid.entry(cell).or_default().push(index);
             }
+            self.object_grid_stale = false;
+            return;
+        }
+
+        for &index in &self.active_objects {
+            let cell = object_cell(self.objects[index].get_pos(), cell_size);
+            let previous = self.object_cells[index];
+            if cell == previous {
+                continue;
+            }
+            if let Some(indices) = self.object_grid.get_mut(&previous) {
+                indices.retain(|&other| other != index);
+                if indices.is_empty() {
+                    self.object_grid.remove(&previous);
+                }
+            }
+            self.object_grid.entry(cell).or_default().push(index);
+            self.object_cells[index] = cell;
         }
     }
 
     /// Serializes this chunk into a string
-    /// Returns a JSON string containing the chunk's data
-    pub fn serialize(&self) -> String {
-        let tiles: Vec<String> = self.tiles.iter().map(|tile| tile.serialize()).collect();
-        let objects: Vec<String> = self.objects.iter().map(|obj| obj.serialize()).collect();
+    /// Returns a JSON string containing the chunk's data, or an error message if
+    /// the chunk or any of its tiles or objects fails to serialize
+    pub fn serialize(&self) -> Result<String, String> {
+        let (tile_palette, tile_runs) = self.pack_tiles(|tile| tile.serialize())?;
+        let overlays = self.overlays.iter()
+            .map(|(layer, tile)| Ok((*layer, tile.serialize()?)))
+            .collect::<Result<Vec<(u8, String)>, String>>()?;
+        let objects = self.objects.iter().map(|obj| obj.serialize()).collect::<Result<Vec<String>, String>>()?;
         let data = ChunkData {
             pos: Vec2Save::from(self.pos),
-            tiles,
+            tiles: Vec::new(),
+            overlays,
             objects,
+            tile_palette,
+            tile_runs,
         };
-        serde_json::to_string(&data).unwrap()
+        serde_json::to_string(&data).map_err(|e| format!("Failed to serialize chunk {:?}: {}", self.pos, e))
     }
 
     /// Deserializes a chunk from a string
@@ -196,29 +616,399 @@ impl Chunk {
     /// - `object_registry`: Registry containing object prototypes
     /// 
     /// Returns a new Chunk instance or an error if deserialization fails
+    ///
+    /// `on_load` hooks of the tiles and objects are not run, as they need the world.
+    /// They run once the chunk is added to a `World`.
     pub fn deserialize(
         data: &str,
         tile_registry: &TileRegistry,
         object_registry: &ObjectRegistry,
+    ) -> Result<Self, String> {
+        Self::deserialize_with_config(data, WorldConfig::default(), tile_registry, object_registry)
+    }
+
+    /// Deserializes a chunk from a string for a world with the given config
+    /// 
+    /// - `data`: The serialized chunk data
+    /// - `config`: Config of the world the chunk belongs to
+    /// - `tile_registry`: Registry containing tile prototypes
+    /// - `object_registry`: Registry containing object prototypes
+    /// 
+    /// Returns a new Chunk instance or an error if deserialization fails
+    ///
+    /// `on_load` hooks of the tiles and objects are not run, as they need the world.
+    /// They run once the chunk is added to a `World`.
+    pub fn deserialize_with_config(
+        data: &str,
+        config: WorldConfig,
+        tile_registry: &TileRegistry,
+        object_registry: &ObjectRegistry,
     ) -> Result<Self, String> {
         let data: ChunkData = serde_json::from_str(data).map_err(|e| e.to_string())?;
-        let pos = Vec2::from(data.pos);
+        let mut chunk = Chunk::with_config(Vec2::from(data.pos), config);
 
-        let tiles_res: Result<Vec<_>, _> = data.tiles.iter().map(|tile_data| tile_registry.deserialize_tile(tile_data)).collect();
+        let tiles_res = if data.tiles.is_empty() {
+            chunk.unpack_tiles(&data.tile_palette, &data.tile_runs, |tile_data| tile_registry.deserialize_tile(tile_data))
+        } else {
+            data.tiles.iter().map(|tile_data| tile_registry.deserialize_tile(tile_data)).collect()
+        };
+        let overlays_res: Result<Vec<_>, String> = data.overlays.iter()
+            .map(|(layer, tile_data)| Ok((*layer, tile_registry.deserialize_tile(tile_data)?)))
+            .collect();
         let objects_res: Result<Vec<_>, _> = data.objects.iter().map(|object_data| object_registry.deserialize_object(object_data)).collect();
 
-        let mut chunk = Chunk::new(pos);
         chunk.tiles = tiles_res?;
+        chunk.overlays = overlays_res?;
+        chunk.overlays.sort_by_key(|(layer, _)| *layer);
         chunk.objects = objects_res?;
+        chunk.dirty = false;
 
         Ok(chunk)
     }
 
+    /// Serializes this chunk into bincode-encoded bytes
+    /// Returns the encoded chunk data, or an error message if the chunk or any of
+    /// its tiles or objects fails to serialize
+    pub fn serialize_bytes(&self) -> Result<Vec<u8>, String> {
+        let (tile_palette, tile_runs) = self.pack_tiles(|tile| tile.serialize_bytes())?;
+        let overlays = self.overlays.iter()
+            .map(|(layer, tile)| Ok((*layer, tile.serialize_bytes()?)))
+            .collect::<Result<Vec<(u8, Vec<u8>)>, String>>()?;
+        let objects = self.objects.iter().map(|obj| obj.serialize_bytes()).collect::<Result<Vec<Vec<u8>>, String>>()?;
+        let data = ChunkData {
+            pos: Vec2Save::from(self.pos),
+            tiles: Vec::new(),
+            overlays,
+            objects,
+            tile_palette,
+            tile_runs,
+        };
+        bincode::serialize(&data).map_err(|e| format!("Failed to serialize chunk {:?}: {}", self.pos, e))
+    }
+
+    /// Deserializes a chunk from bincode-encoded bytes
+    /// 
+    /// - `data`: Bytes produced by `serialize_bytes`
+    /// - `tile_registry`: Registry containing tile prototypes
+    /// - `object_registry`: Registry containing object prototypes
+    /// 
+    /// Returns a new Chunk instance or an error if deserialization fails
+    ///
+    /// `on_load` hooks of the tiles and objects are not run, as they need the world.
+    /// They run once the chunk is added to a `World`.
+    pub fn deserialize_bytes(
+        data: &[u8],
+        tile_registry: &TileRegistry,
+        object_registry: &ObjectRegistry,
+    ) -> Result<Self, String> {
+        Self::deserialize_bytes_with_config(data, WorldConfig::default(), tile_registry, object_registry)
+    }
+
+    /// Deserializes a chunk from bincode-encoded bytes for a world with the given config
+    /// 
+    /// - `data`: Bytes produced by `serialize_bytes`
+    /// - `config`: Config of the world the chunk belongs to
+    /// - `tile_registry`: Registry containing tile prototypes
+    /// - `object_registry`: Registry containing object prototypes
+    /// 
+    /// Returns a new Chunk instance or an error if deserialization fails
+    ///
+    /// `on_load` hooks of the tiles and objects are not run, as they need the world.
+    /// They run once the chunk is added to a `World`.
+    pub fn deserialize_bytes_with_config(
+        data: &[u8],
+        config: WorldConfig,
+        tile_registry: &TileRegistry,
+        object_registry: &ObjectRegistry,
+    ) -> Result<Self, String> {
+        let data: ChunkData<Vec<u8>> = bincode::deserialize(data).map_err(|e| e.to_string())?;
+        let mut chunk = Chunk::with_config(Vec2::from(data.pos), config);
+
+        let tiles_res = if data.tiles.is_empty() {
+            chunk.unpack_tiles(&data.tile_palette, &data.tile_runs, |tile_data| tile_registry.deserialize_tile_bytes(tile_data))
+        } else {
+            data.tiles.iter().map(|tile_data| tile_registry.deserialize_tile_bytes(tile_data)).collect()
+        };
+        let overlays_res: Result<Vec<_>, String> = data.overlays.iter()
+            .map(|(layer, tile_data)| Ok((*layer, tile_registry.deserialize_tile_bytes(tile_data)?)))
+            .collect();
+        let objects_res: Result<Vec<_>, _> = data.objects.iter().map(|object_data| object_registry.deserialize_object_bytes(object_data)).collect();
+
+        chunk.tiles = tiles_res?;
+        chunk.overlays = overlays_res?;
+        chunk.overlays.sort_by_key(|(layer, _)| *layer);
+        chunk.objects = objects_res?;
+        chunk.dirty = false;
+
+        Ok(chunk)
+    }
+
+    /// Serializes the tiles as a palette of distinct tiles and runs into it
+    ///
+    /// - `serialize`: Serializes a single tile
+    ///
+    /// Tiles are serialized at the origin, so tiles that differ only in their
+    /// position share a palette entry. Saved tile data holds nothing besides the
+    /// type, size and variant, so those identify an entry and only the first tile
+    /// of each entry is copied to serialize it. Returns the palette and the runs, or
+    /// an error message if a tile fails to serialize or there are too many distinct
+    /// tiles to index with a `u16`
+    fn pack_tiles<T>(&self, serialize: impl Fn(&dyn Tile) -> Result<T, String>) -> Result<(Vec<T>, TileRuns), String> {
+        let mut palette: Vec<T> = Vec::new();
+        let mut indices: HashMap<(&'static str, u32, [u32; 2]), usize> = HashMap::new();
+        let mut runs: Vec<(u16, u16)> = Vec::new();
+        for tile in &self.tiles {
+            let key = (tile.get_type_tag(), tile.get_variant(), tile.get_size().to_array().map(f32::to_bits));
+            let index = match indices.get(&key) {
+                Some(&index) => index,
+                None => {
+                    let mut entry = tile.clone_box();
+                    entry.set_pos(Vec2::ZERO);
+                    palette.push(serialize(entry.as_ref())?);
+                    indices.insert(key, palette.len() - 1);
+                    palette.len() - 1
+                }
+            };
+            let index = u16::try_from(index)
+                .map_err(|_| format!("Chunk {:?} has more than {} distinct tiles", self.pos, u16::MAX as usize + 1))?;
+            match runs.last_mut() {
+                Some((run_index, length)) if *run_index == index && *length < u16::MAX => *length += 1,
+                _ => runs.push((index, 1)),
+            }
+        }
+        Ok((palette, runs))
+    }
+
+    /// Recreates the tiles stored by `pack_tiles`
+    ///
+    /// - `palette`: Serialized distinct tiles
+    /// - `runs`: Runs of `(palette index, length)`, row by row
+    /// - `deserialize`: Deserializes a single palette entry
+    ///
+    /// Returns the tiles at their positions, or an error message if an entry fails to
+    /// deserialize, a run refers to a missing entry or the runs do not cover the chunk
+    /// exactly. Chunks without tiles have no runs.
+    fn unpack_tiles<T>(
+        &self,
+        palette: &[T],
+        runs: &[(u16, u16)],
+        deserialize: impl Fn(&T) -> Result<Box<dyn Tile>, String>,
+    ) -> Result<Vec<Box<dyn Tile>>, String> {
+        let (tile_size, chunk_size) = (self.config.tile_size, self.config.chunk_size);
+        let total: usize = runs.iter().map(|&(_, length)| length as usize).sum();
+        if total != 0 && total != chunk_size * chunk_size {
+            return Err(format!("Tile runs cover {} tiles, but chunk {:?} has {}", total, self.pos, chunk_size * chunk_size));
+        }
+        let prototypes = palette.iter().map(deserialize).collect::<Result<Vec<_>, String>>()?;
+        let mut tiles: Vec<Box<dyn Tile>> = Vec::with_capacity(chunk_size * chunk_size);
+        for &(index, length) in runs {
+            let prototype = prototypes
+                .get(index as usize)
+                .ok_or_else(|| format!("Tile run refers to missing palette entry {}", index))?;
+            for _ in 0..length {
+                let cell = tiles.len();
+                let mut tile = prototype.clone_box();
+                tile.set_pos(self.bounds.0 + vec2((cell % chunk_size) as f32 * tile_size, (cell / chunk_size) as f32 * tile_size));
+                tiles.push(tile);
+            }
+        }
+        Ok(tiles)
+    }
+
+    /// Returns the tile at the given local coordinates
+    ///
+    /// - `x`: Column of the tile within the chunk, `0..chunk_size` of the chunk's config
+    /// - `y`: Row of the tile within the chunk, `0..chunk_size` of the chunk's config
+    ///
+    /// Returns `None` if the coordinates are out of range or no tile is stored there
+    pub fn tile_at(&self, x: usize, y: usize) -> Option<&dyn Tile> {
+        if x >= self.config.chunk_size || y >= self.config.chunk_size {
+            return None;
+        }
+        self.tiles.get(y * self.config.chunk_size + x).map(|tile| tile.as_ref())
+    }
+
+    /// Returns a mutable reference to the tile at the given local coordinates
+    ///
+    /// - `x`: Column of the tile within the chunk, `0..chunk_size` of the chunk's config
+    /// - `y`: Row of the tile within the chunk, `0..chunk_size` of the chunk's config
+    ///
+    /// Returns `None` if the coordinates are out of range or no tile is stored there
+    pub fn tile_at_mut(&mut self, x: usize, y: usize) -> Option<&mut dyn Tile> {
+        if x >= self.config.chunk_size || y >= self.config.chunk_size {
+            return None;
+        }
+        let tile = self.tiles.get_mut(y * self.config.chunk_size + x)?;
+        self.dirty = true;
+        Some(tile.as_mut())
+    }
+
+    /// Places a tile at the given local coordinates
+    ///
+    /// - `x`: Column of the tile within the chunk, `0..chunk_size` of the chunk's config
+    /// - `y`: Row of the tile within the chunk, `0..chunk_size` of the chunk's config
+    /// - `tile`: The tile to place
+    ///
+    /// The tile's position is set to the matching world position. Tiles are stored
+    /// row by row; if the chunk holds fewer tiles than the index needs, the cells in
+    /// between are filled with copies of the placed tile, so sparse or partially
+    /// generated chunks can be edited anywhere.
+    ///
+    /// Returns the replaced tile, `None` if the cell was empty, or an error message if
+    /// the coordinates are out of range
+    pub fn set_tile(&mut self, x: usize, y: usize, mut tile: Box<dyn Tile>) -> Result<Option<Box<dyn Tile>>, String> {
+        let chunk_size = self.config.chunk_size;
+        if x >= chunk_size || y >= chunk_size {
+            return Err(format!("Tile coordinates ({}, {}) are outside the chunk", x, y));
+        }
+
+        let index = y * chunk_size + x;
+        let tile_pos = |cell: usize| self.bounds.0 + vec2((cell % chunk_size) as f32, (cell / chunk_size) as f32) * self.config.tile_size;
+        tile.set_pos(tile_pos(index));
+        self.dirty = true;
+        if index < self.tiles.len() {
+            return Ok(Some(std::mem::replace(&mut self.tiles[index], tile)));
+        }
+
+        for cell in self.tiles.len()..index {
+            let mut filler = tile.clone_box();
+            filler.set_pos(tile_pos(cell));
+            self.tiles.push(filler);
+        }
+        self.tiles.push(tile);
+        Ok(None)
+    }
+
+    /// Places an overlay tile above the base tile at the given local coordinates
+    ///
+    /// - `x`: Column of the tile within the chunk, `0..chunk_size` of the chunk's config
+    /// - `y`: Row of the tile within the chunk, `0..chunk_size` of the chunk's config
+    /// - `layer`: Layer of the overlay, higher layers are drawn on top
+    /// - `tile`: The tile to place
+    ///
+    /// Multiple overlays can share a cell. The tile's position is set to the matching
+    /// world position.
+    ///
+    /// Returns `Ok(())` on success, or an error message if the coordinates are out of range
+    pub fn add_overlay(&mut self, x: usize, y: usize, layer: u8, mut tile: Box<dyn Tile>) -> Result<(), String> {
+        if x >= self.config.chunk_size || y >= self.config.chunk_size {
+            return Err(format!("Tile coordinates ({}, {}) are outside the chunk", x, y));
+        }
+
+        tile.set_pos(self.bounds.0 + vec2(x as f32, y as f32) * self.config.tile_size);
+        let index = self.overlays.partition_point(|(tile_layer, _)| *tile_layer <= layer);
+        self.overlays.insert(index, (layer, tile));
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Returns the overlay tiles at the given local coordinates with their layers
+    ///
+    /// - `x`: Column of the tile within the chunk, `0..chunk_size` of the chunk's config
+    /// - `y`: Row of the tile within the chunk, `0..chunk_size` of the chunk's config
+    pub fn overlays_at(&self, x: usize, y: usize) -> Vec<(u8, &dyn Tile)> {
+        let cell = self.bounds.0 + vec2(x as f32, y as f32) * self.config.tile_size;
+        self.overlays
+            .iter()
+            .filter(|(_, tile)| tile.get_pos() == cell)
+            .map(|(layer, tile)| (*layer, tile.as_ref()))
+            .collect()
+    }
+
+    /// Removes all overlay tiles at the given local coordinates
+    ///
+    /// - `x`: Column of the tile within the chunk, `0..chunk_size` of the chunk's config
+    /// - `y`: Row of the tile within the chunk, `0..chunk_size` of the chunk's config
+    ///
+    /// Returns the removed tiles with their layers
+    pub fn remove_overlays_at(&mut self, x: usize, y: usize) -> Vec<(u8, Box<dyn Tile>)> {
+        let cell = self.bounds.0 + vec2(x as f32, y as f32) * self.config.tile_size;
+        let (removed, kept) = std::mem::take(&mut self.overlays)
+            .into_iter()
+            .partition(|(_, tile)| tile.get_pos() == cell);
+        self.overlays = kept;
+        if !removed.is_empty() {
+            self.dirty = true;
+        }
+        removed
+    }
+
+    /// Removes the object at the given index from this chunk
+    ///
+    /// - `index`: Index of the object in `objects`
+    ///
+    /// The list of active objects is adjusted so it keeps pointing at the same
+    /// objects, which makes it safe to despawn objects between updates.
+    ///
+    /// Returns the removed object, or `None` if the index is out of range
+    pub fn remove_object(&mut self, index: usize) -> Option<Box<dyn Object>> {
+        if index >= self.objects.len() {
+            return None;
+        }
+
+        let removed = self.objects.remove(index);
+        self.object_grid_stale = true;
+        self.remap_objects(|old| match old.cmp(&index) {
+            std::cmp::Ordering::Less => Some(old),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Greater => Some(old - 1),
+        });
+        self.dirty = true;
+        Some(removed)
+    }
+
+    /// Removes every object for which the predicate returns `false`
+    ///
+    /// - `keep`: Called once per object, in order; return `true` to keep it
+    ///
+    /// The list of active objects is rebuilt so it keeps pointing at the objects
+    /// that remain.
+    ///
+    /// Returns the removed objects in their original order
+    pub fn retain_objects<F>(&mut self, mut keep: F) -> Vec<Box<dyn Object>>
+    where
+        F: FnMut(&dyn Object) -> bool,
+    {
+        let mut new_index = Vec::with_capacity(self.objects.len());
+        let mut kept = Vec::with_capacity(self.objects.len());
+        let mut removed = Vec::new();
+        for obj in std::mem::take(&mut self.objects) {
+            if keep(obj.as_ref()) {
+                new_index.push(Some(kept.len()));
+                kept.push(obj);
+            } else {
+                new_index.push(None);
+                removed.push(obj);
+            }
+        }
+        self.objects = kept;
+        if !removed.is_empty() {
+            self.object_grid_stale = true;
+        }
+
+        self.remap_objects(|old| new_index.get(old).copied().flatten());
+        if !removed.is_empty() {
+            self.dirty = true;
+        }
+        removed
+    }
+
+    /// Points the active objects and the objects waiting for their tick at the
+    /// new indices of their objects after objects were removed
+    ///
+    /// - `new_index`: Maps an old index to the new one, or `None` if the object was removed
+    fn remap_objects(&mut self, new_index: impl Fn(usize) -> Option<usize>) {
+        self.active_objects = self.active_objects.iter().filter_map(|&old| new_index(old)).collect();
+        self.tick_queue = self.tick_queue.iter().filter_map(|&old| new_index(old)).collect();
+        self.ticking = self.ticking.and_then(&new_index);
+    }
+
     /// Returns all objects of the specified type in this chunk
     /// 
     /// - `type_tag`: The type of objects to find
     /// 
     /// Returns a vector of references to matching objects
+    #[allow(clippy::borrowed_box)]
     pub fn get_objects_by_type(&self, type_tag: &str) -> Vec<&Box<dyn Object>> {
         let mut objects = Vec::new();
 
@@ -235,6 +1025,7 @@ impl Chunk {
     /// - `type_tag`: The type of tiles to find
     /// 
     /// Returns a vector of references to matching tiles
+    #[allow(clippy::borrowed_box)]
     pub fn get_tiles_by_type(&self, type_tag: &str) -> Vec<&Box<dyn Tile>> {
         let mut tiles = Vec::new();
 
@@ -246,3 +1037,177 @@ impl Chunk {
         tiles
     }
 }
+
+/// Stands in for an object while it is taken out of its chunk for its tick
+///
+/// It has no type tag, id or size, and its position is NaN, so queries for
+/// objects by type, id or area never match it.
+struct Ticking;
+
+impl Object for Ticking {
+    fn get_type_tag(&self) -> &'static str {
+        ""
+    }
+
+    fn get_pos(&self) -> Vec2 {
+        Vec2::NAN
+    }
+
+    fn get_size(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+
+    fn get_velocity(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+
+    fn draw(&self, _batch: &mut DrawBatch) {}
+
+    fn set_size(&mut self, _size: Vec2) {}
+
+    fn set_pos(&mut self, _pos: Vec2) {}
+
+    fn set_velocity(&mut self, _velocity: Vec2) {}
+
+    fn clone_box(&self) -> Box<dyn Object> {
+        Box::new(Ticking)
+    }
+}
+
+/// Returns the world-space area of a screen centered on the camera position
+///
+/// - `camera_pos`: Current camera position in world coordinates
+/// - `screen_size`: Size of the visible area in world units
+fn screen_rect(camera_pos: Vec2, screen_size: Vec2) -> Rect {
+    let min = camera_pos - screen_size / 2.0;
+    Rect::new(min.x, min.y, screen_size.x, screen_size.y)
+}
+
+/// Returns the cell of the object grid containing a world position
+///
+/// - `pos`: Position in world coordinates
+/// - `cell_size`: Size of a grid cell, see `WorldConfig::object_index_cell_size`
+fn object_cell(pos: Vec2, cell_size: f32) -> (i32, i32) {
+    (
+        (pos.x / cell_size).floor() as i32,
+        (pos.y / cell_size).floor() as i32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::core::testing::{registries, TestObject, TestTile};
+    use crate::OBJECT_ACTIVATION_MARGIN;
+
+    fn small_config() -> WorldConfig {
+        WorldConfig { tile_size: 8.0, chunk_size: 4, ..Default::default() }
+    }
+
+    fn tile_registry() -> TileRegistry {
+        let (mut tiles, _, _) = registries();
+        tiles.register(TestTile::new("stone"));
+        tiles
+    }
+
+    /// A 4x4 chunk of grass with a stone in the first and the last cell
+    fn stony_chunk() -> Chunk {
+        let mut chunk = Chunk::with_config(vec2(-1.0, 2.0), small_config());
+        for index in 0..16 {
+            let tag = if index == 0 || index == 15 { "stone" } else { "grass" };
+            chunk.set_tile(index % 4, index / 4, Box::new(TestTile::new(tag))).unwrap();
+        }
+        chunk
+    }
+
+    fn tiles_of(chunk: &Chunk) -> Vec<(&'static str, Vec2)> {
+        chunk.tiles.iter().map(|tile| (tile.get_type_tag(), tile.get_pos())).collect()
+    }
+
+    #[test]
+    fn tiles_round_trip_as_palette_and_runs() {
+        let (tiles, objects) = (tile_registry(), registries().1);
+        let chunk = stony_chunk();
+
+        let (palette, runs) = chunk.pack_tiles(|tile| tile.serialize()).unwrap();
+        assert_eq!(palette.len(), 2);
+        assert_eq!(runs, vec![(0, 1), (1, 14), (0, 1)]);
+
+        let json = Chunk::deserialize_with_config(&chunk.serialize().unwrap(), small_config(), &tiles, &objects).unwrap();
+        let bin = Chunk::deserialize_bytes_with_config(&chunk.serialize_bytes().unwrap(), small_config(), &tiles, &objects).unwrap();
+        assert_eq!(tiles_of(&json), tiles_of(&chunk));
+        assert_eq!(tiles_of(&bin), tiles_of(&chunk));
+    }
+
+    #[test]
+    fn full_tile_arrays_of_older_saves_load() {
+        let (tiles, objects) = (tile_registry(), registries().1);
+        let chunk = stony_chunk();
+        let tile_data: Vec<String> = chunk.tiles.iter().map(|tile| tile.serialize().unwrap()).collect();
+        let old = serde_json::json!({ "pos": { "x": -1.0, "y": 2.0 }, "tiles": tile_data, "objects": [] });
+
+        let loaded = Chunk::deserialize_with_config(&old.to_string(), small_config(), &tiles, &objects).unwrap();
+        assert_eq!(tiles_of(&loaded), tiles_of(&chunk));
+    }
+
+    #[test]
+    fn runs_that_do_not_cover_the_chunk_are_rejected() {
+        let (tiles, objects) = (tile_registry(), registries().1);
+        let (palette, _) = stony_chunk().pack_tiles(|tile| tile.serialize()).unwrap();
+        for runs in [vec![(1, 15)], vec![(1, 16), (0, 1)]] {
+            let data = ChunkData {
+                pos: Vec2Save::from(Vec2::ZERO),
+                tiles: Vec::new(),
+                overlays: Vec::new(),
+                objects: Vec::new(),
+                tile_palette: palette.clone(),
+                tile_runs: runs,
+            };
+            let json = serde_json::to_string(&data).unwrap();
+            assert!(Chunk::deserialize_with_config(&json, small_config(), &tiles, &objects).is_err());
+        }
+    }
+
+    /// Compares activating objects through the grid with scanning every object,
+    /// once with the default activation margin and once with none.
+    /// Run with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore = "measurement"]
+    fn measure_activation_of_2000_objects() {
+        for margin in [OBJECT_ACTIVATION_MARGIN, 0.0] {
+            let config = WorldConfig { object_activation_margin: margin, ..Default::default() };
+            let mut chunk = Chunk::with_config(Vec2::ZERO, config);
+            for index in 0..2000 {
+                let pos = vec2((index % 50) as f32 * 5.0, (index / 50) as f32 * 6.0);
+                chunk.objects.push(Box::new(TestObject::new(pos)));
+            }
+            let area = Rect::new(0.0, 0.0, 16.0, 16.0);
+            let rounds = 10_000;
+
+            let start = Instant::now();
+            for _ in 0..rounds {
+                chunk.update_active_objects(area);
+            }
+            let grid = start.elapsed() / rounds;
+            let active = chunk.active_objects.len();
+
+            let (min, max) = (area.point() - Vec2::splat(margin), area.point() + area.size() + Vec2::splat(margin));
+            let start = Instant::now();
+            for _ in 0..rounds {
+                chunk.active_objects.clear();
+                for (index, obj) in chunk.objects.iter().enumerate() {
+                    let pos = obj.get_pos();
+                    if pos.x >= min.x && pos.x <= max.x && pos.y >= min.y && pos.y <= max.y {
+                        chunk.active_objects.push(index);
+                    }
+                }
+            }
+            let scan = start.elapsed() / rounds;
+
+            assert_eq!(chunk.active_objects.len(), active);
+            println!("2000 objects, margin {}, {} active: grid {:?}, scan {:?} per update", margin, active, grid, scan);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use macroquad::math::Vec2;
+
+use crate::{utils::{hash::hash2, rand::hash_to_unit}, Biome, BiomeRegistry, Chunk, ObjectRegistry, TileRegistry, WorldConfig};
+
+/// Context passed to a `WorldGenerator` when a chunk is generated.
+///
+/// Gives the generator access to the world's registries so it can create
+/// tiles, objects and look up biomes.
+pub struct GenContext<'a> {
+    /// Registry of all available tile types
+    pub tile_registry: &'a TileRegistry,
+    /// Registry of all available object types
+    pub object_registry: &'a ObjectRegistry,
+    /// Registry of all available biome types
+    pub biome_registry: &'a BiomeRegistry,
+    /// Seed of the world, used for deterministic generation
+    pub seed: u64,
+    /// Tile and chunk sizes of the world
+    pub config: WorldConfig,
+}
+
+impl GenContext<'_> {
+    /// Derives a seed for a single chunk from the world seed.
+    ///
+    /// - `chunk_pos`: The position of the chunk in chunk coordinates.
+    ///
+    /// Returns a seed that is the same for the same world seed and chunk position.
+    pub fn chunk_seed(&self, chunk_pos: Vec2) -> u64 {
+        let (x, y) = (chunk_pos.x as i32, chunk_pos.y as i32);
+        let high = hash2(self.seed, x, y) as u64;
+        let low = hash2(self.seed.rotate_left(32), x, y) as u64;
+        (high << 32) | low
+    }
+
+    /// Creates an empty chunk laid out with the world's config.
+    ///
+    /// Use it instead of `Chunk::new` so generated chunks match worlds that
+    /// use a custom `WorldConfig`.
+    ///
+    /// - `chunk_pos`: The position of the chunk in chunk coordinates.
+    pub fn new_chunk(&self, chunk_pos: Vec2) -> Chunk {
+        Chunk::with_config(chunk_pos, self.config)
+    }
+
+    /// Returns a deterministic random roll for a tile of a chunk.
+    ///
+    /// Use it for spawn chances and similar decisions instead of a global random
+    /// number generator, so a given seed always produces the same world.
+    ///
+    /// - `chunk_pos`: The position of the chunk in chunk coordinates.
+    /// - `x`: Column of the tile within the chunk.
+    /// - `y`: Row of the tile within the chunk.
+    ///
+    /// Returns a value in the range 0.0 (inclusive) to 1.0 (exclusive).
+    pub fn roll(&self, chunk_pos: Vec2, x: usize, y: usize) -> f32 {
+        let size = self.config.chunk_size as i32;
+        let tile_x = chunk_pos.x as i32 * size + x as i32;
+        let tile_y = chunk_pos.y as i32 * size + y as i32;
+        hash_to_unit(self.seed, tile_x, tile_y)
+    }
+
+    /// Runs a biome's decoration pass over a chunk.
+    ///
+    /// Call this from `WorldGenerator::generate` after the ground tiles are placed.
+    ///
+    /// - `chunk`: The chunk to decorate.
+    /// - `biome`: The biome whose `Biome::decorate` is called.
+    pub fn decorate(&self, chunk: &mut Chunk, biome: &dyn Biome) {
+        biome.decorate(chunk, self.chunk_seed(chunk.pos));
+    }
+}
+
+/// Generates chunks on demand as they become visible.
+///
+/// A generator is registered on a `World` with `World::set_generator`. Whenever a
+/// visible chunk coordinate has no loaded chunk, the world asks the generator to
+/// create one.
+pub trait WorldGenerator: Send + Sync {
+    /// Generates the chunk at the given position.
+    ///
+    /// - `chunk_pos`: The position of the chunk in chunk coordinates.
+    /// - `ctx`: Registries and generation parameters of the world.
+    ///
+    /// Returns the newly generated chunk.
+    fn generate(&self, chunk_pos: Vec2, ctx: &GenContext) -> Chunk;
+}
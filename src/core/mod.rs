@@ -1,7 +1,11 @@
 pub mod biome;
 pub mod chunk;
+pub mod generator;
 pub mod object;
 pub mod save;
 pub mod tile;
 pub mod world;
 pub mod ui;
+
+#[cfg(test)]
+pub(crate) mod testing;
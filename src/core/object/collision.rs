@@ -0,0 +1,220 @@
+use macroquad::math::{vec2, Vec2};
+
+use super::Object;
+
+/// Separates two colliding objects by adjusting their velocities
+///
+/// - `a`: First object of the pair
+/// - `b`: Second object of the pair
+/// - `buffer`: Skin width in world units, see below
+///
+/// The buffer is a skin around each object: both bounding boxes are shrunk by it
+/// on every side before testing, so objects may touch and overlap by up to twice
+/// the buffer without being pushed apart. A larger buffer hides jitter between
+/// resting objects at the cost of visible overlap, `0.0` makes boxes meet exactly.
+///
+/// If the boxes do not overlap
+/// yet, they are swept along their relative velocity and the motion along the
+/// axis of first contact is cut so they end the tick touching, which also stops
+/// fast objects from tunnelling through each other. If they already overlap, a
+/// single minimum-translation vector is computed from the boxes they would
+/// occupy after applying their velocities, along the axis of least penetration
+/// (both axes on a tie).
+///
+/// Either way the correction is split between the two velocities in proportion
+/// to how fast each body moves along that axis. A moving object that walks into
+/// a resting one therefore stops without shoving it, and two objects meeting
+/// head-on at the same speed both stop. Bodies at rest on that axis share the
+/// correction equally.
+///
+/// Returns `true` if the velocities were adjusted
+pub fn resolve_collision(a: &mut dyn Object, b: &mut dyn Object, buffer: f32) -> bool {
+    let mut velocity_a = a.get_velocity();
+    let mut velocity_b = b.get_velocity();
+    let (a_min, a_max) = buffered_bounds(a, buffer);
+    let (b_min, b_max) = buffered_bounds(b, buffer);
+
+    if let Some((time, axes)) = sweep(a_min, a_max, b_min, b_max, velocity_a - velocity_b) {
+        for &axis in axes {
+            let excess = (velocity_a[axis] - velocity_b[axis]) * (1.0 - time);
+            let share_a = split_share(velocity_a, velocity_b, axis);
+            velocity_a[axis] -= excess * share_a;
+            velocity_b[axis] += excess * (1.0 - share_a);
+        }
+    } else {
+        let next_a_min = a_min + velocity_a;
+        let next_a_max = a_max + velocity_a;
+        let next_b_min = b_min + velocity_b;
+        let next_b_max = b_max + velocity_b;
+
+        let overlap = next_a_max.min(next_b_max) - next_a_min.max(next_b_min);
+        if overlap.x <= 0.0 || overlap.y <= 0.0 {
+            return false;
+        }
+
+        let axes: &[usize] = if overlap.x < overlap.y {
+            &[0]
+        } else if overlap.x > overlap.y {
+            &[1]
+        } else {
+            &[0, 1]
+        };
+
+        let a_center = (next_a_min + next_a_max) * 0.5;
+        let b_center = (next_b_min + next_b_max) * 0.5;
+        for &axis in axes {
+            let push = if a_center[axis] < b_center[axis] { -overlap[axis] } else { overlap[axis] };
+            let share_a = split_share(velocity_a, velocity_b, axis);
+            velocity_a[axis] += push * share_a;
+            velocity_b[axis] -= push * (1.0 - share_a);
+        }
+    }
+
+    a.set_velocity(velocity_a);
+    b.set_velocity(velocity_b);
+    true
+}
+
+/// Checks whether two objects will touch during the next tick
+///
+/// - `a`: First object of the pair
+/// - `b`: Second object of the pair
+/// - `buffer`: Skin width in world units, as passed to `resolve_collision`
+///
+/// Both boxes are shrunk by the buffer like in `resolve_collision`, so pairs the
+/// resolver would leave alone are not reported. Objects that are apart are swept along their relative velocity, so glancing
+/// contacts and fast objects catching up with slower ones are detected even if
+/// they would pass through each other within a single tick. Objects that already
+/// overlap only count as colliding while that overlap is not shrinking, which
+/// lets bodies that are separating move apart undisturbed.
+pub(crate) fn will_collide(a: &dyn Object, b: &dyn Object, buffer: f32) -> bool {
+    let velocity_a = a.get_velocity();
+    let velocity_b = b.get_velocity();
+    let (a_min, a_max) = buffered_bounds(a, buffer);
+    let (b_min, b_max) = buffered_bounds(b, buffer);
+
+    let overlap = a_max.min(b_max) - a_min.max(b_min);
+    if overlap.x > 0.0 && overlap.y > 0.0 {
+        let next = (a_max + velocity_a).min(b_max + velocity_b) - (a_min + velocity_a).max(b_min + velocity_b);
+        let next_area = next.x.max(0.0) * next.y.max(0.0);
+        return next_area >= overlap.x * overlap.y;
+    }
+
+    sweep(a_min, a_max, b_min, b_max, velocity_a - velocity_b).is_some()
+}
+
+/// Returns the minimum and maximum corner of an object's box shrunk by `buffer` on every side
+fn buffered_bounds(obj: &dyn Object, buffer: f32) -> (Vec2, Vec2) {
+    let buffer = vec2(buffer, buffer);
+    (obj.get_pos() + buffer, obj.get_pos() + obj.get_size() - buffer)
+}
+
+/// Sweeps box `a` along `relative` against the static box `b`
+///
+/// Returns the fraction of the tick at which the boxes first touch, together
+/// with the axes that started overlapping last, or `None` if they already
+/// overlap or do not meet within the tick.
+fn sweep(a_min: Vec2, a_max: Vec2, b_min: Vec2, b_max: Vec2, relative: Vec2) -> Option<(f32, &'static [usize])> {
+    let mut entry = [0.0; 2];
+    let mut exit = [0.0; 2];
+    for axis in 0..2 {
+        if relative[axis] == 0.0 {
+            if a_max[axis] <= b_min[axis] || a_min[axis] >= b_max[axis] {
+                return None;
+            }
+            entry[axis] = f32::NEG_INFINITY;
+            exit[axis] = f32::INFINITY;
+        } else {
+            let t1 = (b_min[axis] - a_max[axis]) / relative[axis];
+            let t2 = (b_max[axis] - a_min[axis]) / relative[axis];
+            entry[axis] = t1.min(t2);
+            exit[axis] = t1.max(t2);
+        }
+    }
+
+    let time = entry[0].max(entry[1]);
+    if !(0.0..1.0).contains(&time) || time >= exit[0].min(exit[1]) {
+        return None;
+    }
+
+    let axes: &'static [usize] = if entry[0] > entry[1] {
+        &[0]
+    } else if entry[0] < entry[1] {
+        &[1]
+    } else {
+        &[0, 1]
+    };
+    Some((time, axes))
+}
+
+/// Returns the fraction of a correction along `axis` that the first body takes
+fn split_share(velocity_a: Vec2, velocity_b: Vec2, axis: usize) -> f32 {
+    let speed_a = velocity_a[axis].abs();
+    let speed_b = velocity_b[axis].abs();
+    if speed_a + speed_b > 0.0 {
+        speed_a / (speed_a + speed_b)
+    } else {
+        0.5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::TestObject;
+
+    fn moving(x: f32, velocity: f32) -> TestObject {
+        TestObject { velocity: vec2(velocity, 0.0), ..TestObject::new(vec2(x, 0.0)) }
+    }
+
+    /// Resolves the pair if it collides, then moves both objects by their velocity.
+    fn step(a: &mut TestObject, b: &mut TestObject, buffer: f32) {
+        if will_collide(a, b, buffer) {
+            resolve_collision(a, b, buffer);
+        }
+        a.pos += a.velocity;
+        b.pos += b.velocity;
+    }
+
+    /// Returns how far the boxes of two objects, shrunk by the buffer, overlap along x.
+    fn overlap_x(a: &TestObject, b: &TestObject, buffer: f32) -> f32 {
+        (a.pos.x + a.size.x - buffer).min(b.pos.x + b.size.x - buffer) - (a.pos.x + buffer).max(b.pos.x + buffer)
+    }
+
+    #[test]
+    fn head_on_objects_stop_without_overlapping() {
+        let (mut a, mut b) = (moving(0.0, 3.0), moving(10.0, -3.0));
+        for _ in 0..10 {
+            step(&mut a, &mut b, 1.0);
+            assert!(overlap_x(&a, &b, 1.0) <= 1e-4);
+        }
+        assert_eq!((a.velocity.x, b.velocity.x), (0.0, 0.0));
+        assert_eq!(a.pos.x + 8.0 - 1.0, b.pos.x + 1.0);
+    }
+
+    #[test]
+    fn fast_object_rear_ending_a_slow_one_is_caught() {
+        let (mut fast, mut slow) = (moving(0.0, 10.0), moving(12.0, 2.0));
+        assert!(will_collide(&fast, &slow, 1.0));
+
+        step(&mut fast, &mut slow, 1.0);
+        assert!(overlap_x(&fast, &slow, 1.0) <= 1e-4);
+        assert!(fast.pos.x < slow.pos.x);
+        assert!(fast.velocity.x > 0.0 && slow.velocity.x > 2.0);
+    }
+
+    #[test]
+    fn objects_one_buffer_apart_collide_only_once_their_skins_meet() {
+        // Raw boxes 1 apart, buffered boxes 3 apart
+        let (mut a, mut b) = (moving(0.0, 2.0), moving(9.0, 0.0));
+        assert!(!will_collide(&a, &b, 1.0));
+        step(&mut a, &mut b, 1.0);
+        assert_eq!((a.pos.x, a.velocity.x), (2.0, 2.0));
+
+        let (mut a, mut b) = (moving(0.0, 4.0), moving(9.0, 0.0));
+        assert!(will_collide(&a, &b, 1.0));
+        step(&mut a, &mut b, 1.0);
+        assert_eq!(overlap_x(&a, &b, 1.0), 0.0);
+        assert_eq!(b.pos.x, 9.0);
+    }
+}
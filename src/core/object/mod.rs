@@ -1,18 +1,25 @@
 use std::any::Any;
 use macroquad::math::Vec2;
 use crate::utils::draw::DrawBatch;
-use crate::World;
+use crate::{Event, World};
 use crate::core::save::Vec2Save;
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
-use macroquad::prelude::vec2;
+
+mod collision;
+mod physics;
+
+pub use collision::resolve_collision;
+pub(crate) use collision::will_collide;
+pub use physics::PhysicsBody;
 
 /// Represents the four cardinal directions used for movement and facing.
-#[derive(PartialEq, Clone, Serialize, Deserialize)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub enum Direction {
     /// Facing or moving upward
     Up,
     /// Facing or moving downward
+    #[default]
     Down,
     /// Facing or moving left
     Left,
@@ -20,6 +27,77 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    /// Returns the direction along the dominant axis of a vector
+    ///
+    /// - `vec`: The vector, such as a velocity or an offset between two objects
+    ///
+    /// Returns `None` for a zero vector. Ties between the axes resolve horizontally.
+    pub fn from_vec(vec: Vec2) -> Option<Direction> {
+        if vec == Vec2::ZERO {
+            None
+        } else if vec.x.abs() >= vec.y.abs() {
+            Some(if vec.x < 0.0 { Direction::Left } else { Direction::Right })
+        } else {
+            Some(if vec.y < 0.0 { Direction::Up } else { Direction::Down })
+        }
+    }
+
+    /// Returns the opposite direction
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// Facing state objects can embed to implement `Object::get_direction` and `Object::set_direction`
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Facing {
+    direction: Direction,
+}
+
+impl Facing {
+    /// Creates a facing state pointing in the given direction
+    ///
+    /// - `direction`: The initial direction
+    pub fn new(direction: Direction) -> Self {
+        Self { direction }
+    }
+
+    /// Returns the direction currently faced
+    pub fn get(&self) -> Direction {
+        self.direction
+    }
+
+    /// Sets the direction currently faced
+    ///
+    /// - `direction`: The new direction
+    pub fn set(&mut self, direction: Direction) {
+        self.direction = direction;
+    }
+
+    /// Turns to face along the dominant axis of a velocity
+    ///
+    /// Keeps the current direction while the velocity is zero, so a stopped
+    /// object keeps facing the way it last moved.
+    ///
+    /// - `velocity`: The current velocity
+    pub fn update_from_velocity(&mut self, velocity: Vec2) {
+        if let Some(direction) = Direction::from_vec(velocity) {
+            self.direction = direction;
+        }
+    }
+
+    /// Returns whether a sprite drawn facing right should be mirrored horizontally
+    pub fn flip_x(&self) -> bool {
+        self.direction == Direction::Left
+    }
+}
+
 /// Represents a dynamic game object that can move and interact with the world.
 /// Objects are entities that can have behaviors, move around, and interact with
 /// both tiles and other objects. Examples include players, enemies, and other objects.
@@ -40,7 +118,13 @@ pub trait Object: Any + Send + Sync {
     /// 
     /// - `dt`: Time elapsed since the last frame in seconds
     /// - `world`: Reference to the game world for interaction
-    fn tick(&mut self, _dt: f32, _world: &mut World) { }
+    ///
+    /// Returns `true` if the tick changed anything that is saved: the position, size,
+    /// velocity, direction or the state returned by `serialize_state`. The object's
+    /// chunk is then marked dirty so the next save writes it. Changes the world makes
+    /// itself, such as stopping an object at a wall, are tracked by the world.
+    /// Returns `false` by default.
+    fn tick(&mut self, _dt: f32, _world: &mut World) -> bool { false }
     
     /// Draws the object on the screen
     /// 
@@ -56,6 +140,75 @@ pub trait Object: Any + Send + Sync {
     /// Sets the velocity of the object
     fn set_velocity(&mut self, velocity: Vec2);
 
+    /// Adds gravity to the object's velocity
+    ///
+    /// Opt-in helper for platformer-style objects, call it from `tick`.
+    ///
+    /// - `dt`: Time elapsed since the last tick in seconds
+    /// - `gravity`: Downward acceleration, in velocity units per second
+    fn apply_gravity(&mut self, dt: f32, gravity: f32) {
+        let mut velocity = self.get_velocity();
+        velocity.y += gravity * dt;
+        self.set_velocity(velocity);
+    }
+
+    /// Returns the direction the object is facing
+    ///
+    /// Returns `Direction::Down` by default. Embed a `Facing` to track it.
+    fn get_direction(&self) -> Direction { Direction::Down }
+
+    /// Sets the direction the object is facing
+    ///
+    /// Does nothing by default.
+    ///
+    /// - `direction`: The new direction
+    fn set_direction(&mut self, _direction: Direction) { }
+
+    /// Returns the stable id the world assigned to this object
+    ///
+    /// Objects that should be reachable through `World::get_object` store the id
+    /// passed to `set_id` and return it here. Returns `None` by default.
+    fn get_id(&self) -> Option<ObjectId> { None }
+
+    /// Stores the stable id the world assigned to this object
+    ///
+    /// Called when the object is spawned, generated or loaded. Does nothing by default.
+    ///
+    /// - `id`: The assigned id
+    fn set_id(&mut self, _id: ObjectId) { }
+
+    /// Returns custom state of the object to save alongside its position, size and velocity
+    ///
+    /// Returns `Ok(None)` by default. Override together with `load_state` to persist
+    /// fields such as health or timers. An error makes saving the world fail
+    /// instead of writing the object without its state.
+    fn serialize_state(&self) -> Result<Option<serde_json::Value>, String> { Ok(None) }
+
+    /// Restores custom state previously returned by `serialize_state`
+    ///
+    /// Does nothing by default. Called after the object was cloned from its
+    /// prototype and its position, size and velocity were set.
+    ///
+    /// - `state`: The saved state
+    fn load_state(&mut self, _state: &serde_json::Value) { }
+
+    /// Called after the chunk holding this object became part of the world
+    ///
+    /// - `world`: The world the chunk was added to
+    ///
+    /// Use it to rebind runtime-only resources that are not saved, such as texture
+    /// handles. Runs for chunks added, reloaded from disk or generated, and only
+    /// through `World`: `Chunk::deserialize` does not run it. When a chunk
+    /// is loaded from a save, it runs after `set_pos`, `set_size`, `set_velocity`,
+    /// `set_direction`, `set_id` and `load_state`. The object's own chunk is not part
+    /// of `world` while the hook runs.
+    fn on_load(&mut self, _world: &World) { }
+
+    /// Called before the chunk holding this object leaves the world
+    ///
+    /// Runs when the chunk is removed, replaced or evicted, after an evicted chunk was saved.
+    fn on_unload(&mut self) { }
+
     /// Called when another object right-clicks on this object.  
     /// 
     /// - `other`: The object that initiated the right-click.
@@ -66,53 +219,46 @@ pub trait Object: Any + Send + Sync {
     /// - `other`: The object that initiated the left-click.
     fn on_left_interact(&mut self, _other: &mut dyn Object) { }  
 
+    /// Called when an event posted with `World::post_event` is delivered to this object
+    ///
+    /// Events are delivered after all objects and tiles have ticked. Does nothing by default.
+    ///
+    /// - `event`: The delivered event
+    /// - `world`: Reference to the game world for interaction
+    fn on_event(&mut self, _event: &Event, _world: &mut World) { }
+
+    /// Called when this object takes damage
+    ///
+    /// Does nothing by default. Override to deduct health or apply knockback.
+    ///
+    /// - `damage`: Amount of damage dealt
+    /// - `attack_dir`: Direction the attack came from
+    fn hurt(&mut self, _damage: i32, _attack_dir: Direction) { }
+
     /// Called when this object collides with another object
-    /// Handles the physics of the collision
-    /// 
+    ///
+    /// The world has already separated the pair with `resolve_collision` by the
+    /// time this runs, so overrides only need to react to the contact, e.g. by
+    /// dealing damage. Both objects of a colliding pair receive the call.
+    ///
+    /// The default does nothing. Up to 0.2 it stopped the object along the axis of
+    /// least overlap with a 1.0 buffer; the world now does this for every pair, so
+    /// call `resolve_collision` from an override to get it outside a world.
+    ///
     /// - `other`: The other object involved in the collision
-    fn collision(&mut self, other: &mut dyn Object) {
-        let buffer = 1.0;
-        let self_pos = self.get_pos();
-        let self_size = self.get_size();
-        let other_pos = other.get_pos();
-        let other_size = other.get_size();
-        
-        let self_bounds = (
-            self_pos + vec2(buffer, buffer),
-            self_pos + self_size - vec2(buffer, buffer)
-        );
-        
-        let other_bounds = (
-            other_pos + vec2(buffer, buffer),
-            other_pos + other_size - vec2(buffer, buffer)
-        );
-        
-        if self_bounds.0.x < other_bounds.1.x &&
-           self_bounds.1.x > other_bounds.0.x &&
-           self_bounds.0.y < other_bounds.1.y &&
-           self_bounds.1.y > other_bounds.0.y {
-            let mut velocity = self.get_velocity();
-            
-            let x_overlap = (self_bounds.1.x - other_bounds.0.x).min(other_bounds.1.x - self_bounds.0.x);
-            let y_overlap = (self_bounds.1.y - other_bounds.0.y).min(other_bounds.1.y - self_bounds.0.y);
-            
-            if x_overlap < y_overlap {
-                velocity.x = 0.0;
-            } else if x_overlap > y_overlap {
-                velocity.y = 0.0;
-            } else {
-                velocity.x = 0.0;
-                velocity.y = 0.0;
-            }
-            
-            self.set_velocity(velocity);
-        }
-    }
+    fn collision(&mut self, _other: &mut dyn Object) { }
     
     /// Creates a boxed clone of this object
     fn clone_box(&self) -> Box<dyn Object>;
 }
 
+/// Stable identifier of an object, assigned by the world when the object is spawned.
+///
+/// Unlike a chunk index, an id stays valid while the object moves between chunks
+/// and across save and load. Resolve it with `World::get_object`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ObjectId(pub u64);
+
 /// Serializable data structure representing an object's state.
 /// Used for saving and loading object states from disk.
 #[derive(Serialize, Deserialize)]
@@ -123,6 +269,19 @@ pub struct ObjectData {
     pub pos: Vec2Save,
     /// Size of the object in world units
     pub size: Vec2Save,
+    /// Velocity of the object
+    #[serde(default)]
+    pub velocity: Vec2Save,
+    /// Direction the object is facing
+    #[serde(default)]
+    pub direction: Direction,
+    /// Custom state from `Object::serialize_state`, encoded as a JSON string
+    /// so it can be stored in every save format
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Stable id of the object, `None` for objects that do not store one
+    #[serde(default)]
+    pub id: Option<ObjectId>,
 }
 
 /// Manages the registration and instantiation of object types.
@@ -171,13 +330,43 @@ impl ObjectRegistry {
     pub fn deserialize_object(&self, data: &str) -> Result<Box<dyn Object>, String> {
         let data: ObjectData = serde_json::from_str(data)
             .map_err(|e| format!("Failed to deserialize ObjectData: {}", e))?;
+        self.create_object_from_data(data)
+    }
 
+    /// Deserializes an object from bincode-encoded bytes
+    /// 
+    /// - `data`: Bytes produced by `SerializableObject::serialize_bytes`
+    /// 
+    /// Returns a boxed object on success, or an error message on failure
+    pub fn deserialize_object_bytes(&self, data: &[u8]) -> Result<Box<dyn Object>, String> {
+        let data: ObjectData = bincode::deserialize(data)
+            .map_err(|e| format!("Failed to deserialize ObjectData: {}", e))?;
+        self.create_object_from_data(data)
+    }
+
+    /// Creates an object from its deserialized data
+    /// 
+    /// - `data`: The deserialized object data
+    /// 
+    /// Returns a boxed object on success, or an error message if the type is unknown
+    fn create_object_from_data(&self, data: ObjectData) -> Result<Box<dyn Object>, String> {
         let prototype = self.prototypes.get(&data.type_tag)
             .ok_or_else(|| format!("Unknown object type: {}", data.type_tag))?;
 
         let mut obj = prototype.clone_box();
         obj.set_pos(Vec2::from(data.pos));
         obj.set_size(Vec2::from(data.size));
+        obj.set_velocity(Vec2::from(data.velocity));
+        obj.set_direction(data.direction);
+        if let Some(id) = data.id {
+            obj.set_id(id);
+        }
+
+        if let Some(state) = data.state {
+            let state: serde_json::Value = serde_json::from_str(&state)
+                .map_err(|e| format!("Failed to deserialize state of {}: {}", data.type_tag, e))?;
+            obj.load_state(&state);
+        }
 
         Ok(obj)
     }
@@ -187,19 +376,87 @@ impl ObjectRegistry {
 /// Primarily used for saving and loading game states.
 pub trait SerializableObject {
     /// Serializes the object to a JSON string
-    fn serialize(&self) -> String;
+    ///
+    /// Returns the JSON string, or an error message if serialization fails
+    fn serialize(&self) -> Result<String, String>;
+
+    /// Serializes the object to bincode-encoded bytes
+    ///
+    /// Returns the encoded bytes, or an error message if serialization fails
+    fn serialize_bytes(&self) -> Result<Vec<u8>, String>;
 }
 
 // Default implementation of SerializableObject for any type implementing Object
 impl SerializableObject for dyn Object {
     /// Serializes the object's data to a JSON string
-    /// Includes type tag, position, and size information
-    fn serialize(&self) -> String {
-        let data = ObjectData {
-            type_tag: self.get_type_tag().to_string(),
-            pos: Vec2Save::from(self.get_pos()),
-            size: Vec2Save::from(self.get_size()),
-        };
-        serde_json::to_string(&data).unwrap()
+    /// Includes type tag, position, size, velocity, direction, custom state and id
+    fn serialize(&self) -> Result<String, String> {
+        serde_json::to_string(&object_data(self)?)
+            .map_err(|e| format!("Failed to serialize object {}: {}", self.get_type_tag(), e))
+    }
+
+    /// Serializes the object's data to bincode-encoded bytes
+    /// Includes type tag, position, size, velocity, direction, custom state and id
+    fn serialize_bytes(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(&object_data(self)?)
+            .map_err(|e| format!("Failed to serialize object {}: {}", self.get_type_tag(), e))
+    }
+}
+
+/// Collects the serializable state of an object
+/// 
+/// - `obj`: The object to collect the state of
+///
+/// Returns the object data, or an error message if the custom state fails to serialize
+fn object_data(obj: &dyn Object) -> Result<ObjectData, String> {
+    let state = obj.serialize_state()
+        .map_err(|e| format!("Failed to serialize state of {}: {}", obj.get_type_tag(), e))?;
+    Ok(ObjectData {
+        type_tag: obj.get_type_tag().to_string(),
+        pos: Vec2Save::from(obj.get_pos()),
+        size: Vec2Save::from(obj.get_size()),
+        velocity: Vec2Save::from(obj.get_velocity()),
+        direction: obj.get_direction(),
+        state: state.map(|state| state.to_string()),
+        id: obj.get_id(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::TestObject;
+
+    fn registry() -> ObjectRegistry {
+        let mut registry = ObjectRegistry::new();
+        registry.register(TestObject::new(Vec2::ZERO));
+        registry
+    }
+
+    #[test]
+    fn objects_round_trip_with_their_id() {
+        let mut obj = TestObject::new(Vec2::new(3.0, 4.0));
+        obj.velocity = Vec2::new(1.0, -1.0);
+        obj.id = Some(ObjectId(5));
+        let obj: Box<dyn Object> = Box::new(obj);
+
+        for loaded in [
+            registry().deserialize_object(&obj.serialize().unwrap()).unwrap(),
+            registry().deserialize_object_bytes(&obj.serialize_bytes().unwrap()).unwrap(),
+        ] {
+            assert_eq!(loaded.get_pos(), Vec2::new(3.0, 4.0));
+            assert_eq!(loaded.get_velocity(), Vec2::new(1.0, -1.0));
+            assert_eq!(loaded.get_id(), Some(ObjectId(5)));
+        }
+    }
+
+    #[test]
+    fn baseline_json_without_optional_fields_loads() {
+        let loaded = registry()
+            .deserialize_object(r#"{"type_tag":"test_object","pos":{"x":3.0,"y":4.0},"size":{"x":8.0,"y":8.0}}"#)
+            .unwrap();
+        assert_eq!(loaded.get_pos(), Vec2::new(3.0, 4.0));
+        assert_eq!(loaded.get_velocity(), Vec2::ZERO);
+        assert!(loaded.get_id().is_none());
     }
 }
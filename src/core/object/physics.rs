@@ -0,0 +1,152 @@
+use macroquad::math::Vec2;
+
+use super::Object;
+
+/// Optional platformer physics that objects can embed and drive from `Object::tick`
+///
+/// Object velocity is the displacement applied each tick, and the world zeroes
+/// it along any axis that would move the object into a tile whose `may_pass`
+/// returns `false` before objects tick. `PhysicsBody` builds on that: each
+/// `step` moves the object by its validated velocity, detects landing when the
+/// world stopped a fall, slows it down by `friction` while it stands on the
+/// ground, then adds gravity for the next tick. Top-down games can ignore it
+/// entirely.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhysicsBody {
+    /// Downward acceleration, in velocity units per second
+    pub gravity: f32,
+    /// Largest downward velocity gravity can build up, in velocity units
+    pub max_fall_speed: f32,
+    /// Share of the horizontal velocity lost per second while on the ground, from
+    /// `0.0` (none) to `1.0` (stops within a second)
+    pub friction: f32,
+    on_ground: bool,
+    expected_fall: f32,
+}
+
+impl PhysicsBody {
+    /// Creates a physics body with the given gravity and fall speed limit
+    ///
+    /// The body has no friction; set `friction` to slow it down on the ground.
+    ///
+    /// - `gravity`: Downward acceleration, in velocity units per second
+    /// - `max_fall_speed`: Largest downward velocity gravity can build up
+    pub fn new(gravity: f32, max_fall_speed: f32) -> Self {
+        Self {
+            gravity,
+            max_fall_speed,
+            friction: 0.0,
+            on_ground: false,
+            expected_fall: 0.0,
+        }
+    }
+
+    /// Returns whether the object stood on a solid tile during the last step
+    pub fn on_ground(&self) -> bool {
+        self.on_ground
+    }
+
+    /// Moves the object by its velocity and applies gravity for the next tick
+    ///
+    /// Call this once from `Object::tick` after setting any horizontal velocity.
+    ///
+    /// - `obj`: The object owning this body
+    /// - `dt`: Time elapsed since the last tick in seconds
+    pub fn step(&mut self, obj: &mut dyn Object, dt: f32) {
+        let velocity = obj.get_velocity();
+        self.on_ground = self.expected_fall > 0.0 && velocity.y == 0.0;
+
+        obj.set_pos(obj.get_pos() + velocity);
+        obj.apply_gravity(dt, self.gravity);
+
+        let mut velocity = obj.get_velocity();
+        velocity.y = velocity.y.min(self.max_fall_speed);
+        if self.on_ground {
+            velocity.x *= (1.0 - self.friction * dt).max(0.0);
+        }
+        obj.set_velocity(velocity);
+        self.expected_fall = velocity.y;
+    }
+
+    /// Starts a jump if the object is on the ground
+    ///
+    /// - `obj`: The object owning this body
+    /// - `speed`: Upward velocity of the jump, in velocity units
+    ///
+    /// Returns `true` if the jump started, `false` if the object was airborne
+    pub fn jump(&mut self, obj: &mut dyn Object, speed: f32) -> bool {
+        if !self.on_ground {
+            return false;
+        }
+        let velocity = obj.get_velocity();
+        obj.set_velocity(Vec2::new(velocity.x, -speed));
+        self.on_ground = false;
+        self.expected_fall = 0.0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::vec2;
+
+    use super::*;
+    use crate::core::testing::TestObject;
+
+    /// Lets the body take one step, with the world stopping any fall first if `landed`.
+    fn step(body: &mut PhysicsBody, obj: &mut TestObject, landed: bool) {
+        if landed {
+            obj.velocity.y = 0.0;
+        }
+        body.step(obj, 0.5);
+    }
+
+    #[test]
+    fn steps_move_by_velocity_then_add_capped_gravity() {
+        let mut body = PhysicsBody::new(10.0, 8.0);
+        let mut obj = TestObject::new(vec2(0.0, 0.0));
+        obj.velocity = vec2(2.0, 1.0);
+
+        step(&mut body, &mut obj, false);
+        assert_eq!(obj.pos, vec2(2.0, 1.0));
+        assert_eq!(obj.velocity, vec2(2.0, 6.0));
+        assert!(!body.on_ground());
+
+        step(&mut body, &mut obj, false);
+        assert_eq!(obj.pos, vec2(4.0, 7.0));
+        assert_eq!(obj.velocity, vec2(2.0, 8.0));
+    }
+
+    #[test]
+    fn stopped_falls_land_and_allow_one_jump() {
+        let mut body = PhysicsBody::new(10.0, 8.0);
+        let mut obj = TestObject::new(vec2(0.0, 0.0));
+        assert!(!body.jump(&mut obj, 4.0));
+
+        step(&mut body, &mut obj, false);
+        step(&mut body, &mut obj, true);
+        assert!(body.on_ground());
+
+        assert!(body.jump(&mut obj, 4.0));
+        assert_eq!(obj.velocity.y, -4.0);
+        assert!(!body.jump(&mut obj, 4.0));
+    }
+
+    #[test]
+    fn friction_slows_only_grounded_bodies() {
+        let mut body = PhysicsBody::new(10.0, 8.0);
+        body.friction = 0.5;
+        let mut obj = TestObject::new(vec2(0.0, 0.0));
+        obj.velocity.x = 4.0;
+
+        step(&mut body, &mut obj, false);
+        assert_eq!(obj.velocity.x, 4.0);
+
+        step(&mut body, &mut obj, true);
+        assert_eq!(obj.velocity.x, 3.0);
+
+        body.friction = 5.0;
+        step(&mut body, &mut obj, true);
+        assert_eq!(obj.velocity.x, 0.0);
+    }
+}
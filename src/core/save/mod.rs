@@ -1,8 +1,23 @@
-use macroquad::math::Vec2;
+use macroquad::{color::Color, math::{Rect, Vec2}};
 use serde::{Deserialize, Serialize};
+use std::fs;
+
+use bincode::Options;
+use crate::WorldData;
+
+/// Version of the save format written by this crate.
+///
+/// Bump this whenever the layout of saved data changes, extend `migrate`
+/// to upgrade saves written by older versions and keep reading the old
+/// binary layout in `world_data_from_bytes`.
+///
+/// - `0`: No version recorded. JSON world data holds only the name.
+/// - `1`: World data holds the version, seed, game time, next object id, camera
+///   and `WorldConfig`, and saves can also be written as bincode.
+pub const SAVE_VERSION: u32 = 1;
 
 /// A serializable version of `macroquad::math::Vec2`.
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Vec2Save {
     /// The x-coordinate of the vector.
     pub x: f32,
@@ -26,3 +41,354 @@ impl From<Vec2Save> for Vec2 {
         Vec2::new(save.x, save.y)
     }
 }
+
+/// A serializable version of `macroquad::math::Rect`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RectSave {
+    /// The x-coordinate of the top-left corner.
+    pub x: f32,
+    /// The y-coordinate of the top-left corner.
+    pub y: f32,
+    /// The width of the rectangle.
+    pub w: f32,
+    /// The height of the rectangle.
+    pub h: f32,
+}
+
+impl From<Rect> for RectSave {
+    /// Converts a `Rect` to a `RectSave`.
+    fn from(rect: Rect) -> Self {
+        RectSave {
+            x: rect.x,
+            y: rect.y,
+            w: rect.w,
+            h: rect.h,
+        }
+    }
+}
+
+impl From<RectSave> for Rect {
+    /// Converts a `RectSave` back to a `Rect`.
+    fn from(save: RectSave) -> Self {
+        Rect::new(save.x, save.y, save.w, save.h)
+    }
+}
+
+/// A serializable version of `macroquad::color::Color`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ColorSave {
+    /// The red component, from 0.0 to 1.0.
+    pub r: f32,
+    /// The green component, from 0.0 to 1.0.
+    pub g: f32,
+    /// The blue component, from 0.0 to 1.0.
+    pub b: f32,
+    /// The alpha component, from 0.0 to 1.0.
+    pub a: f32,
+}
+
+impl From<Color> for ColorSave {
+    /// Converts a `Color` to a `ColorSave`.
+    fn from(color: Color) -> Self {
+        ColorSave {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a: color.a,
+        }
+    }
+}
+
+impl From<ColorSave> for Color {
+    /// Converts a `ColorSave` back to a `Color`.
+    fn from(save: ColorSave) -> Self {
+        Color::new(save.r, save.g, save.b, save.a)
+    }
+}
+
+
+/// On-disk format used when saving and loading a world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SaveFormat {
+    /// Human-readable JSON files with a `.json` extension.
+    #[default]
+    Json,
+    /// Compact bincode files with a `.bin` extension.
+    Bincode,
+}
+
+impl SaveFormat {
+    /// Returns the file extension used for files in this format, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SaveFormat::Json => "json",
+            SaveFormat::Bincode => "bin",
+        }
+    }
+}
+
+/// Upgrades world data loaded from an older save to the current `SAVE_VERSION`.
+///
+/// - `old_version`: The version the data was saved with.
+/// - `data`: The loaded world data.
+///
+/// Returns the upgraded data, or an error message if the version is newer than
+/// this crate supports.
+pub fn migrate(old_version: u32, mut data: WorldData) -> Result<WorldData, String> {
+    check_version(old_version)?;
+
+    // Fields missing from version 0 saves are filled in with their serde defaults
+    // while reading, so no step changes the data yet.
+    // Future format changes add a step per version here.
+
+    data.version = SAVE_VERSION;
+    Ok(data)
+}
+
+/// Checks that a save was not written by a newer version of this crate.
+///
+/// - `version`: The version the save was written with.
+///
+/// Returns `Ok(())` if this crate can read the save, or an error message otherwise.
+fn check_version(version: u32) -> Result<(), String> {
+    if version > SAVE_VERSION {
+        return Err(format!(
+            "Save version {} is newer than the supported version {}",
+            version, SAVE_VERSION
+        ));
+    }
+    Ok(())
+}
+
+/// Encodes world data for a `world.bin` file.
+///
+/// - `world_data`: The world data to encode.
+///
+/// The file starts with `SAVE_VERSION` as a little-endian `u32`, followed by the
+/// bincode encoding of the data, so readers can pick the layout before decoding it.
+///
+/// Returns the encoded bytes, or an error message on failure.
+pub(crate) fn world_data_to_bytes(world_data: &WorldData) -> Result<Vec<u8>, String> {
+    let mut bytes = SAVE_VERSION.to_le_bytes().to_vec();
+    bincode::serialize_into(&mut bytes, world_data).map_err(|e| format!("Failed to write world data: {}", e))?;
+    Ok(bytes)
+}
+
+/// Reads world data written by `world_data_to_bytes`.
+///
+/// - `data`: The contents of a `world.bin` file.
+///
+/// Returns the world data as saved, before `migrate`, or an error message if the
+/// version prefix is missing or unknown, the save is newer than this crate supports,
+/// or the data does not match the layout of its version.
+pub(crate) fn world_data_from_bytes(data: &[u8]) -> Result<WorldData, String> {
+    let (prefix, payload) = data.split_first_chunk::<4>()
+        .ok_or_else(|| "Failed to read world data: missing version".to_string())?;
+    let version = u32::from_le_bytes(*prefix);
+    check_version(version)?;
+
+    match version {
+        1 => bincode::options()
+            .with_fixint_encoding()
+            .reject_trailing_bytes()
+            .deserialize(payload)
+            .map_err(|e| format!("Failed to read world data: {}", e)),
+        _ => Err(format!("Failed to read world data: no binary layout for version {}", version)),
+    }
+}
+
+/// Writes a file atomically by writing to a temporary file and renaming it into place.
+///
+/// The rename is atomic on the same filesystem, so a crash during the write leaves
+/// either the old file or the new one, never a truncated file.
+///
+/// - `path`: The path of the file to write.
+/// - `data`: The contents of the file.
+///
+/// Returns `Ok(())` on success, or an error message on failure.
+pub fn write_atomic(path: &str, data: &[u8]) -> Result<(), String> {
+    let tmp_path = format!("{}.tmp", path);
+    fs::write(&tmp_path, data).map_err(|e| format!("Failed to write {}: {}", tmp_path, e))?;
+    fs::rename(&tmp_path, path).map_err(|e| format!("Failed to rename {} to {}: {}", tmp_path, path, e))
+}
+
+/// Extension appended to the name of compressed chunk files, without the dot.
+pub(crate) const COMPRESSED_EXTENSION: &str = "gz";
+
+/// Compresses the contents of a file with gzip.
+///
+/// - `data`: The uncompressed contents.
+///
+/// Returns the compressed contents, or an error message on failure.
+#[cfg(feature = "gzip")]
+pub(crate) fn compress(data: &[u8]) -> Result<Vec<u8>, String> {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).map_err(|e| format!("Failed to compress: {}", e))?;
+    encoder.finish().map_err(|e| format!("Failed to compress: {}", e))
+}
+
+/// Decompresses the contents of a gzip-compressed file.
+///
+/// - `data`: The compressed contents.
+///
+/// Returns the uncompressed contents, or an error message on failure.
+#[cfg(feature = "gzip")]
+pub(crate) fn decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(data)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| format!("Failed to decompress: {}", e))?;
+    Ok(decompressed)
+}
+
+/// Decompresses the contents of a gzip-compressed file.
+///
+/// Always fails, as the crate was built without the `gzip` feature.
+#[cfg(not(feature = "gzip"))]
+pub(crate) fn decompress(_data: &[u8]) -> Result<Vec<u8>, String> {
+    Err("Compressed chunk files require the gzip feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WorldConfig;
+
+    fn world_data() -> WorldData {
+        WorldData {
+            version: SAVE_VERSION,
+            name: "test".to_string(),
+            seed: 42,
+            game_time: 12.5,
+            next_object_id: 7,
+            camera: Some(Vec2Save { x: 3.0, y: 4.0 }),
+            camera_zoom: 2.0,
+            config: WorldConfig { tile_size: 8.0, chunk_size: 4, object_activation_margin: 10.0 },
+        }
+    }
+
+    #[test]
+    fn world_data_round_trips_in_both_formats() {
+        let json: WorldData = serde_json::from_slice(&serde_json::to_vec(&world_data()).unwrap()).unwrap();
+        let bin = world_data_from_bytes(&world_data_to_bytes(&world_data()).unwrap()).unwrap();
+        for loaded in [json, bin] {
+            assert_eq!(loaded.version, SAVE_VERSION);
+            assert_eq!((loaded.name.as_str(), loaded.seed, loaded.game_time), ("test", 42, 12.5));
+            assert_eq!(loaded.next_object_id, 7);
+            assert_eq!(loaded.camera.map(|camera| (camera.x, camera.y)), Some((3.0, 4.0)));
+            assert_eq!(loaded.camera_zoom, 2.0);
+            assert_eq!(loaded.config.chunk_size, 4);
+        }
+    }
+
+    #[test]
+    fn rects_and_colors_round_trip_in_both_formats() {
+        let rect = Rect::new(-1.5, 2.0, 30.0, 0.25);
+        let color = Color::new(0.1, 0.5, 1.0, 0.75);
+        let pair = (RectSave::from(rect), ColorSave::from(color));
+
+        let json: (RectSave, ColorSave) = serde_json::from_str(&serde_json::to_string(&pair).unwrap()).unwrap();
+        let bin: (RectSave, ColorSave) = bincode::deserialize(&bincode::serialize(&pair).unwrap()).unwrap();
+        for (loaded_rect, loaded_color) in [json, bin] {
+            assert_eq!(Rect::from(loaded_rect), rect);
+            assert_eq!(Color::from(loaded_color), color);
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn gzip_compressed_saves_round_trip() {
+        use crate::core::testing::{registries, TestObject, TestTile};
+        use crate::{Chunk, World, TILE_SIZE};
+        use macroquad::math::vec2;
+
+        let grass_chunk = |x: i32, y: i32| {
+            let mut chunk = Chunk::new(vec2(x as f32, y as f32));
+            let (origin, size) = (chunk.bounds().0, chunk.config().chunk_size);
+            for ty in 0..size {
+                for tx in 0..size {
+                    let tile = TestTile { pos: origin + vec2(tx as f32, ty as f32) * TILE_SIZE, ..TestTile::new("grass") };
+                    chunk.tiles.push(Box::new(tile));
+                }
+            }
+            chunk
+        };
+
+        let tiles_of = |chunk: &crate::Chunk| -> Vec<_> {
+            chunk.tiles.iter().map(|tile| (tile.get_type_tag(), tile.get_pos())).collect()
+        };
+        let objects_of = |chunk: &crate::Chunk| -> Vec<_> {
+            chunk.objects.iter().map(|obj| (obj.get_type_tag(), obj.get_pos(), obj.get_id(), obj.get_velocity())).collect()
+        };
+        for format in [SaveFormat::Json, SaveFormat::Bincode] {
+            let save_dir = std::env::temp_dir()
+                .join(format!("gaymwtf-core-gzip-{}-{}", format.extension(), std::process::id()))
+                .to_string_lossy()
+                .into_owned();
+            let _ = fs::remove_dir_all(&save_dir);
+            let (tiles, objects, biomes) = registries();
+            let mut world = World::new("test", tiles, objects, biomes);
+            world.add_chunk(grass_chunk(0, 0));
+            world.add_chunk(grass_chunk(-1, 2));
+            let mob = TestObject { velocity: vec2(1.0, -2.0), ..TestObject::new(vec2(10.0, 20.0)) };
+            world.spawn_object(Box::new(mob)).unwrap();
+            world.set_chunk_compression(true);
+            world.save_world_as(&save_dir, format).unwrap();
+
+            for entry in fs::read_dir(format!("{}/chunks", save_dir)).unwrap() {
+                let path = entry.unwrap().path();
+                assert_eq!(path.extension().and_then(|ext| ext.to_str()), Some(COMPRESSED_EXTENSION));
+                let compressed = fs::read(&path).unwrap();
+                assert!(compressed.len() < decompress(&compressed).unwrap().len());
+            }
+
+            let (tiles, objects, biomes) = registries();
+            let loaded = World::load_world_as(&save_dir, format, tiles, objects, biomes).unwrap();
+            for pos in [(0, 0), (-1, 2)] {
+                let (saved, loaded) = (world.get_chunk(pos).unwrap(), loaded.get_chunk(pos).unwrap());
+                assert_eq!(tiles_of(loaded), tiles_of(saved));
+                assert_eq!(objects_of(loaded), objects_of(saved));
+            }
+            let _ = fs::remove_dir_all(&save_dir);
+        }
+    }
+
+    #[test]
+    fn v0_json_without_version_loads() {
+        let loaded: WorldData = serde_json::from_str(r#"{"name":"old"}"#).unwrap();
+        let loaded = migrate(loaded.version, loaded).unwrap();
+        assert_eq!((loaded.version, loaded.name.as_str(), loaded.seed), (SAVE_VERSION, "old", 0));
+        assert!(loaded.camera.is_none());
+        assert_eq!(loaded.camera_zoom, 1.0);
+    }
+
+    #[test]
+    fn newer_versions_are_rejected() {
+        let newer = WorldData { version: SAVE_VERSION + 1, ..world_data() };
+        assert!(migrate(newer.version, newer).is_err());
+        let mut newer_layout = (SAVE_VERSION + 1).to_le_bytes().to_vec();
+        newer_layout.extend_from_slice(b"new");
+        let error = world_data_from_bytes(&newer_layout).err().unwrap();
+        assert!(error.contains("newer"), "{}", error);
+    }
+
+    #[test]
+    fn bincode_world_data_is_read_by_its_version_prefix() {
+        let bytes = world_data_to_bytes(&world_data()).unwrap();
+        assert_eq!(bytes[..4], SAVE_VERSION.to_le_bytes());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(world_data_from_bytes(&trailing).is_err());
+        assert!(world_data_from_bytes(&bytes[..2]).is_err());
+
+        let mut unversioned = bytes;
+        unversioned[..4].copy_from_slice(&0u32.to_le_bytes());
+        let error = world_data_from_bytes(&unversioned).err().unwrap();
+        assert!(error.contains("version 0"), "{}", error);
+    }
+}
@@ -0,0 +1,115 @@
+//! Plain tile and object types registered by `registries` for the unit tests of the core modules.
+
+use macroquad::math::Vec2;
+
+use crate::{BiomeRegistry, DrawBatch, Object, ObjectId, ObjectRegistry, Tile, TileRegistry, World, TILE_SIZE};
+
+/// A plain square tile that does nothing on its own.
+#[derive(Clone)]
+pub struct TestTile {
+    pub pos: Vec2,
+    pub tag: &'static str,
+}
+
+impl TestTile {
+    pub fn new(tag: &'static str) -> Self {
+        Self { pos: Vec2::ZERO, tag }
+    }
+}
+
+impl Tile for TestTile {
+    fn get_type_tag(&self) -> &'static str {
+        self.tag
+    }
+
+    fn get_pos(&self) -> Vec2 {
+        self.pos
+    }
+
+    fn get_size(&self) -> Vec2 {
+        Vec2::splat(TILE_SIZE)
+    }
+
+    fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+    fn set_pos(&mut self, pos: Vec2) {
+        self.pos = pos;
+    }
+
+    fn clone_box(&self) -> Box<dyn Tile> {
+        Box::new(self.clone())
+    }
+}
+
+/// An object that stores its id and moves by its velocity when ticked.
+#[derive(Clone)]
+pub struct TestObject {
+    pub pos: Vec2,
+    pub size: Vec2,
+    pub velocity: Vec2,
+    pub id: Option<ObjectId>,
+}
+
+impl TestObject {
+    pub fn new(pos: Vec2) -> Self {
+        Self { pos, size: Vec2::splat(8.0), velocity: Vec2::ZERO, id: None }
+    }
+}
+
+impl Object for TestObject {
+    fn get_type_tag(&self) -> &'static str {
+        "test_object"
+    }
+
+    fn get_pos(&self) -> Vec2 {
+        self.pos
+    }
+
+    fn get_size(&self) -> Vec2 {
+        self.size
+    }
+
+    fn get_velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    fn tick(&mut self, dt: f32, _world: &mut World) -> bool {
+        self.pos += self.velocity * dt;
+        self.velocity != Vec2::ZERO
+    }
+
+    fn draw(&self, _batch: &mut DrawBatch) {}
+
+    fn set_size(&mut self, size: Vec2) {
+        self.size = size;
+    }
+
+    fn set_pos(&mut self, pos: Vec2) {
+        self.pos = pos;
+    }
+
+    fn set_velocity(&mut self, velocity: Vec2) {
+        self.velocity = velocity;
+    }
+
+    fn get_id(&self) -> Option<ObjectId> {
+        self.id
+    }
+
+    fn set_id(&mut self, id: ObjectId) {
+        self.id = Some(id);
+    }
+
+    fn clone_box(&self) -> Box<dyn Object> {
+        Box::new(self.clone())
+    }
+}
+
+/// Creates registries that know `TestTile` as "grass" and `TestObject`.
+pub fn registries() -> (TileRegistry, ObjectRegistry, BiomeRegistry) {
+    let mut tiles = TileRegistry::new();
+    tiles.register(TestTile::new("grass"));
+    let mut objects = ObjectRegistry::new();
+    objects.register(TestObject::new(Vec2::ZERO));
+    (tiles, objects, BiomeRegistry::new())
+}
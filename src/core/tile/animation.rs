@@ -0,0 +1,131 @@
+use macroquad::math::Rect;
+use macroquad::texture::Texture2D;
+
+/// Frame animation helper for tiles.
+///
+/// Holds a list of frames and cycles through them as time advances.
+/// Call `advance` from `Tile::tick` and draw `current_frame` in `Tile::draw`.
+#[derive(Clone)]
+pub struct TileAnimation {
+    /// Textures of the animation, in playback order
+    pub frames: Vec<Texture2D>,
+    /// Time each frame is shown, in seconds
+    pub frame_time: f32,
+    /// Time elapsed since the animation started, in seconds
+    elapsed: f32,
+}
+
+impl TileAnimation {
+    /// Creates a new animation starting at the first frame.
+    ///
+    /// - `frames`: Textures of the animation, in playback order.
+    /// - `frame_time`: Time each frame is shown, in seconds.
+    pub fn new(frames: Vec<Texture2D>, frame_time: f32) -> Self {
+        Self {
+            frames,
+            frame_time,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Advances the animation.
+    ///
+    /// - `dt`: Time elapsed since the last call in seconds.
+    pub fn advance(&mut self, dt: f32) {
+        self.elapsed += dt;
+        let cycle = self.frame_time * self.frames.len() as f32;
+        if cycle > 0.0 {
+            self.elapsed %= cycle;
+        }
+    }
+
+    /// Returns the index of the frame that should currently be shown.
+    pub fn frame_index(&self) -> usize {
+        frame_index(self.elapsed, self.frame_time, self.frames.len())
+    }
+
+    /// Returns the texture of the frame that should currently be shown,
+    /// or `None` if the animation has no frames.
+    pub fn current_frame(&self) -> Option<&Texture2D> {
+        self.frames.get(self.frame_index())
+    }
+}
+
+/// Returns the source rect of the current frame of an animation laid out
+/// horizontally on a single sprite sheet.
+///
+/// Pass the result as `DrawParams::source` to draw the frame with a `DrawBatch`.
+///
+/// - `time`: Time elapsed since the animation started, in seconds.
+/// - `frame_time`: Time each frame is shown, in seconds.
+/// - `frame_size`: Width and height of one frame in texture pixels.
+/// - `frame_count`: Number of frames on the sheet.
+pub fn animated_source_rect(time: f32, frame_time: f32, frame_size: (f32, f32), frame_count: usize) -> Rect {
+    let index = frame_index(time, frame_time, frame_count);
+    Rect::new(index as f32 * frame_size.0, 0.0, frame_size.0, frame_size.1)
+}
+
+/// Computes which frame of a looping animation is shown at a given time.
+///
+/// - `time`: Time elapsed since the animation started, in seconds.
+/// - `frame_time`: Time each frame is shown, in seconds.
+/// - `frame_count`: Number of frames in the animation.
+fn frame_index(time: f32, frame_time: f32, frame_count: usize) -> usize {
+    if frame_count == 0 || frame_time <= 0.0 {
+        return 0;
+    }
+    (time / frame_time).floor().max(0.0) as usize % frame_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::miniquad::{RawId, TextureId};
+
+    /// Creates an animation with the given number of frames, without a graphics context.
+    fn animation(frames: u32, frame_time: f32) -> TileAnimation {
+        let frames = (0..frames)
+            .map(|id| Texture2D::from_miniquad_texture(TextureId::from_raw_id(RawId::OpenGl(id))))
+            .collect();
+        TileAnimation::new(frames, frame_time)
+    }
+
+    #[test]
+    fn frames_change_every_frame_time() {
+        for (time, expected) in [(0.0, 0), (0.24, 0), (0.25, 1), (0.6, 2), (0.99, 3), (1.0, 0), (2.6, 2)] {
+            assert_eq!(frame_index(time, 0.25, 4), expected, "time {}", time);
+        }
+    }
+
+    #[test]
+    fn advancing_wraps_around_the_cycle() {
+        let mut anim = animation(3, 0.5);
+        anim.advance(0.6);
+        assert_eq!(anim.frame_index(), 1);
+        anim.advance(1.0);
+        assert_eq!(anim.frame_index(), 0);
+        assert!(anim.elapsed < 1.5);
+
+        anim.advance(1000.0 * 1.5 + 1.2);
+        assert_eq!(anim.frame_index(), 2);
+        assert!(anim.current_frame().is_some());
+    }
+
+    #[test]
+    fn empty_or_timeless_animations_stay_on_the_first_frame() {
+        let mut empty = animation(0, 0.5);
+        empty.advance(3.0);
+        assert_eq!(empty.frame_index(), 0);
+        assert!(empty.current_frame().is_none());
+
+        let mut timeless = animation(2, 0.0);
+        timeless.advance(3.0);
+        assert_eq!(timeless.frame_index(), 0);
+    }
+
+    #[test]
+    fn sheet_frames_are_laid_out_horizontally() {
+        assert_eq!(animated_source_rect(0.3, 0.1, (16.0, 8.0), 4), Rect::new(48.0, 0.0, 16.0, 8.0));
+        assert_eq!(animated_source_rect(0.45, 0.1, (16.0, 8.0), 4), Rect::new(0.0, 0.0, 16.0, 8.0));
+    }
+}
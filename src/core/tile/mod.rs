@@ -1,10 +1,32 @@
-use macroquad::math::Vec2;
-use crate::{DrawBatch, Object, World};
+use macroquad::{color::Color, math::Vec2};
+use crate::{log_world, DrawBatch, Object, World};
 use std::any::Any;
 use serde::{Serialize, Deserialize};
 use crate::core::save::Vec2Save;
+use crate::utils::hash::hash2;
 use std::collections::HashMap;
 
+pub mod animation;
+
+pub use animation::{animated_source_rect, TileAnimation};
+
+/// Bit set in a neighbor mask when the tile above has the same type.
+pub const NEIGHBOR_UP: u8 = 1 << 0;
+/// Bit set in a neighbor mask when the tile above and to the right has the same type.
+pub const NEIGHBOR_UP_RIGHT: u8 = 1 << 1;
+/// Bit set in a neighbor mask when the tile to the right has the same type.
+pub const NEIGHBOR_RIGHT: u8 = 1 << 2;
+/// Bit set in a neighbor mask when the tile below and to the right has the same type.
+pub const NEIGHBOR_DOWN_RIGHT: u8 = 1 << 3;
+/// Bit set in a neighbor mask when the tile below has the same type.
+pub const NEIGHBOR_DOWN: u8 = 1 << 4;
+/// Bit set in a neighbor mask when the tile below and to the left has the same type.
+pub const NEIGHBOR_DOWN_LEFT: u8 = 1 << 5;
+/// Bit set in a neighbor mask when the tile to the left has the same type.
+pub const NEIGHBOR_LEFT: u8 = 1 << 6;
+/// Bit set in a neighbor mask when the tile above and to the left has the same type.
+pub const NEIGHBOR_UP_LEFT: u8 = 1 << 7;
+
 /// Represents a static game element that is part of the world's terrain or environment.
 /// Tiles are the basic building blocks of the game world and are typically used for terrain.
 pub trait Tile: Any + Send + Sync {
@@ -21,7 +43,20 @@ pub trait Tile: Any + Send + Sync {
     /// 
     /// - `dt`: Time elapsed since the last frame in seconds
     /// - `world`: Reference to the game world for interaction
-    fn tick(&mut self, _dt: f32, _world: &mut World) {}
+    ///
+    /// Returns `true` if the tick changed anything that is saved, such as the
+    /// variant, so the tile's chunk is marked dirty. Returns `false` by default.
+    fn tick(&mut self, _dt: f32, _world: &mut World) -> bool { false }
+
+    /// Returns whether this tile looks at or changes the tiles of its own chunk in `tick`
+    ///
+    /// Return `true` to have the world swap the tile for a copy of itself during
+    /// its tick, so `World::tile_neighbors`, `tile_at` and `tile_at_mut` reach the
+    /// tiles of its chunk. Copying costs a clone per tick, so other tiles are
+    /// ticked while their chunk is taken out of the world and only see the other chunks.
+    fn reads_neighbors(&self) -> bool {
+        false
+    }
     
     /// Draws the tile on the screen
     /// 
@@ -29,12 +64,96 @@ pub trait Tile: Any + Send + Sync {
     /// - `pos`: The position to draw the tile at
     fn draw(&self, batch: &mut DrawBatch, pos: Vec2);
 
+    /// Returns whether this tile picks its sprite based on its neighbors
+    ///
+    /// Return `true` to have the world call `draw_connected` with a neighbor mask
+    /// instead of `draw`. Computing the mask costs eight tile lookups per tile, so
+    /// it is skipped for tiles that keep the default `false`.
+    fn is_connected(&self) -> bool {
+        false
+    }
+
+    /// Draws the tile knowing which of its neighbors have the same type
+    ///
+    /// - `batch`: The draw batch to add drawing commands to
+    /// - `pos`: The position to draw the tile at
+    /// - `neighbor_mask`: Combination of the `NEIGHBOR_*` bits for every
+    ///   neighbor with the same type tag, see `World::neighbor_mask`
+    ///
+    /// Only called for tiles whose `is_connected` returns `true`. Defaults to `draw`.
+    fn draw_connected(&self, batch: &mut DrawBatch, pos: Vec2, _neighbor_mask: u8) {
+        self.draw(batch, pos);
+    }
+
     /// Sets the position of the tile in world coordinates
     fn set_pos(&mut self, pos: Vec2);
     
     /// Sets the size of the tile in world units
     fn set_size(&mut self, _size: Vec2) {}
 
+    /// Returns whether objects may move through this tile
+    ///
+    /// Tiles are passable by default. Return `false` to make the world stop
+    /// objects from moving into this tile.
+    fn may_pass(&self) -> bool {
+        true
+    }
+
+    /// Returns how much light this tile emits, from `0.0` (none) to `1.0` (full)
+    ///
+    /// Used by `World::compute_light`. Tiles emit no light by default.
+    fn light_emission(&self) -> f32 {
+        0.0
+    }
+
+    /// Returns how much of the light passing through this tile is blocked
+    ///
+    /// `0.0` lets light through unchanged and `1.0` stops it completely. Opaque
+    /// tiles are still lit themselves, they just do not pass light on. Used by
+    /// `World::compute_light`. Tiles are fully transparent by default.
+    fn light_opacity(&self) -> f32 {
+        0.0
+    }
+
+    /// Returns the color this tile is shown with on a minimap
+    ///
+    /// Used by `World::minimap_colors`. Returns `None` by default, which shows
+    /// the tile as gray.
+    fn minimap_color(&self) -> Option<Color> {
+        None
+    }
+
+    /// Returns the index of the visual variant of this tile
+    ///
+    /// Tiles registered with `TileRegistry::register_variants` should store the
+    /// index passed to `set_variant` and return it here, so saves record which
+    /// variant was used.
+    fn get_variant(&self) -> u32 {
+        0
+    }
+
+    /// Sets the index of the visual variant of this tile
+    ///
+    /// - `variant`: Index of the variant in the list passed to `register_variants`
+    fn set_variant(&mut self, _variant: u32) {}
+
+    /// Called after the chunk holding this tile became part of the world
+    ///
+    /// - `world`: The world the chunk was added to
+    ///
+    /// Use it to rebind runtime-only resources that are not saved, such as texture
+    /// handles. Runs for chunks added, reloaded from disk or generated, and only
+    /// through `World`: `Chunk::deserialize` does not run it. When a chunk
+    /// is loaded from a save, it runs after the tile was created from its prototype
+    /// and given its position and variant. The tile's own chunk is not part of
+    /// `world` while the hook runs.
+    fn on_load(&mut self, _world: &World) {}
+
+    /// Called before the chunk holding this tile leaves the world
+    ///
+    /// Runs when the chunk is removed, replaced or evicted, after an evicted chunk was saved.
+    fn on_unload(&mut self) {}
+
     /// Called when object right-clicks on this tile.  
     /// 
     /// - `obj`: The object that initiated the right-click.
@@ -59,6 +178,9 @@ pub struct TileData {
     pub pos: Vec2Save,
     /// Size of the tile in world units
     pub size: Vec2Save,
+    /// Index of the visual variant of the tile
+    #[serde(default)]
+    pub variant: u32,
 }
 
 /// Manages the registration and instantiation of tile types.
@@ -66,6 +188,8 @@ pub struct TileData {
 pub struct TileRegistry {
     /// Map of tile type tags to their prototype instances
     prototypes: HashMap<String, Box<dyn Tile>>,
+    /// Map of tile type tags to the prototypes of their visual variants
+    variants: HashMap<String, Vec<Box<dyn Tile>>>,
 }
 
 impl Default for TileRegistry {
@@ -79,6 +203,7 @@ impl TileRegistry {
     pub fn new() -> Self {
         Self {
             prototypes: HashMap::new(),
+            variants: HashMap::new(),
         }
     }
 
@@ -86,10 +211,61 @@ impl TileRegistry {
     /// 
     /// - `tile`: The prototype tile to register
     /// - `T`: Type parameter that implements both Tile and 'static
+    ///
+    /// Replaces variants registered for the same type with `register_variants`,
+    /// which is logged as a warning.
     pub fn register<T: Tile + 'static>(&mut self, tile: T) {
+        if self.variants.remove(tile.get_type_tag()).is_some() {
+            log_world!(log::Level::Warn, "Registering tile {} replaces its variants", tile.get_type_tag());
+        }
         self.prototypes.insert(tile.get_type_tag().to_string(), Box::new(tile));
     }
 
+    /// Registers several visual variants of one tile type with the registry
+    /// 
+    /// - `type_tag`: The type identifier shared by all variants
+    /// - `variants`: The prototype of every variant, the first one is the default
+    /// 
+    /// Each prototype is told its index through `Tile::set_variant`.
+    /// Use `create_tile_variant` to pick a variant based on position.
+    ///
+    /// Returns `Ok(())` on success, or an error message if `variants` is empty
+    pub fn register_variants(&mut self, type_tag: &str, mut variants: Vec<Box<dyn Tile>>) -> Result<(), String> {
+        if variants.is_empty() {
+            return Err(format!("No variants given for tile {}", type_tag));
+        }
+        for (index, variant) in variants.iter_mut().enumerate() {
+            variant.set_variant(index as u32);
+        }
+        self.prototypes.insert(type_tag.to_string(), variants[0].clone_box());
+        self.variants.insert(type_tag.to_string(), variants);
+        Ok(())
+    }
+
+    /// Creates a new instance of a tile, picking a variant from its position
+    /// 
+    /// - `type_tag`: The type identifier of the tile to create
+    /// - `pos`: The world position the tile is created at
+    /// - `seed`: The world seed, e.g. `GenContext::seed`
+    /// - `tile_size`: The tile size of the world, e.g. `WorldConfig::tile_size`
+    /// 
+    /// The variant is chosen by hashing the tile grid position with the seed, so the
+    /// same position always gets the same variant in a world. The tile's position is
+    /// set to `pos`.
+    /// 
+    /// Returns `Some(boxed_tile)` if found, `None` otherwise
+    pub fn create_tile_variant(&self, type_tag: &str, pos: Vec2, seed: u64, tile_size: f32) -> Option<Box<dyn Tile>> {
+        let mut tile = match self.variants.get(type_tag) {
+            Some(variants) => {
+                let index = hash_tile_pos(pos, seed, tile_size) as usize % variants.len();
+                variants[index].clone_box()
+            }
+            None => self.create_tile_by_id(type_tag)?,
+        };
+        tile.set_pos(pos);
+        Some(tile)
+    }
+
     /// Creates a new instance of a tile by its type tag
     /// 
     /// - `type_tag`: The type identifier of the tile to create
@@ -107,8 +283,29 @@ impl TileRegistry {
     pub fn deserialize_tile(&self, data: &str) -> Result<Box<dyn Tile>, String> {
         let data: TileData = serde_json::from_str(data)
             .map_err(|e| format!("Failed to deserialize TileData: {}", e))?;
+        self.create_tile_from_data(data)
+    }
+
+    /// Deserializes a tile from bincode-encoded bytes
+    /// 
+    /// - `data`: Bytes produced by `SerializableTile::serialize_bytes`
+    /// 
+    /// Returns a boxed tile on success, or an error message on failure
+    pub fn deserialize_tile_bytes(&self, data: &[u8]) -> Result<Box<dyn Tile>, String> {
+        let data: TileData = bincode::deserialize(data)
+            .map_err(|e| format!("Failed to deserialize TileData: {}", e))?;
+        self.create_tile_from_data(data)
+    }
 
-        let prototype = self.prototypes.get(&data.type_tag)
+    /// Creates a tile from its deserialized data
+    /// 
+    /// - `data`: The deserialized tile data
+    /// 
+    /// Returns a boxed tile on success, or an error message if the type is unknown
+    fn create_tile_from_data(&self, data: TileData) -> Result<Box<dyn Tile>, String> {
+        let variant = self.variants.get(&data.type_tag)
+            .and_then(|variants| variants.get(data.variant as usize));
+        let prototype = variant.or_else(|| self.prototypes.get(&data.type_tag))
             .ok_or_else(|| format!("Unknown tile type: {}", data.type_tag))?;
 
         let mut tile = prototype.clone_box();
@@ -123,19 +320,136 @@ impl TileRegistry {
 /// Primarily used for saving and loading game states.
 pub trait SerializableTile {
     /// Serializes the tile to a JSON string
-    fn serialize(&self) -> String;
+    ///
+    /// Returns the JSON string, or an error message if serialization fails
+    fn serialize(&self) -> Result<String, String>;
+
+    /// Serializes the tile to bincode-encoded bytes
+    ///
+    /// Returns the encoded bytes, or an error message if serialization fails
+    fn serialize_bytes(&self) -> Result<Vec<u8>, String>;
 }
 
 // Default implementation of SerializableTile for any type implementing Tile
 impl SerializableTile for dyn Tile {
     /// Serializes the tile's data to a JSON string
     /// Includes type tag, position, and size information
-    fn serialize(&self) -> String {
-        let data = TileData {
-            type_tag: self.get_type_tag().to_string(),
-            pos: Vec2Save::from(self.get_pos()),
-            size: Vec2Save::from(self.get_size()),
-        };
-        serde_json::to_string(&data).unwrap()
+    fn serialize(&self) -> Result<String, String> {
+        serde_json::to_string(&tile_data(self))
+            .map_err(|e| format!("Failed to serialize tile {}: {}", self.get_type_tag(), e))
+    }
+
+    /// Serializes the tile's data to bincode-encoded bytes
+    /// Includes type tag, position, and size information
+    fn serialize_bytes(&self) -> Result<Vec<u8>, String> {
+        bincode::serialize(&tile_data(self))
+            .map_err(|e| format!("Failed to serialize tile {}: {}", self.get_type_tag(), e))
+    }
+}
+
+/// Collects the serializable state of a tile
+/// 
+/// - `tile`: The tile to collect the state of
+fn tile_data(tile: &dyn Tile) -> TileData {
+    TileData {
+        type_tag: tile.get_type_tag().to_string(),
+        pos: Vec2Save::from(tile.get_pos()),
+        size: Vec2Save::from(tile.get_size()),
+        variant: tile.get_variant(),
+    }
+}
+
+/// Hashes the tile grid cell containing a world position
+/// 
+/// - `pos`: Position in world coordinates
+/// - `seed`: Seed of the hash
+/// - `tile_size`: Size of a grid cell in world units
+fn hash_tile_pos(pos: Vec2, seed: u64, tile_size: f32) -> u32 {
+    hash2(seed, (pos.x / tile_size).floor() as i32, (pos.y / tile_size).floor() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TILE_SIZE;
+
+    /// A tile with visual variants
+    #[derive(Clone)]
+    struct Flower {
+        pos: Vec2,
+        variant: u32,
+    }
+
+    impl Tile for Flower {
+        fn get_type_tag(&self) -> &'static str {
+            "flower"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn get_variant(&self) -> u32 {
+            self.variant
+        }
+
+        fn set_variant(&mut self, variant: u32) {
+            self.variant = variant;
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn registry() -> TileRegistry {
+        let mut registry = TileRegistry::new();
+        let variants = (0..4).map(|_| Box::new(Flower { pos: Vec2::ZERO, variant: 0 }) as Box<dyn Tile>).collect();
+        registry.register_variants("flower", variants).unwrap();
+        registry
+    }
+
+    fn variants(registry: &TileRegistry, seed: u64, tile_size: f32) -> Vec<u32> {
+        (0..64)
+            .map(|x| registry.create_tile_variant("flower", Vec2::new(x as f32 * tile_size, 0.0), seed, tile_size).unwrap())
+            .map(|tile| tile.get_variant())
+            .collect()
+    }
+
+    #[test]
+    fn variants_depend_on_the_seed_and_the_tile_grid() {
+        let registry = registry();
+        assert_eq!(variants(&registry, 7, 8.0), variants(&registry, 7, 8.0));
+        assert_ne!(variants(&registry, 7, 8.0), variants(&registry, 8, 8.0));
+        // Positions within one cell of the world's tile size share a variant
+        let inside_cells: Vec<u32> = (0..64)
+            .map(|x| registry.create_tile_variant("flower", Vec2::new(x as f32 * 8.0 + 7.5, 3.0), 7, 8.0).unwrap())
+            .map(|tile| tile.get_variant())
+            .collect();
+        assert_eq!(variants(&registry, 7, 8.0), inside_cells);
+    }
+
+    #[test]
+    fn registering_without_variants_fails() {
+        let mut registry = TileRegistry::new();
+        assert!(registry.register_variants("flower", Vec::new()).is_err());
+        assert!(registry.create_tile_by_id("flower").is_none());
+    }
+
+    #[test]
+    fn registering_a_plain_tile_replaces_its_variants() {
+        let mut registry = registry();
+        registry.register(Flower { pos: Vec2::ZERO, variant: 9 });
+        assert_eq!(variants(&registry, 7, TILE_SIZE), vec![9; 64]);
     }
 }
@@ -1,5 +1,6 @@
 use macroquad::prelude::*;
 use super::Element;
+use crate::utils::draw::DrawBatch;
 
 /// Represents the visual and interactive state of a button.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,11 +16,59 @@ pub enum ButtonState {
 
 }
 
+/// Visual style of a button.
+///
+/// The default style matches the built-in gray look of `Button`.
+#[derive(Clone)]
+pub struct ButtonStyle {
+    /// Background color in the `Normal` state.
+    pub normal_background: Color,
+    /// Background color in the `Hovered` state.
+    pub hovered_background: Color,
+    /// Background color in the `Pressed` state.
+    pub pressed_background: Color,
+    /// Background color in the `Disabled` state.
+    pub disabled_background: Color,
+    /// Color of the text while the button is enabled.
+    pub text_color: Color,
+    /// Color of the text while the button is disabled.
+    pub disabled_text_color: Color,
+    /// Color of the border while the button is not pressed.
+    pub border_color: Color,
+    /// Color of the border while the button is pressed.
+    pub pressed_border_color: Color,
+    /// Thickness of the border in pixels, `0.0` to draw no border.
+    pub border_thickness: f32,
+    /// Size of the text in pixels.
+    pub font_size: u16,
+    /// Font of the text, `None` for the default font.
+    pub font: Option<Font>,
+}
+
+impl Default for ButtonStyle {
+    fn default() -> Self {
+        Self {
+            normal_background: GRAY,
+            hovered_background: LIGHTGRAY,
+            pressed_background: DARKGRAY,
+            disabled_background: Color::new(0.3, 0.3, 0.3, 0.5),
+            text_color: WHITE,
+            disabled_text_color: GRAY,
+            border_color: BLACK,
+            pressed_border_color: DARKGRAY,
+            border_thickness: 2.0,
+            font_size: 20,
+            font: None,
+        }
+    }
+}
+
 /// A clickable button UI element.
 ///
 /// This component handles user interaction and visual feedback for button presses.
 /// It supports different visual states (normal, hovered, pressed, disabled)
-/// and can be used to trigger actions when clicked.
+/// and can be used to trigger actions when clicked, either by polling
+/// `was_clicked` or through the `set_on_click` callback.
 pub struct Button {
     /// The text displayed on the button.
     text: String,
@@ -31,6 +80,14 @@ pub struct Button {
     visible: bool,
     /// Whether the button was pressed since the last check.
     was_pressed: bool,
+    /// Whether the button currently has keyboard focus.
+    focused: bool,
+    /// The visual style of the button.
+    style: ButtonStyle,
+    /// Callback run when a click completes.
+    on_click: Option<Box<dyn FnMut()>>,
+    /// Callback run when the mouse starts hovering over the button.
+    on_hover_enter: Option<Box<dyn FnMut()>>,
 }
 
 impl Button {
@@ -41,15 +98,69 @@ impl Button {
     ///
     /// Returns a new `Button` instance in the `Normal` state.
     pub fn new(text: &str, bounds: Rect) -> Self {
+        Self::with_style(text, bounds, ButtonStyle::default())
+    }
+    
+    /// Creates a new button with the specified text, bounds and style.
+    ///
+    /// - `text`: The text to display on the button.
+    /// - `bounds`: The position and size of the button in screen coordinates.
+    /// - `style`: The visual style of the button.
+    ///
+    /// Returns a new `Button` instance in the `Normal` state.
+    pub fn with_style(text: &str, bounds: Rect, style: ButtonStyle) -> Self {
         Self {
             text: text.to_string(),
             bounds,
             state: ButtonState::Normal,
             visible: true,
             was_pressed: false,
+            focused: false,
+            style,
+            on_click: None,
+            on_hover_enter: None,
         }
     }
     
+    /// Sets the visual style of the button.
+    ///
+    /// - `style`: The new style.
+    pub fn set_style(&mut self, style: ButtonStyle) {
+        self.style = style;
+    }
+    
+    /// Gets the visual style of the button.
+    pub fn style(&self) -> &ButtonStyle {
+        &self.style
+    }
+    
+    /// Sets a callback that runs every time the button is clicked.
+    ///
+    /// The callback runs during `update` (or `activate`) at the moment the click
+    /// completes, before `was_clicked` is observable, so it suits sounds and other
+    /// side effects. Polling `was_clicked` keeps working alongside it.
+    ///
+    /// - `callback`: The function to run, replacing any previous one.
+    pub fn set_on_click(&mut self, callback: impl FnMut() + 'static) {
+        self.on_click = Some(Box::new(callback));
+    }
+    
+    /// Sets a callback that runs when the mouse starts hovering over the button.
+    ///
+    /// The callback runs during `update`, once per hover, and not while the button
+    /// is disabled.
+    ///
+    /// - `callback`: The function to run, replacing any previous one.
+    pub fn set_on_hover_enter(&mut self, callback: impl FnMut() + 'static) {
+        self.on_hover_enter = Some(Box::new(callback));
+    }
+    
+    /// Removes the click and hover callbacks.
+    pub fn clear_callbacks(&mut self) {
+        self.on_click = None;
+        self.on_hover_enter = None;
+    }
+    
     /// Sets the text displayed on the button.
     ///
     /// - `text`: The new text to display.
@@ -77,6 +188,49 @@ impl Button {
         self.was_pressed
     }
     
+    /// Enables or disables the button.
+    ///
+    /// A disabled button ignores input, is skipped by focus traversal and is drawn
+    /// with the disabled colors. Disabling also clears a click that was not yet
+    /// observed. Enabling a disabled button returns it to `Normal`; enabling an
+    /// already enabled button keeps its current hover or press state.
+    ///
+    /// - `enabled`: `true` to enable the button, `false` to disable it.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.state = ButtonState::Disabled;
+            self.was_pressed = false;
+        } else if self.state == ButtonState::Disabled {
+            self.state = ButtonState::Normal;
+        }
+    }
+    
+    /// Checks if the button is enabled.
+    ///
+    /// Returns `true` unless the button was disabled with `set_enabled(false)`.
+    pub fn is_enabled(&self) -> bool {
+        self.state != ButtonState::Disabled
+    }
+    
+    /// Gets the current visual state of the button.
+    pub fn state(&self) -> ButtonState {
+        self.state
+    }
+    
+    /// Clicks the button as if it was pressed and released.
+    ///
+    /// Does nothing while the button is hidden or disabled. `update` calls this when
+    /// the button is focused and Enter or Space is pressed, and when a mouse click
+    /// completes. Runs the `set_on_click` callback.
+    pub fn activate(&mut self) {
+        if self.visible && self.state != ButtonState::Disabled {
+            self.was_pressed = true;
+            if let Some(on_click) = &mut self.on_click {
+                on_click();
+            }
+        }
+    }
+    
     /// Resets the clicked state of the button.
     ///
     /// After calling this method, `was_clicked()` will return `false` until
@@ -100,6 +254,12 @@ impl Element for Button {
         
         let mut state_changed = false;
         
+        if self.focused && (is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space)) {
+            self.activate();
+            state_changed = true;
+        }
+        
+        let was_hovered = self.state != ButtonState::Normal;
         match self.state {
             ButtonState::Normal if is_hovered && is_pressed => {
                 self.state = ButtonState::Pressed;
@@ -107,8 +267,8 @@ impl Element for Button {
             }
             ButtonState::Pressed if !is_pressed => {
                 if is_hovered {
-                    self.was_pressed = true;
                     self.state = ButtonState::Hovered;
+                    self.activate();
                 } else {
                     self.state = ButtonState::Normal;
                 }
@@ -129,55 +289,57 @@ impl Element for Button {
             _ => {}
         }
         
+        if !was_hovered && self.state != ButtonState::Normal {
+            if let Some(on_hover_enter) = &mut self.on_hover_enter {
+                on_hover_enter();
+            }
+        }
+        
         state_changed
     }
     
-    fn draw(&self) {
+    fn draw_batched(&self, batch: &mut DrawBatch) {
         if !self.visible {
             return;
         }
         
+        let style = &self.style;
         let bg_color = match self.state {
-            ButtonState::Normal => GRAY,
-            ButtonState::Hovered => LIGHTGRAY,
-            ButtonState::Pressed => DARKGRAY,
-            ButtonState::Disabled => Color::new(0.3, 0.3, 0.3, 0.5),
+            ButtonState::Normal => style.normal_background,
+            ButtonState::Hovered => style.hovered_background,
+            ButtonState::Pressed => style.pressed_background,
+            ButtonState::Disabled => style.disabled_background,
         };
         
-        draw_rectangle(
-            self.bounds.x,
-            self.bounds.y,
-            self.bounds.w,
-            self.bounds.h,
-            bg_color,
-        );
+        batch.add_rect(self.bounds, bg_color);
         
-        let text_size = measure_text(&self.text, None, 20, 1.0);
+        let text_size = measure_text(&self.text, style.font.as_ref(), style.font_size, 1.0);
         let text_x = self.bounds.x + (self.bounds.w - text_size.width) / 2.0;
         let text_y = self.bounds.y + (self.bounds.h + text_size.height) / 2.0;
         
         let text_color = if self.state == ButtonState::Disabled {
-            GRAY
+            style.disabled_text_color
         } else {
-            WHITE
+            style.text_color
         };
         
-        draw_text(
-            &self.text,
-            text_x,
-            text_y,
-            20.0,
-            text_color,
-        );
+        batch.add_text(&self.text, vec2(text_x, text_y), style.font_size, style.font.as_ref(), text_color);
         
-        draw_rectangle_lines(
-            self.bounds.x,
-            self.bounds.y,
-            self.bounds.w,
-            self.bounds.h,
-            2.0,
-            if self.state == ButtonState::Pressed { DARKGRAY } else { BLACK },
-        );
+        if style.border_thickness > 0.0 {
+            batch.add_rect_lines(
+                self.bounds,
+                style.border_thickness,
+                if self.state == ButtonState::Pressed { style.pressed_border_color } else { style.border_color },
+            );
+        }
+        
+        if self.focused {
+            batch.add_rect_lines(
+                Rect::new(self.bounds.x - 3.0, self.bounds.y - 3.0, self.bounds.w + 6.0, self.bounds.h + 6.0),
+                2.0,
+                WHITE,
+            );
+        }
     }
     
     fn bounds(&self) -> Rect {
@@ -201,4 +363,20 @@ impl Element for Button {
     fn is_visible(&self) -> bool {
         self.visible
     }
+    
+    fn is_focusable(&self) -> bool {
+        self.visible && self.state != ButtonState::Disabled
+    }
+    
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+    
+    fn activate(&mut self) {
+        Button::activate(self);
+    }
+    
+    fn activates_on_keys(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,197 @@
+use macroquad::prelude::*;
+use super::Element;
+use crate::utils::draw::DrawBatch;
+
+/// A checkbox UI element representing a boolean option.
+///
+/// The checkbox toggles its checked state when a click is pressed and released
+/// within its bounds, like `Button`, and
+/// draws a box with a check mark when checked, followed by its label.
+pub struct Checkbox {
+    /// The text displayed next to the box.
+    label: String,
+    /// The position and size of the checkbox in screen coordinates, including the label.
+    bounds: Rect,
+    /// Whether the checkbox is currently checked.
+    checked: bool,
+    /// Whether the mouse is hovering over the checkbox.
+    hovered: bool,
+    /// Whether the left mouse button went down over the checkbox and was not released yet.
+    pressed: bool,
+    /// Whether the checkbox is currently visible.
+    visible: bool,
+    /// Whether the checkbox was toggled since the last check.
+    was_toggled: bool,
+    /// Whether the checkbox currently has keyboard focus.
+    focused: bool,
+}
+
+impl Checkbox {
+    /// Creates a new checkbox with the specified label and bounds.
+    ///
+    /// - `label`: The text to display next to the box.
+    /// - `bounds`: The position and size of the checkbox in screen coordinates.
+    /// - `checked`: The initial checked state.
+    ///
+    /// Returns a new `Checkbox` instance.
+    pub fn new(label: &str, bounds: Rect, checked: bool) -> Self {
+        Self {
+            label: label.to_string(),
+            bounds,
+            checked,
+            hovered: false,
+            pressed: false,
+            visible: true,
+            was_toggled: false,
+            focused: false,
+        }
+    }
+
+    /// Sets the text displayed next to the box.
+    ///
+    /// - `label`: The new text to display.
+    pub fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+    }
+
+    /// Gets the current text displayed next to the box.
+    ///
+    /// Returns a reference to the checkbox's label.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Checks if the checkbox is currently checked.
+    ///
+    /// Returns `true` if checked, `false` otherwise.
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Sets the checked state of the checkbox.
+    ///
+    /// This does not count as a toggle for `was_toggled()`.
+    ///
+    /// - `checked`: The new checked state.
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+
+    /// Checks if the checkbox was toggled by a click since the last check.
+    ///
+    /// This method returns `true` if the checkbox was clicked since `reset_toggle()`
+    /// was last called.
+    ///
+    /// Returns `true` if the checkbox was toggled, `false` otherwise.
+    pub fn was_toggled(&self) -> bool {
+        if !self.visible {
+            return false;
+        }
+        self.was_toggled
+    }
+
+    /// Resets the toggled state of the checkbox.
+    ///
+    /// After calling this method, `was_toggled()` will return `false` until
+    /// the checkbox is clicked again.
+    pub fn reset_toggle(&mut self) {
+        self.was_toggled = false;
+    }
+
+    /// Returns the rectangle of the box drawn at the left of the bounds.
+    fn box_rect(&self) -> Rect {
+        let side = self.bounds.h.min(self.bounds.w);
+        Rect::new(self.bounds.x, self.bounds.y, side, side)
+    }
+}
+
+impl Element for Checkbox {
+    fn update(&mut self) -> bool {
+        if !self.visible {
+            return false;
+        }
+
+        let mouse_pos = mouse_position().into();
+        let is_hovered = self.bounds.contains(mouse_pos);
+        let mut state_changed = is_hovered != self.hovered;
+        self.hovered = is_hovered;
+
+        if is_hovered && is_mouse_button_pressed(MouseButton::Left) {
+            self.pressed = true;
+        }
+        if self.pressed && is_mouse_button_released(MouseButton::Left) {
+            self.pressed = false;
+            if is_hovered {
+                self.checked = !self.checked;
+                self.was_toggled = true;
+                state_changed = true;
+            }
+        }
+
+        state_changed
+    }
+
+    fn draw_batched(&self, batch: &mut DrawBatch) {
+        if !self.visible {
+            return;
+        }
+
+        let check_box = self.box_rect();
+        let bg_color = if self.hovered { LIGHTGRAY } else { GRAY };
+
+        batch.add_rect(check_box, bg_color);
+        batch.add_rect_lines(check_box, 2.0, if self.focused { WHITE } else { BLACK });
+
+        if self.checked {
+            let thickness = (check_box.w / 8.0).max(2.0);
+            let left = vec2(check_box.x + check_box.w * 0.2, check_box.y + check_box.h * 0.5);
+            let bottom = vec2(check_box.x + check_box.w * 0.42, check_box.y + check_box.h * 0.75);
+            let right = vec2(check_box.x + check_box.w * 0.8, check_box.y + check_box.h * 0.25);
+            batch.add_line(left, bottom, thickness, BLACK);
+            batch.add_line(bottom, right, thickness, BLACK);
+        }
+
+        let text_size = measure_text(&self.label, None, 20, 1.0);
+        let text_x = check_box.x + check_box.w + 8.0;
+        let text_y = self.bounds.y + (self.bounds.h + text_size.height) / 2.0;
+
+        batch.add_text(&self.label, vec2(text_x, text_y), 20, None, WHITE);
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn set_position(&mut self, position: Vec2) {
+        self.bounds.x = position.x;
+        self.bounds.y = position.y;
+    }
+
+    fn set_size(&mut self, size: Vec2) {
+        self.bounds.w = size.x;
+        self.bounds.h = size.y;
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.visible
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn activate(&mut self) {
+        if self.visible {
+            self.checked = !self.checked;
+            self.was_toggled = true;
+        }
+    }
+}
@@ -1,4 +1,12 @@
 use macroquad::prelude::*;
+use std::cell::RefCell;
+use crate::utils::draw::DrawBatch;
+
+thread_local! {
+    /// Batch reused by `Element::draw`, so drawing an element on its own does not
+    /// create a new batch every frame.
+    static DRAW_BATCH: RefCell<DrawBatch> = RefCell::new(DrawBatch::new());
+}
 
 /// Base trait for all UI elements.
 ///
@@ -14,9 +22,30 @@ pub trait Element {
     
     /// Draws the element on the screen.
     ///
-    /// This method is responsible for rendering the element's visual representation.
-    /// It should only be called if `is_visible()` returns `true`.
-    fn draw(&self);
+    /// Queues the element with `draw_batched` into a batch kept for the current
+    /// thread and draws it right away. Prefer `draw_batched` with the frame's own
+    /// batch when drawing several elements.
+    fn draw(&self) {
+        DRAW_BATCH.with(|batch| match batch.try_borrow_mut() {
+            Ok(mut batch) => {
+                self.draw_batched(&mut batch);
+                batch.draw();
+            }
+            Err(_) => {
+                let mut batch = DrawBatch::new();
+                self.draw_batched(&mut batch);
+                batch.draw();
+            }
+        });
+    }
+    
+    /// Queues the element's visual representation into a draw batch.
+    ///
+    /// This lets UI be layered with world rendering and drawn together when the
+    /// batch is flushed. Hidden elements queue nothing.
+    ///
+    /// - `batch`: The draw batch to queue into.
+    fn draw_batched(&self, batch: &mut DrawBatch);
     
     /// Checks if the element contains the specified point.
     ///
@@ -53,4 +82,34 @@ pub trait Element {
     ///
     /// Returns `true` if the element is visible, `false` otherwise.
     fn is_visible(&self) -> bool;
+    
+    /// Checks if the element can receive keyboard focus.
+    ///
+    /// Used by `FocusGroup` to skip elements such as labels during focus traversal.
+    ///
+    /// Returns `true` if the element is focusable, `false` otherwise. Defaults to `false`.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+    
+    /// Sets whether the element currently has keyboard focus.
+    ///
+    /// - `focused`: `true` if the element gained focus, `false` if it lost it.
+    fn set_focused(&mut self, _focused: bool) {
+    }
+    
+    /// Activates the element as if it was clicked.
+    ///
+    /// Called by `FocusGroup` when Enter or Space is pressed while the element is focused.
+    fn activate(&mut self) {
+    }
+    
+    /// Checks if the element activates itself in `update` when focused and Enter or Space is pressed.
+    ///
+    /// `FocusGroup` does not call `activate` on such elements, so they are not activated twice.
+    ///
+    /// Returns `true` if the element handles the activation keys itself. Defaults to `false`.
+    fn activates_on_keys(&self) -> bool {
+        false
+    }
 }
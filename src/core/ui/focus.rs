@@ -0,0 +1,197 @@
+use macroquad::prelude::*;
+use super::Element;
+use crate::utils::draw::DrawBatch;
+
+/// A container that tracks keyboard focus across its child elements.
+///
+/// Tab and the Down/Right arrow keys move focus to the next focusable child,
+/// Shift+Tab and the Up/Left arrow keys move it back. Enter or Space activates
+/// the focused child through `Element::activate`, unless the child handles those
+/// keys itself, see `Element::activates_on_keys`. Mouse input is still handled
+/// by the children themselves.
+pub struct FocusGroup {
+    /// The child elements in traversal order.
+    children: Vec<Box<dyn Element>>,
+    /// Index of the focused child, if any child is focused.
+    focused: Option<usize>,
+    /// Whether the group and its children are visible.
+    visible: bool,
+}
+
+impl Default for FocusGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FocusGroup {
+    /// Creates a new, empty focus group.
+    pub fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            focused: None,
+            visible: true,
+        }
+    }
+
+    /// Adds a child element at the end of the traversal order.
+    ///
+    /// The first focusable child added receives focus.
+    ///
+    /// - `child`: The element to add.
+    pub fn add_child(&mut self, child: Box<dyn Element>) {
+        self.children.push(child);
+        if self.focused.is_none() {
+            self.focus_next();
+        }
+    }
+
+    /// Gets the child elements in traversal order.
+    pub fn children(&self) -> &[Box<dyn Element>] {
+        &self.children
+    }
+
+    /// Gets mutable access to the child elements in traversal order.
+    pub fn children_mut(&mut self) -> &mut [Box<dyn Element>] {
+        &mut self.children
+    }
+
+    /// Gets the index of the focused child.
+    ///
+    /// Returns `None` if no child is focusable.
+    pub fn focused_index(&self) -> Option<usize> {
+        self.focused
+    }
+
+    /// Moves focus to the child at the given index.
+    ///
+    /// - `index`: Index of the child to focus.
+    ///
+    /// Returns `true` if the child exists and is focusable, `false` otherwise.
+    pub fn set_focus(&mut self, index: usize) -> bool {
+        if !self.children.get(index).is_some_and(|child| child.is_focusable()) {
+            return false;
+        }
+        if let Some(old) = self.focused {
+            self.children[old].set_focused(false);
+        }
+        self.children[index].set_focused(true);
+        self.focused = Some(index);
+        true
+    }
+
+    /// Moves focus to the next focusable child, wrapping around at the end.
+    pub fn focus_next(&mut self) {
+        self.step_focus(1);
+    }
+
+    /// Moves focus to the previous focusable child, wrapping around at the start.
+    pub fn focus_previous(&mut self) {
+        self.step_focus(self.children.len().saturating_sub(1));
+    }
+
+    /// Moves focus by `offset` positions modulo the child count, skipping unfocusable children.
+    fn step_focus(&mut self, offset: usize) {
+        let count = self.children.len();
+        if count == 0 {
+            return;
+        }
+
+        let start = match self.focused {
+            Some(index) => index,
+            None => count - 1,
+        };
+        let offset = if self.focused.is_none() { 1 } else { offset };
+
+        for step in 1..=count {
+            let index = (start + offset * step) % count;
+            if self.set_focus(index) {
+                return;
+            }
+        }
+
+        if let Some(old) = self.focused.take() {
+            self.children[old].set_focused(false);
+        }
+    }
+}
+
+impl Element for FocusGroup {
+    fn update(&mut self) -> bool {
+        if !self.visible {
+            return false;
+        }
+
+        let mut state_changed = false;
+        for child in &mut self.children {
+            state_changed |= child.update();
+        }
+
+        let shift = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+        let previous = self.focused;
+        if (is_key_pressed(KeyCode::Tab) && shift)
+            || is_key_pressed(KeyCode::Up)
+            || is_key_pressed(KeyCode::Left)
+        {
+            self.focus_previous();
+        } else if is_key_pressed(KeyCode::Tab)
+            || is_key_pressed(KeyCode::Down)
+            || is_key_pressed(KeyCode::Right)
+        {
+            self.focus_next();
+        }
+        state_changed |= self.focused != previous;
+
+        if is_key_pressed(KeyCode::Enter) || is_key_pressed(KeyCode::Space) {
+            if let Some(child) = self.focused.map(|index| &mut self.children[index]) {
+                if !child.activates_on_keys() {
+                    child.activate();
+                    state_changed = true;
+                }
+            }
+        }
+
+        state_changed
+    }
+
+    fn draw_batched(&self, batch: &mut DrawBatch) {
+        if !self.visible {
+            return;
+        }
+
+        for child in &self.children {
+            child.draw_batched(batch);
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        let mut children = self.children.iter().map(|child| child.bounds());
+        match children.next() {
+            Some(first) => children.fold(first, |acc, rect| acc.combine_with(rect)),
+            None => Rect::default(),
+        }
+    }
+
+    fn set_position(&mut self, position: Vec2) {
+        let offset = position - self.bounds().point();
+        for child in &mut self.children {
+            let child_pos = child.bounds().point();
+            child.set_position(child_pos + offset);
+        }
+    }
+
+    fn set_size(&mut self, _size: Vec2) {
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        for child in &mut self.children {
+            child.set_visible(visible);
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
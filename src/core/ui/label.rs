@@ -1,16 +1,45 @@
 use macroquad::prelude::*;
 use super::Element;
+use crate::utils::draw::DrawBatch;
 
-/// A text label UI element that displays a single line of text.
+/// Horizontal alignment of a label's text relative to its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    /// The text starts at the position.
+    #[default]
+    Left,
+    /// The text is centered on the position.
+    Center,
+    /// The text ends at the position.
+    Right,
+}
+
+impl Alignment {
+    /// Returns the x offset from the anchor to the left edge of a line.
+    ///
+    /// - `width`: The width of the line in pixels.
+    fn offset(self, width: f32) -> f32 {
+        match self {
+            Alignment::Left => 0.0,
+            Alignment::Center => -width / 2.0,
+            Alignment::Right => -width,
+        }
+    }
+}
+
+/// A text label UI element that displays a line of text.
 ///
 /// The label's size is automatically determined by its text content and font size.
 /// It supports basic text rendering with configurable position, color, and visibility.
+/// When a maximum width is set, the text is wrapped on word boundaries over several lines.
 pub struct Label {
     text: String,
     position: Vec2,
     font_size: u16,
     color: Color,
     visible: bool,
+    max_width: Option<f32>,
+    alignment: Alignment,
 }
 
 impl Label {
@@ -29,6 +58,8 @@ impl Label {
             font_size,
             color,
             visible: true,
+            max_width: None,
+            alignment: Alignment::Left,
         }
     }
     
@@ -52,6 +83,75 @@ impl Label {
     pub fn set_color(&mut self, color: Color) {
         self.color = color;
     }
+    
+    /// Sets the maximum width of the label before text wraps onto a new line.
+    ///
+    /// - `max_width`: The maximum line width in pixels, or `None` to draw a single line.
+    pub fn set_max_width(&mut self, max_width: Option<f32>) {
+        self.max_width = max_width;
+    }
+    
+    /// Gets the maximum width of the label before text wraps onto a new line.
+    ///
+    /// Returns `None` if the label draws a single line.
+    pub fn max_width(&self) -> Option<f32> {
+        self.max_width
+    }
+    
+    /// Sets the horizontal alignment of the text relative to the label's position.
+    ///
+    /// - `alignment`: The new alignment. Each wrapped line is aligned on its own.
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        self.alignment = alignment;
+    }
+    
+    /// Gets the horizontal alignment of the text relative to the label's position.
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+    
+    /// Splits the text into the lines that are drawn.
+    ///
+    /// Words longer than the maximum width are broken between characters.
+    fn lines(&self) -> Vec<String> {
+        let max_width = match self.max_width {
+            Some(max_width) => max_width,
+            None => return vec![self.text.clone()],
+        };
+        
+        let fits = |line: &str| measure_text(line, None, self.font_size, 1.0).width <= max_width;
+        let mut lines = Vec::new();
+        
+        for paragraph in self.text.split('\n') {
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                let candidate = if line.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{} {}", line, word)
+                };
+                if fits(&candidate) {
+                    line = candidate;
+                    continue;
+                }
+                
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                for ch in word.chars() {
+                    line.push(ch);
+                    if !fits(&line) && line.chars().count() > 1 {
+                        line.pop();
+                        lines.push(std::mem::take(&mut line));
+                        line.push(ch);
+                    }
+                }
+            }
+            lines.push(line);
+        }
+        
+        lines
+    }
 }
 
 impl Element for Label {
@@ -59,27 +159,36 @@ impl Element for Label {
         false
     }
     
-    fn draw(&self) {
+    fn draw_batched(&self, batch: &mut DrawBatch) {
         if !self.visible {
             return;
         }
         
-        draw_text(
-            &self.text,
-            self.position.x,
-            self.position.y + self.font_size as f32,
-            self.font_size as f32,
-            self.color,
-        );
+        for (i, line) in self.lines().iter().enumerate() {
+            let line_width = measure_text(line, None, self.font_size, 1.0).width;
+            let pos = vec2(
+                self.position.x + self.alignment.offset(line_width),
+                self.position.y + self.font_size as f32 * (i + 1) as f32,
+            );
+            batch.add_text(line, pos, self.font_size, None, self.color);
+        }
     }
     
     fn bounds(&self) -> Rect {
-        let text_size = measure_text(&self.text, None, self.font_size, 1.0);
+        let lines = self.lines();
+        let width = lines
+            .iter()
+            .map(|line| measure_text(line, None, self.font_size, 1.0).width)
+            .fold(0.0, f32::max);
+        let last_height = lines
+            .last()
+            .map(|line| measure_text(line, None, self.font_size, 1.0).height)
+            .unwrap_or(0.0);
         Rect::new(
-            self.position.x,
+            self.position.x + self.alignment.offset(width),
             self.position.y,
-            text_size.width,
-            text_size.height,
+            width,
+            self.font_size as f32 * (lines.len() - 1) as f32 + last_height,
         )
     }
     
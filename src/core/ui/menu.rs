@@ -1,10 +1,12 @@
+use std::any::Any;
+use std::fmt;
+
 use crate::utils::draw::DrawBatch;
 
 /// Represents an action that can be returned by a menu.
 ///
 /// This enum is used to communicate user interactions with the menu
 /// back to the game state manager.
-#[derive(Debug, PartialEq, Eq)]
 pub enum MenuAction {
     /// No action was taken during this update.
     None,
@@ -14,8 +16,64 @@ pub enum MenuAction {
     ChangeState(String),
     /// Request to quit the application.
     Quit,
+    /// Structured data for the game loop, such as the name and seed entered on a
+    /// new-world screen.
+    ///
+    /// `name` tells the game what the action is, and the game downcasts the payload
+    /// to the type it expects, see `MenuAction::payload`.
+    Custom {
+        /// Name identifying the action, such as `"create_world"`.
+        name: String,
+        /// The data carried by the action.
+        payload: Box<dyn Any>,
+    },
+}
+
+impl MenuAction {
+    /// Creates a `Custom` action carrying the given payload.
+    ///
+    /// - `name`: Name identifying the action.
+    /// - `payload`: The data to hand to the game loop.
+    pub fn custom<T: Any>(name: &str, payload: T) -> Self {
+        MenuAction::Custom { name: name.to_string(), payload: Box::new(payload) }
+    }
+
+    /// Gets the payload of a `Custom` action as the given type.
+    ///
+    /// Returns `None` if the action is not `Custom` or the payload has a different type.
+    pub fn payload<T: Any>(&self) -> Option<&T> {
+        match self {
+            MenuAction::Custom { payload, .. } => payload.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for MenuAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MenuAction::None => f.write_str("None"),
+            MenuAction::ChangeState(state) => f.debug_tuple("ChangeState").field(state).finish(),
+            MenuAction::Quit => f.write_str("Quit"),
+            MenuAction::Custom { name, .. } => f.debug_struct("Custom").field("name", name).finish_non_exhaustive(),
+        }
+    }
 }
 
+/// `Custom` actions are compared by name only, as payloads cannot be compared.
+impl PartialEq for MenuAction {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (MenuAction::None, MenuAction::None) | (MenuAction::Quit, MenuAction::Quit) => true,
+            (MenuAction::ChangeState(a), MenuAction::ChangeState(b)) => a == b,
+            (MenuAction::Custom { name: a, .. }, MenuAction::Custom { name: b, .. }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for MenuAction {}
+
 /// A trait representing a menu in the game's user interface.
 ///
 /// This trait defines the interface that all menu implementations must provide.
@@ -1,10 +1,17 @@
-pub mod menu;
 pub mod button;
+pub mod checkbox;
 pub mod element;
+pub mod focus;
 pub mod label;
+pub mod menu;
+pub mod panel;
+pub mod scroll;
 
-pub use button::{Button, ButtonState};
-pub use label::Label;
-
-pub use element::*;
-pub use menu::*;
\ No newline at end of file
+pub use button::{Button, ButtonState, ButtonStyle};
+pub use checkbox::Checkbox;
+pub use element::Element;
+pub use focus::FocusGroup;
+pub use label::{Alignment, Label};
+pub use menu::{Menu, MenuAction};
+pub use panel::{LayoutDirection, Panel};
+pub use scroll::ScrollList;
@@ -0,0 +1,178 @@
+use macroquad::prelude::*;
+use super::Element;
+use crate::utils::draw::DrawBatch;
+
+/// The axis along which a `Panel` stacks its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutDirection {
+    /// Children are stacked top to bottom.
+    #[default]
+    Vertical,
+    /// Children are stacked left to right.
+    Horizontal,
+}
+
+/// A container that owns child elements and lays them out in a stack.
+///
+/// Children keep their size along the stacking axis and are stretched to the
+/// panel's inner size across it. Layout is recomputed whenever a child is added
+/// or the panel is moved, resized or reconfigured.
+pub struct Panel {
+    /// The child elements in layout order.
+    children: Vec<Box<dyn Element>>,
+    /// The position and size of the panel in screen coordinates.
+    bounds: Rect,
+    /// The axis along which children are stacked.
+    direction: LayoutDirection,
+    /// Gap between consecutive children, in pixels.
+    spacing: f32,
+    /// Gap between the panel's edges and its children, in pixels.
+    padding: f32,
+    /// Whether the panel and its children are visible.
+    visible: bool,
+}
+
+impl Panel {
+    /// Creates a new, empty panel.
+    ///
+    /// - `bounds`: The position and size of the panel in screen coordinates.
+    /// - `direction`: The axis along which children are stacked.
+    ///
+    /// Returns a new `Panel` with no spacing or padding.
+    pub fn new(bounds: Rect, direction: LayoutDirection) -> Self {
+        Self {
+            children: Vec::new(),
+            bounds,
+            direction,
+            spacing: 0.0,
+            padding: 0.0,
+            visible: true,
+        }
+    }
+
+    /// Adds a child element at the end of the stack.
+    ///
+    /// - `child`: The element to add.
+    pub fn add_child(&mut self, child: Box<dyn Element>) {
+        self.children.push(child);
+        self.layout();
+    }
+
+    /// Gets the child elements in layout order.
+    pub fn children(&self) -> &[Box<dyn Element>] {
+        &self.children
+    }
+
+    /// Gets mutable access to the child elements in layout order.
+    ///
+    /// Call `layout()` afterwards if a child's size was changed.
+    pub fn children_mut(&mut self) -> &mut [Box<dyn Element>] {
+        &mut self.children
+    }
+
+    /// Sets the axis along which children are stacked.
+    ///
+    /// - `direction`: The new layout direction.
+    pub fn set_direction(&mut self, direction: LayoutDirection) {
+        self.direction = direction;
+        self.layout();
+    }
+
+    /// Sets the gap between consecutive children.
+    ///
+    /// - `spacing`: The gap in pixels.
+    pub fn set_spacing(&mut self, spacing: f32) {
+        self.spacing = spacing;
+        self.layout();
+    }
+
+    /// Sets the gap between the panel's edges and its children.
+    ///
+    /// - `padding`: The gap in pixels, applied on all four sides.
+    pub fn set_padding(&mut self, padding: f32) {
+        self.padding = padding;
+        self.layout();
+    }
+
+    /// Recomputes the bounds of all children relative to the panel's rect.
+    pub fn layout(&mut self) {
+        let inner = Rect::new(
+            self.bounds.x + self.padding,
+            self.bounds.y + self.padding,
+            (self.bounds.w - self.padding * 2.0).max(0.0),
+            (self.bounds.h - self.padding * 2.0).max(0.0),
+        );
+
+        let mut cursor = match self.direction {
+            LayoutDirection::Vertical => inner.y,
+            LayoutDirection::Horizontal => inner.x,
+        };
+
+        for child in &mut self.children {
+            let child_bounds = child.bounds();
+            match self.direction {
+                LayoutDirection::Vertical => {
+                    child.set_size(vec2(inner.w, child_bounds.h));
+                    child.set_position(vec2(inner.x, cursor));
+                    cursor += child.bounds().h + self.spacing;
+                }
+                LayoutDirection::Horizontal => {
+                    child.set_size(vec2(child_bounds.w, inner.h));
+                    child.set_position(vec2(cursor, inner.y));
+                    cursor += child.bounds().w + self.spacing;
+                }
+            }
+        }
+    }
+}
+
+impl Element for Panel {
+    fn update(&mut self) -> bool {
+        if !self.visible {
+            return false;
+        }
+
+        let mut state_changed = false;
+        for child in &mut self.children {
+            state_changed |= child.update();
+        }
+        state_changed
+    }
+
+    fn draw_batched(&self, batch: &mut DrawBatch) {
+        if !self.visible {
+            return;
+        }
+
+        for child in &self.children {
+            child.draw_batched(batch);
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn set_position(&mut self, position: Vec2) {
+        self.bounds.x = position.x;
+        self.bounds.y = position.y;
+        self.layout();
+    }
+
+    fn set_size(&mut self, size: Vec2) {
+        self.bounds.w = size.x;
+        self.bounds.h = size.y;
+        self.layout();
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        for child in &mut self.children {
+            child.set_visible(visible);
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
@@ -0,0 +1,297 @@
+use macroquad::prelude::*;
+use super::Element;
+use crate::utils::draw::DrawBatch;
+
+/// Distance scrolled per mouse wheel notch, in pixels.
+const SCROLL_STEP: f32 = 30.0;
+
+/// Width of the scrollbar drawn when content overflows, in pixels.
+const SCROLLBAR_WIDTH: f32 = 8.0;
+
+/// A vertically scrolling list of selectable rows.
+///
+/// Rows are stacked top to bottom inside the list's bounds and clipped to them.
+/// The list scrolls with the mouse wheel or by dragging the scrollbar, and a left
+/// click on a row selects it and reports its index through `clicked_row()`. Like
+/// `Button`, a click completes when the button is released over the row it was
+/// pressed on. Every row is updated each frame, so rows notice when the mouse
+/// leaves them even if it leaves the list at the same time. Clipping is queued into the draw batch, so the
+/// list is drawn in order with the rest of the batch.
+pub struct ScrollList {
+    /// The rows in display order.
+    rows: Vec<Box<dyn Element>>,
+    /// The position and size of the visible viewport in screen coordinates.
+    bounds: Rect,
+    /// Gap between consecutive rows, in pixels.
+    spacing: f32,
+    /// Current scroll offset from the top of the content, in pixels.
+    scroll: f32,
+    /// Offset between the mouse and the scrollbar thumb while it is being dragged.
+    drag_offset: Option<f32>,
+    /// Index of the row the left button was pressed on, until it is released.
+    pressed: Option<usize>,
+    /// Index of the selected row, if any.
+    selected: Option<usize>,
+    /// Index of the row clicked since the last check, if any.
+    clicked: Option<usize>,
+    /// Whether the list and its rows are visible.
+    visible: bool,
+}
+
+impl ScrollList {
+    /// Creates a new, empty scroll list.
+    ///
+    /// - `bounds`: The position and size of the visible viewport in screen coordinates.
+    ///
+    /// Returns a new `ScrollList` with no rows.
+    pub fn new(bounds: Rect) -> Self {
+        Self {
+            rows: Vec::new(),
+            bounds,
+            spacing: 0.0,
+            scroll: 0.0,
+            drag_offset: None,
+            pressed: None,
+            selected: None,
+            clicked: None,
+            visible: true,
+        }
+    }
+
+    /// Adds a row at the end of the list.
+    ///
+    /// - `row`: The element to add.
+    pub fn add_row(&mut self, row: Box<dyn Element>) {
+        self.rows.push(row);
+        self.layout();
+    }
+
+    /// Removes all rows and resets scrolling, selection, any thumb drag and any pending click.
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.scroll = 0.0;
+        self.drag_offset = None;
+        self.pressed = None;
+        self.selected = None;
+        self.clicked = None;
+    }
+
+    /// Gets the rows in display order.
+    pub fn rows(&self) -> &[Box<dyn Element>] {
+        &self.rows
+    }
+
+    /// Sets the gap between consecutive rows.
+    ///
+    /// - `spacing`: The gap in pixels.
+    pub fn set_spacing(&mut self, spacing: f32) {
+        self.spacing = spacing;
+        self.layout();
+    }
+
+    /// Gets the index of the selected row.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Sets the selected row.
+    ///
+    /// - `index`: Index of the row to select, or `None` to clear the selection.
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|&index| index < self.rows.len());
+    }
+
+    /// Gets the index of the row clicked since the last check.
+    ///
+    /// Returns `None` until a row is clicked again after `reset_click()`.
+    pub fn clicked_row(&self) -> Option<usize> {
+        if !self.visible {
+            return None;
+        }
+        self.clicked
+    }
+
+    /// Resets the clicked row.
+    pub fn reset_click(&mut self) {
+        self.clicked = None;
+    }
+
+    /// Gets the current scroll offset from the top of the content, in pixels.
+    pub fn scroll(&self) -> f32 {
+        self.scroll
+    }
+
+    /// Sets the scroll offset, clamped so the content stays in view.
+    ///
+    /// - `scroll`: The offset from the top of the content, in pixels.
+    pub fn set_scroll(&mut self, scroll: f32) {
+        self.scroll = scroll.clamp(0.0, self.max_scroll());
+        self.layout();
+    }
+
+    /// Returns the total height of all rows including spacing.
+    fn content_height(&self) -> f32 {
+        let rows: f32 = self.rows.iter().map(|row| row.bounds().h).sum();
+        rows + self.spacing * self.rows.len().saturating_sub(1) as f32
+    }
+
+    /// Returns the largest valid scroll offset.
+    fn max_scroll(&self) -> f32 {
+        (self.content_height() - self.bounds.h).max(0.0)
+    }
+
+    /// Checks whether the content is taller than the viewport.
+    fn overflows(&self) -> bool {
+        self.max_scroll() > 0.0
+    }
+
+    /// Returns the rectangle of the scrollbar thumb, if the content overflows.
+    fn thumb_rect(&self) -> Option<Rect> {
+        if !self.overflows() {
+            return None;
+        }
+        let content_height = self.content_height();
+        let thumb_height = (self.bounds.h * self.bounds.h / content_height).max(SCROLLBAR_WIDTH * 2.0);
+        let travel = self.bounds.h - thumb_height;
+        let thumb_y = self.bounds.y + travel * self.scroll / self.max_scroll();
+        Some(Rect::new(
+            self.bounds.right() - SCROLLBAR_WIDTH,
+            thumb_y,
+            SCROLLBAR_WIDTH,
+            thumb_height,
+        ))
+    }
+
+    /// Recomputes the bounds of all rows from the scroll offset.
+    fn layout(&mut self) {
+        let width = if self.overflows() {
+            self.bounds.w - SCROLLBAR_WIDTH
+        } else {
+            self.bounds.w
+        };
+
+        let mut cursor = self.bounds.y - self.scroll;
+        for row in &mut self.rows {
+            let height = row.bounds().h;
+            row.set_size(vec2(width, height));
+            row.set_position(vec2(self.bounds.x, cursor));
+            cursor += row.bounds().h + self.spacing;
+        }
+    }
+
+    /// Returns the index of the row under the given point, if any.
+    fn row_at(&self, point: Vec2) -> Option<usize> {
+        if !self.bounds.contains(point) {
+            return None;
+        }
+        self.rows.iter().position(|row| row.contains(point))
+    }
+}
+
+impl Element for ScrollList {
+    fn update(&mut self) -> bool {
+        if !self.visible {
+            return false;
+        }
+
+        let mouse_pos: Vec2 = mouse_position().into();
+        let is_hovered = self.bounds.contains(mouse_pos);
+        let old_scroll = self.scroll;
+        let mut state_changed = false;
+
+        if is_hovered {
+            let (_, wheel_y) = mouse_wheel();
+            if wheel_y != 0.0 {
+                self.set_scroll(self.scroll - wheel_y.signum() * SCROLL_STEP);
+            }
+        }
+
+        if is_mouse_button_pressed(MouseButton::Left) {
+            if let Some(thumb) = self.thumb_rect() {
+                if thumb.contains(mouse_pos) {
+                    self.drag_offset = Some(mouse_pos.y - thumb.y);
+                }
+            }
+        }
+        if !is_mouse_button_down(MouseButton::Left) {
+            self.drag_offset = None;
+        }
+        if let (Some(offset), Some(thumb)) = (self.drag_offset, self.thumb_rect()) {
+            let travel = self.bounds.h - thumb.h;
+            if travel > 0.0 {
+                let fraction = (mouse_pos.y - offset - self.bounds.y) / travel;
+                self.set_scroll(fraction * self.max_scroll());
+            }
+        }
+        state_changed |= self.scroll != old_scroll;
+
+        for row in &mut self.rows {
+            state_changed |= row.update();
+        }
+
+        if self.drag_offset.is_none() && is_mouse_button_pressed(MouseButton::Left) {
+            self.pressed = self.row_at(mouse_pos);
+        }
+        if is_mouse_button_released(MouseButton::Left) {
+            if let Some(index) = self.pressed.take().filter(|&index| self.row_at(mouse_pos) == Some(index)) {
+                self.selected = Some(index);
+                self.clicked = Some(index);
+                state_changed = true;
+            }
+        }
+
+        state_changed
+    }
+
+    fn draw_batched(&self, batch: &mut DrawBatch) {
+        if !self.visible {
+            return;
+        }
+
+        batch.add_clip(Some(self.bounds));
+        for (index, row) in self.rows.iter().enumerate() {
+            let row_bounds = row.bounds();
+            if !row_bounds.overlaps(&self.bounds) {
+                continue;
+            }
+            if self.selected == Some(index) {
+                batch.add_rect(row_bounds, Color::new(1.0, 1.0, 1.0, 0.15));
+            }
+            row.draw_batched(batch);
+        }
+        batch.add_clip(None);
+
+        if let Some(thumb) = self.thumb_rect() {
+            batch.add_rect(Rect::new(thumb.x, self.bounds.y, thumb.w, self.bounds.h), Color::new(0.0, 0.0, 0.0, 0.3));
+            batch.add_rect(thumb, LIGHTGRAY);
+        }
+    }
+
+    fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    fn set_position(&mut self, position: Vec2) {
+        self.bounds.x = position.x;
+        self.bounds.y = position.y;
+        self.layout();
+    }
+
+    fn set_size(&mut self, size: Vec2) {
+        self.bounds.w = size.x;
+        self.bounds.h = size.y;
+        self.scroll = self.scroll.clamp(0.0, self.max_scroll());
+        self.layout();
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        for row in &mut self.rows {
+            row.set_visible(visible);
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
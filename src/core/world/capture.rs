@@ -0,0 +1,133 @@
+use macroquad::prelude::*;
+
+use crate::World;
+
+impl World {
+    /// Renders a region of the world into an image
+    /// - `center`: Center of the region in world coordinates
+    /// - `size`: Size of the region in world units, which is also the image size in pixels
+    ///
+    /// The region is drawn into an off-screen render target with its own camera,
+    /// independent of the camera used on screen, and read back. Tiles, overlays and
+    /// objects are drawn in the same order as `draw`, culled against the region.
+    /// Only loaded chunks are drawn; chunks are not generated or read from disk for
+    /// the capture. Every object overlapping the region is drawn, whether or not it
+    /// was active in the last update. Must be called from the main thread while a
+    /// frame is being rendered; the camera set before the call is restored.
+    ///
+    /// Returns the rendered region, which can be saved with `Image::export_png`
+    pub fn render_region_to_image(&mut self, center: Vec2, size: Vec2) -> Image {
+        let width = size.x.round().max(1.0) as u32;
+        let height = size.y.round().max(1.0) as u32;
+        let area = Rect::new(center.x - size.x / 2.0, center.y - size.y / 2.0, size.x, size.y);
+
+        let target = render_target(width, height);
+        target.texture.set_filter(FilterMode::Nearest);
+        let mut camera = Camera2D::from_display_rect(area);
+        // Render targets are read back bottom row first, so flip to keep the image upright
+        camera.zoom.y = -camera.zoom.y;
+        camera.render_target = Some(target.clone());
+
+        let min_chunk = self.get_chunk_coords(area.point());
+        let max_chunk = self.get_chunk_coords(area.point() + area.size());
+        let mut chunks = Vec::new();
+        for y in min_chunk.1..=max_chunk.1 {
+            for x in min_chunk.0..=max_chunk.0 {
+                chunks.push((x, y));
+            }
+        }
+
+        push_camera_state();
+        set_camera(&camera);
+        clear_background(BLANK);
+        self.draw_chunks(&chunks, area);
+        pop_camera_state();
+
+        target.texture.get_texture_data()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::registries;
+    use crate::{Chunk, DrawBatch, Tile, TILE_SIZE};
+
+    /// A tile drawn as a square of one color.
+    #[derive(Clone)]
+    struct Painted {
+        pos: Vec2,
+        texture: Texture2D,
+    }
+
+    impl Painted {
+        fn new(color: [u8; 4]) -> Self {
+            Self { pos: Vec2::ZERO, texture: Texture2D::from_rgba8(1, 1, &color) }
+        }
+    }
+
+    impl Tile for Painted {
+        fn get_type_tag(&self) -> &'static str {
+            "painted"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, batch: &mut DrawBatch, pos: Vec2) {
+            batch.add(self.texture.clone(), pos, 1.0, Some(Vec2::splat(TILE_SIZE)));
+        }
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Puts a tile of the given color at a tile position of a loaded chunk.
+    fn paint(world: &mut World, tile: (i32, i32), color: [u8; 4]) {
+        let pos = vec2(tile.0 as f32, tile.1 as f32) * TILE_SIZE;
+        let (chunk, index) = world.get_tile_index(pos);
+        world.get_chunk_mut(chunk).unwrap().tiles[index] = Box::new(Painted { pos, ..Painted::new(color) });
+    }
+
+    /// Opens a window to get a graphics context, so it can't run on a headless machine.
+    #[test]
+    #[ignore = "needs a display"]
+    fn captured_pixels_match_the_tiles() {
+        macroquad::Window::new("capture", async {
+            let (red, green, blue) = ([255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]);
+            let (tiles, objects, biomes) = registries();
+            let mut world = World::new("capture", tiles, objects, biomes);
+            let mut chunk = Chunk::new(Vec2::ZERO);
+            let size = chunk.config().chunk_size;
+            for y in 0..size {
+                for x in 0..size {
+                    chunk.tiles.push(Box::new(Painted { pos: vec2(x as f32, y as f32) * TILE_SIZE, ..Painted::new(red) }));
+                }
+            }
+            world.add_chunk(chunk);
+            paint(&mut world, (1, 0), green);
+            paint(&mut world, (0, 1), blue);
+
+            let image = world.render_region_to_image(Vec2::splat(TILE_SIZE), Vec2::splat(TILE_SIZE * 2.0));
+            assert_eq!((image.width(), image.height()), (TILE_SIZE as usize * 2, TILE_SIZE as usize * 2));
+            let pixel = |tile_x: f32, tile_y: f32| {
+                let color = image.get_pixel(((tile_x + 0.5) * TILE_SIZE) as u32, ((tile_y + 0.5) * TILE_SIZE) as u32);
+                [color.r, color.g, color.b, color.a].map(|channel| (channel * 255.0).round() as u8)
+            };
+            assert_eq!(pixel(0.0, 0.0), red);
+            assert_eq!(pixel(1.0, 0.0), green);
+            assert_eq!(pixel(0.0, 1.0), blue);
+            assert_eq!(pixel(1.0, 1.0), red);
+        });
+    }
+}
@@ -0,0 +1,239 @@
+use std::any::Any;
+
+use crate::{Direction, Object, World};
+
+/// A message sent to objects through `World::post_event`.
+pub enum Event {
+    /// The receiver was hit by an attack.
+    Hit {
+        /// Amount of damage dealt
+        damage: i32,
+        /// Direction the attack came from
+        direction: Direction,
+    },
+    /// A plain text message.
+    Message(String),
+    /// A user-defined payload. Receivers downcast it to the type they expect.
+    Custom(Box<dyn Any + Send + Sync>),
+}
+
+/// The objects an event is delivered to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventTarget {
+    /// Every object in a visible chunk whose type tag matches.
+    Type(String),
+    /// A single object, addressed by its chunk and its index in that chunk's object list.
+    ///
+    /// Indices are only stable until objects move between chunks at the start of the
+    /// next update, so address objects this way only within the same update, for
+    /// example with an index from a `RayHit` or a query made during `tick`.
+    Object {
+        /// Coordinates of the chunk holding the object
+        chunk: (i32, i32),
+        /// Index of the object in the chunk's object list
+        index: usize,
+    },
+    /// Every object in a visible chunk.
+    All,
+}
+
+impl EventTarget {
+    /// Checks whether an object is addressed by this target.
+    ///
+    /// - `chunk`: Coordinates of the chunk holding the object
+    /// - `index`: Index of the object in the chunk's object list
+    /// - `obj`: The object
+    fn matches(&self, chunk: (i32, i32), index: usize, obj: &dyn Object) -> bool {
+        match self {
+            EventTarget::Type(type_tag) => obj.get_type_tag() == type_tag,
+            EventTarget::Object { chunk: target_chunk, index: target_index } => {
+                *target_chunk == chunk && *target_index == index
+            }
+            EventTarget::All => true,
+        }
+    }
+}
+
+impl World {
+    /// Queues an event for delivery after all objects and tiles have ticked
+    /// - `target`: The objects to deliver the event to
+    /// - `event`: The event
+    ///
+    /// Events are delivered at the end of the current update by calling
+    /// `Object::on_event` on every addressed object. Deferring delivery means an
+    /// object can message another from inside `tick` without both being borrowed
+    /// mutably at once. Events posted while events are being delivered are
+    /// delivered at the end of the next update.
+    pub fn post_event(&mut self, target: EventTarget, event: Event) {
+        self.events.push((target, event));
+    }
+
+    /// Delivers all queued events to the objects they address
+    ///
+    /// Events are delivered in the order they were posted. Chunks holding an object
+    /// that received an event are marked dirty, so the change is saved.
+    pub(super) fn dispatch_events(&mut self) {
+        if self.events.is_empty() {
+            return;
+        }
+
+        let events = std::mem::take(&mut self.events);
+        let visible_chunks = self.visible_chunks.clone();
+        for (target, event) in &events {
+            let chunk_positions = match target {
+                EventTarget::Object { chunk, .. } => std::slice::from_ref(chunk),
+                _ => visible_chunks.as_slice(),
+            };
+
+            for &chunk_pos in chunk_positions {
+                if let Some(mut chunk) = self.chunks.remove(&chunk_pos) {
+                    let mut delivered = false;
+                    for (index, obj) in chunk.objects.iter_mut().enumerate() {
+                        if target.matches(chunk_pos, index, obj.as_ref()) {
+                            obj.on_event(event, self);
+                            delivered = true;
+                        }
+                    }
+                    if delivered {
+                        chunk.mark_changed();
+                    }
+                    self.chunks.insert(chunk_pos, chunk);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::{vec2, Vec2};
+
+    use super::{Event, EventTarget};
+    use crate::core::testing::{registries, TestObject};
+    use crate::{Chunk, DrawBatch, Object, World};
+
+    fn test_world() -> World {
+        let (tiles, objects, biomes) = registries();
+        World::new("test", tiles, objects, biomes)
+    }
+
+    /// Records the text of every message it receives and answers "ping" with "pong"
+    #[derive(Clone)]
+    struct Listener {
+        pos: Vec2,
+        received: Vec<String>,
+    }
+
+    impl Listener {
+        fn at(x: f32) -> Self {
+            Self { pos: vec2(x, 10.0), received: Vec::new() }
+        }
+    }
+
+    impl Object for Listener {
+        fn get_type_tag(&self) -> &'static str {
+            "listener"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(8.0)
+        }
+
+        fn get_velocity(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch) {}
+
+        fn set_size(&mut self, _size: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn set_velocity(&mut self, _velocity: Vec2) {}
+
+        fn on_event(&mut self, event: &Event, world: &mut World) {
+            if let Event::Message(text) = event {
+                self.received.push(text.clone());
+                if text == "ping" {
+                    world.post_event(EventTarget::All, Event::Message("pong".to_string()));
+                }
+            }
+        }
+
+        fn clone_box(&self) -> Box<dyn Object> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Returns the messages received by each listener of a chunk, in object order.
+    fn received(world: &World, chunk_pos: (i32, i32)) -> Vec<Vec<String>> {
+        world.get_chunk(chunk_pos).unwrap().objects.iter()
+            .filter_map(|obj| (obj.as_ref() as &dyn std::any::Any).downcast_ref::<Listener>())
+            .map(|listener| listener.received.clone())
+            .collect()
+    }
+
+    fn message(text: &str) -> Event {
+        Event::Message(text.to_string())
+    }
+
+    #[test]
+    fn events_reach_their_targets_in_posting_order() {
+        let mut world = test_world();
+        let mut chunk = Chunk::new(vec2(0.0, 0.0));
+        chunk.objects.push(Box::new(Listener::at(10.0)));
+        chunk.objects.push(Box::new(TestObject::new(vec2(20.0, 10.0))));
+        chunk.objects.push(Box::new(Listener::at(30.0)));
+        world.add_chunk(chunk);
+        world.visible_chunks = vec![(0, 0)];
+        world.get_chunk_mut((0, 0)).unwrap().clear_dirty();
+
+        world.post_event(EventTarget::All, message("first"));
+        world.post_event(EventTarget::Object { chunk: (0, 0), index: 2 }, message("second"));
+        world.post_event(EventTarget::Type("listener".to_string()), message("third"));
+        world.post_event(EventTarget::Object { chunk: (5, 5), index: 0 }, message("lost"));
+        world.dispatch_events();
+
+        assert_eq!(received(&world, (0, 0)), [vec!["first", "third"], vec!["first", "second", "third"]]);
+        assert!(world.get_chunk((0, 0)).unwrap().is_dirty());
+    }
+
+    #[test]
+    fn events_posted_during_delivery_wait_for_the_next_dispatch() {
+        let mut world = test_world();
+        let mut chunk = Chunk::new(vec2(0.0, 0.0));
+        chunk.objects.push(Box::new(Listener::at(10.0)));
+        world.add_chunk(chunk);
+        world.visible_chunks = vec![(0, 0)];
+
+        world.post_event(EventTarget::All, message("ping"));
+        world.dispatch_events();
+        assert_eq!(received(&world, (0, 0)), [vec!["ping"]]);
+
+        world.dispatch_events();
+        assert_eq!(received(&world, (0, 0)), [vec!["ping", "pong"]]);
+    }
+
+    #[test]
+    fn events_skip_chunks_that_are_not_visible() {
+        let mut world = test_world();
+        for x in [0, 1] {
+            let mut chunk = Chunk::new(vec2(x as f32, 0.0));
+            chunk.objects.push(Box::new(Listener::at(x as f32 * world.config().chunk_pixels() + 10.0)));
+            world.add_chunk(chunk);
+        }
+        world.visible_chunks = vec![(0, 0)];
+
+        world.post_event(EventTarget::All, message("hello"));
+        world.dispatch_events();
+
+        assert_eq!(received(&world, (0, 0)), [vec!["hello"]]);
+        assert_eq!(received(&world, (1, 0)), [Vec::<String>::new()]);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::{HashSet, VecDeque};
+
+use macroquad::prelude::*;
+
+use crate::{Tile, World};
+
+impl World {
+    /// Collects the tiles connected to a start tile that satisfy a predicate
+    /// - `start`: Position in world coordinates inside the start tile
+    /// - `max_cells`: Maximum number of tiles to collect
+    /// - `predicate`: Decides whether a tile belongs to the region
+    ///
+    /// The fill spreads to the four orthogonal neighbors of each tile in the
+    /// region and crosses chunk borders, but stops at tiles that are not loaded.
+    /// It ends early once `max_cells` tiles were collected, so a fill on an open
+    /// map stays bounded.
+    ///
+    /// Returns the positions of the tiles in the region, nearest to the start first,
+    /// or an empty list if the start tile is not loaded or does not satisfy the predicate
+    pub fn flood_fill(&self, start: Vec2, max_cells: usize, predicate: impl Fn(&dyn Tile) -> bool) -> Vec<Vec2> {
+        let matches = |(x, y): (i32, i32)| {
+            self.tile_at(vec2(x as f32, y as f32) * self.config.tile_size)
+                .is_some_and(&predicate)
+        };
+
+        let start = self.tile_coords(start);
+        let mut region = Vec::new();
+        if max_cells == 0 || !matches(start) {
+            return region;
+        }
+
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, y)) = queue.pop_front() {
+            region.push(vec2(x as f32, y as f32) * self.config.tile_size);
+            if region.len() >= max_cells {
+                break;
+            }
+            for next in [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)] {
+                if seen.insert(next) && matches(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        region
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{registries, TestTile};
+    use crate::{Chunk, DrawBatch, TILE_SIZE};
+
+    /// A tile that is not part of grass regions
+    #[derive(Clone)]
+    struct Stone {
+        pos: Vec2,
+    }
+
+    impl Tile for Stone {
+        fn get_type_tag(&self) -> &'static str {
+            "stone"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Creates a world with the given chunks filled with grass.
+    fn grass_world(chunks: &[(i32, i32)]) -> World {
+        let (tiles, objects, biomes) = registries();
+        let mut world = World::new("test", tiles, objects, biomes);
+        for &(x, y) in chunks {
+            let mut chunk = Chunk::new(vec2(x as f32, y as f32));
+            let (origin, size) = (chunk.bounds().0, chunk.config().chunk_size);
+            for ty in 0..size {
+                for tx in 0..size {
+                    let tile = TestTile { pos: origin + vec2(tx as f32, ty as f32) * TILE_SIZE, ..TestTile::new("grass") };
+                    chunk.tiles.push(Box::new(tile));
+                }
+            }
+            world.add_chunk(chunk);
+        }
+        world
+    }
+
+    /// Replaces the tile at the given tile coordinates with stone.
+    fn place_stone(world: &mut World, (x, y): (i32, i32)) {
+        let pos = vec2(x as f32, y as f32) * TILE_SIZE;
+        let (chunk, index) = world.get_tile_index(pos);
+        world.get_chunk_mut(chunk).unwrap().tiles[index] = Box::new(Stone { pos });
+    }
+
+    fn is_grass(tile: &dyn Tile) -> bool {
+        tile.get_type_tag() == "grass"
+    }
+
+    fn tile(x: i32, y: i32) -> Vec2 {
+        vec2(x as f32, y as f32) * TILE_SIZE
+    }
+
+    #[test]
+    fn fills_stop_after_max_cells_nearest_first() {
+        let world = grass_world(&[(0, 0)]);
+        let start = tile(4, 4) + Vec2::splat(TILE_SIZE / 2.0);
+
+        let region = world.flood_fill(start, 5, is_grass);
+        assert_eq!(region, [tile(4, 4), tile(4, 3), tile(5, 4), tile(4, 5), tile(3, 4)]);
+        assert!(world.flood_fill(start, 0, is_grass).is_empty());
+    }
+
+    #[test]
+    fn fills_stay_inside_walls() {
+        let mut world = grass_world(&[(0, 0)]);
+        for i in 0..4 {
+            for wall in [(i, 3), (3, i)] {
+                place_stone(&mut world, wall);
+            }
+        }
+
+        let region = world.flood_fill(tile(1, 1), usize::MAX, is_grass);
+        assert_eq!(region.len(), 9);
+        assert!(region.iter().all(|pos| pos.x < tile(3, 0).x && pos.y < tile(0, 3).y));
+        assert!(world.flood_fill(tile(3, 3), usize::MAX, is_grass).is_empty());
+    }
+
+    #[test]
+    fn fills_cross_chunk_borders_up_to_unloaded_chunks() {
+        let mut world = grass_world(&[(0, 0), (1, 0)]);
+        let size = world.config().chunk_size as i32;
+        for x in 0..size * 2 {
+            for y in 1..size {
+                place_stone(&mut world, (x, y));
+            }
+        }
+
+        let region = world.flood_fill(tile(size - 1, 0), usize::MAX, is_grass);
+        assert_eq!(region.len(), size as usize * 2);
+        assert!(region.contains(&tile(0, 0)) && region.contains(&tile(size * 2 - 1, 0)));
+        assert!(region.iter().all(|pos| pos.y == 0.0));
+    }
+}
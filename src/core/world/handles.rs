@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+use crate::{Object, ObjectId, World};
+
+impl World {
+    /// Returns the object with the given stable id
+    /// - `id`: Id assigned to the object when it was spawned or generated
+    ///
+    /// Only objects that store their id (see `Object::get_id`) can be found, and
+    /// only while their chunk is loaded. An object cannot find itself from inside
+    /// its own `tick`, since it is taken out of its chunk while it runs.
+    ///
+    /// Returns `None` if no loaded object has that id
+    pub fn get_object(&self, id: ObjectId) -> Option<&dyn Object> {
+        let (chunk_pos, index) = self.locate_object(id)?;
+        self.chunks.get(&chunk_pos)?.objects.get(index).map(|obj| obj.as_ref())
+    }
+
+    /// Returns a mutable reference to the object with the given stable id
+    /// - `id`: Id assigned to the object when it was spawned or generated
+    ///
+    /// Marks the object's chunk as changed.
+    ///
+    /// Returns `None` if no loaded object has that id
+    pub fn get_object_mut(&mut self, id: ObjectId) -> Option<&mut dyn Object> {
+        let (chunk_pos, index) = self.locate_object(id)?;
+        let chunk = self.chunks.get_mut(&chunk_pos)?;
+        chunk.mark_dirty();
+        chunk.objects.get_mut(index).map(|obj| obj.as_mut())
+    }
+
+    /// Finds the chunk and index of the object with the given id
+    /// - `id`: Id of the object
+    ///
+    /// The recorded index is used when it still holds the object. Otherwise, for
+    /// example after a tick despawned an object before it or game code rearranged
+    /// the chunk's objects by hand, only the recorded chunk is searched.
+    /// Objects moved to another chunk by hand are found again once that chunk is
+    /// reindexed, at the latest by the next update.
+    fn locate_object(&self, id: ObjectId) -> Option<((i32, i32), usize)> {
+        let &(chunk_pos, index) = self.object_index.get(&id)?;
+        let chunk = self.chunks.get(&chunk_pos)?;
+        if chunk.objects.get(index).is_some_and(|obj| obj.get_id() == Some(id)) {
+            return Some((chunk_pos, index));
+        }
+        chunk.objects
+            .iter()
+            .position(|obj| obj.get_id() == Some(id))
+            .map(|index| (chunk_pos, index))
+    }
+
+    /// Assigns ids to the objects of a chunk and records where they live
+    /// - `chunk_pos`: Chunk coordinates of the chunk
+    ///
+    /// Objects without an id get the next free one. Objects that already have an
+    /// id, e.g. from a save, keep it and move the id counter past it. Call it again
+    /// whenever objects were added to, removed from or reordered in the chunk.
+    pub(super) fn register_chunk_objects(&mut self, chunk_pos: (i32, i32)) {
+        let Some(chunk) = self.chunks.get_mut(&chunk_pos) else {
+            return;
+        };
+        for (index, obj) in chunk.objects.iter_mut().enumerate() {
+            Self::register_object(&mut self.next_object_id, &mut self.object_index, obj.as_mut(), (chunk_pos, index));
+        }
+    }
+
+    /// Assigns an id to a single object if needed and records where it lives
+    /// - `next_id`: Id counter of the world
+    /// - `object_index`: Chunk and index each known id was last seen at
+    /// - `obj`: The object
+    /// - `location`: Chunk coordinates of the chunk holding the object and its index there
+    pub(super) fn register_object(
+        next_id: &mut u64,
+        object_index: &mut HashMap<ObjectId, ((i32, i32), usize)>,
+        obj: &mut dyn Object,
+        location: ((i32, i32), usize),
+    ) {
+        if obj.get_id().is_none() {
+            obj.set_id(ObjectId(*next_id));
+        }
+        if let Some(id) = obj.get_id() {
+            *next_id = (*next_id).max(id.0 + 1);
+            object_index.insert(id, location);
+        }
+    }
+
+    /// Forgets the recorded locations of objects that left the world
+    /// - `objects`: The removed objects
+    pub(super) fn forget_objects<'a>(&mut self, objects: impl IntoIterator<Item = &'a Box<dyn Object>>) {
+        for obj in objects {
+            if let Some(id) = obj.get_id() {
+                self.object_index.remove(&id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::{vec2, Rect, Vec2};
+
+    use crate::core::testing::{registries, TestObject, TestTile};
+    use crate::{Chunk, DrawBatch, Object, ObjectId, SaveFormat, Viewport, World};
+
+    fn test_world() -> World {
+        let (tiles, objects, biomes) = registries();
+        World::new("test", tiles, objects, biomes)
+    }
+
+    /// Creates a chunk with the default config filled with grass.
+    fn grass_chunk(pos: (i32, i32)) -> Chunk {
+        let mut chunk = Chunk::new(vec2(pos.0 as f32, pos.1 as f32));
+        let (origin, size, tile_size) = (chunk.bounds().0, chunk.config().chunk_size, chunk.config().tile_size);
+        for y in 0..size {
+            for x in 0..size {
+                let tile = TestTile { pos: origin + vec2(x as f32, y as f32) * tile_size, ..TestTile::new("grass") };
+                chunk.tiles.push(Box::new(tile));
+            }
+        }
+        chunk
+    }
+
+    /// Returns a viewport that shows chunk `(0, 0)`.
+    fn first_chunk_viewport(world: &World) -> Viewport {
+        let area = Rect::new(0.0, 0.0, world.config.chunk_pixels(), world.config.chunk_pixels());
+        Viewport::new(area.center(), area.size())
+    }
+
+    fn spawn(world: &mut World, obj: impl Object) -> ObjectId {
+        world.spawn_object(Box::new(obj)).unwrap().unwrap()
+    }
+
+    /// Looks up another object by id on every tick and despawns one on its first tick
+    #[derive(Clone, Default)]
+    struct Seeker {
+        pos: Vec2,
+        id: Option<ObjectId>,
+        /// Object to look up with `World::get_object`
+        target: Option<ObjectId>,
+        /// Whether the target was found on the last tick
+        found_target: bool,
+        /// Object to despawn with `World::despawn_objects_where`
+        despawn: Option<ObjectId>,
+        ticks: u32,
+    }
+
+    impl Seeker {
+        fn at(x: f32) -> Self {
+            Self { pos: vec2(x, 10.0), ..Default::default() }
+        }
+    }
+
+    impl Object for Seeker {
+        fn get_type_tag(&self) -> &'static str {
+            "seeker"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(8.0)
+        }
+
+        fn get_velocity(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+
+        fn tick(&mut self, _dt: f32, world: &mut World) -> bool {
+            self.ticks += 1;
+            if let Some(id) = self.despawn.take() {
+                world.despawn_objects_where(|obj| obj.get_id() == Some(id));
+            }
+            self.found_target = self.target.is_some_and(|target| world.get_object(target).is_some());
+            false
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch) {}
+
+        fn set_size(&mut self, _size: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn set_velocity(&mut self, _velocity: Vec2) {}
+
+        fn get_id(&self) -> Option<ObjectId> {
+            self.id
+        }
+
+        fn set_id(&mut self, id: ObjectId) {
+            self.id = Some(id);
+        }
+
+        fn clone_box(&self) -> Box<dyn Object> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn as_seeker(obj: &dyn Object) -> Option<&Seeker> {
+        (obj as &dyn std::any::Any).downcast_ref()
+    }
+
+    #[test]
+    fn ids_resolve_after_earlier_objects_are_despawned() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        let first = spawn(&mut world, TestObject::new(vec2(10.0, 10.0)));
+        let second = spawn(&mut world, TestObject::new(vec2(20.0, 20.0)));
+        world.visible_chunks = vec![(0, 0)];
+
+        assert_eq!(world.despawn_objects_where(|obj| obj.get_id() == Some(first)), 1);
+
+        assert!(world.get_object(first).is_none());
+        assert_eq!(world.get_object(second).map(|obj| obj.get_pos()), Some(vec2(20.0, 20.0)));
+        assert_eq!(world.object_index.get(&second), Some(&((0, 0), 0)));
+    }
+
+    #[test]
+    fn ids_follow_objects_between_chunks() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((1, 0)));
+        let id = spawn(&mut world, TestObject::new(vec2(10.0, 10.0)));
+        let chunk_pixels = world.config.chunk_pixels();
+
+        world.get_object_mut(id).unwrap().set_pos(vec2(chunk_pixels + 10.0, 10.0));
+        world.visible_chunks = vec![(0, 0), (1, 0)];
+        world.migrate_objects();
+
+        assert_eq!(world.object_index.get(&id), Some(&((1, 0), 0)));
+        assert!(world.get_object(id).is_some());
+    }
+
+    #[test]
+    fn ticking_objects_see_their_own_chunk() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        let target = spawn(&mut world, TestObject::new(vec2(10.0, 10.0)));
+        let seeker = spawn(&mut world, Seeker { target: Some(target), ..Seeker::at(20.0) });
+
+        world.advance(first_chunk_viewport(&world), 0.1);
+
+        assert!(world.get_object(seeker).and_then(as_seeker).is_some_and(|seeker| seeker.found_target));
+    }
+
+    #[test]
+    fn ids_resolve_after_a_tick_despawns_the_first_of_three() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        let (first, second, third) = (ObjectId(1), ObjectId(2), ObjectId(3));
+        spawn(&mut world, Seeker { id: Some(first), ..Seeker::at(10.0) });
+        spawn(&mut world, Seeker { id: Some(second), despawn: Some(first), ..Seeker::at(20.0) });
+        spawn(&mut world, Seeker { id: Some(third), ..Seeker::at(30.0) });
+
+        world.advance(first_chunk_viewport(&world), 0.1);
+
+        assert!(world.get_object(first).is_none());
+        for (id, x) in [(second, 20.0), (third, 30.0)] {
+            let obj = world.get_object(id).and_then(as_seeker).unwrap();
+            assert_eq!((obj.pos.x, obj.ticks), (x, 1));
+        }
+        assert_eq!(world.object_index.get(&third), Some(&((0, 0), 1)));
+    }
+
+    #[test]
+    fn objects_despawned_by_a_sibling_tick_are_skipped() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        let (first, second, third) = (ObjectId(1), ObjectId(2), ObjectId(3));
+        spawn(&mut world, Seeker { id: Some(first), despawn: Some(second), ..Seeker::at(10.0) });
+        spawn(&mut world, Seeker { id: Some(second), ..Seeker::at(20.0) });
+        spawn(&mut world, Seeker { id: Some(third), ..Seeker::at(30.0) });
+
+        world.advance(first_chunk_viewport(&world), 0.1);
+
+        assert!(world.get_object(second).is_none());
+        let ticks = |id| world.get_object(id).and_then(as_seeker).map(|obj| obj.ticks);
+        assert_eq!(ticks(first), Some(1));
+        assert_eq!(ticks(third), Some(1));
+        assert_eq!(world.get_chunk((0, 0)).unwrap().objects.len(), 2);
+    }
+
+    #[test]
+    fn ids_survive_save_and_load() {
+        for format in [SaveFormat::Json, SaveFormat::Bincode] {
+            let save_dir = std::env::temp_dir()
+                .join(format!("gaymwtf-core-ids-{}-{}", format.extension(), std::process::id()))
+                .to_string_lossy()
+                .into_owned();
+            let _ = std::fs::remove_dir_all(&save_dir);
+            let mut world = test_world();
+            world.add_chunk(grass_chunk((0, 0)));
+            spawn(&mut world, TestObject::new(vec2(10.0, 10.0)));
+            let id = spawn(&mut world, TestObject::new(vec2(20.0, 20.0)));
+            world.save_world_as(&save_dir, format).unwrap();
+
+            let (tiles, objects, biomes) = registries();
+            let mut loaded = World::load_world_as(&save_dir, format, tiles, objects, biomes).unwrap();
+            assert_eq!(loaded.get_object(id).map(|obj| obj.get_pos()), Some(vec2(20.0, 20.0)));
+            let next = spawn(&mut loaded, TestObject::new(vec2(30.0, 30.0)));
+            assert!(next.0 > id.0);
+            let _ = std::fs::remove_dir_all(&save_dir);
+        }
+    }
+}
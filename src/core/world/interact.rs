@@ -0,0 +1,188 @@
+use macroquad::prelude::*;
+
+use crate::{Object, World};
+
+impl World {
+    /// Routes a mouse click at a world position to the object or tile under it
+    /// - `world_pos`: Clicked position in world coordinates
+    /// - `button`: Mouse button that was clicked
+    /// - `actor`: Object performing the interaction, usually the player
+    ///
+    /// The topmost object in a visible chunk whose bounds contain the position
+    /// receives `on_left_interact` or `on_right_interact`; if there is none, the
+    /// tile at the position does, see `object_index_at`. `actor` must not be stored in the world while
+    /// it is borrowed here; take it out of its chunk first if it lives there.
+    ///
+    /// Unlike `tick` and `on_event`, the interaction hooks do not receive the world,
+    /// so the target is borrowed in place. Taking its chunk out of the world and
+    /// reinserting it afterwards is only needed when a hook must read the world.
+    ///
+    /// Returns `true` if an object or tile received the interaction, `false` for
+    /// buttons other than left and right or if nothing is at that position
+    pub fn interact_at(&mut self, world_pos: Vec2, button: MouseButton, actor: &mut dyn Object) -> bool {
+        if !matches!(button, MouseButton::Left | MouseButton::Right) {
+            return false;
+        }
+
+        if let Some((chunk_pos, index)) = self.object_index_at(world_pos) {
+            if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
+                let target = &mut chunk.objects[index];
+                match button {
+                    MouseButton::Left => target.on_left_interact(actor),
+                    _ => target.on_right_interact(actor),
+                }
+                chunk.mark_dirty();
+                return true;
+            }
+        }
+
+        let Some(tile) = self.tile_at_mut(world_pos) else {
+            return false;
+        };
+        match button {
+            MouseButton::Left => tile.on_left_interact(actor),
+            _ => tile.on_right_interact(actor),
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{registries, TestObject};
+    use crate::{Chunk, DrawBatch, Tile, TILE_SIZE};
+
+    /// Counts left and right interactions
+    #[derive(Clone, Default)]
+    struct Lever {
+        pos: Vec2,
+        left: u32,
+        right: u32,
+    }
+
+    impl Tile for Lever {
+        fn get_type_tag(&self) -> &'static str {
+            "lever"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn on_left_interact(&mut self, _obj: &mut dyn Object) {
+            self.left += 1;
+        }
+
+        fn on_right_interact(&mut self, _obj: &mut dyn Object) {
+            self.right += 1;
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    impl Object for Lever {
+        fn get_type_tag(&self) -> &'static str {
+            "lever"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(8.0)
+        }
+
+        fn get_velocity(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch) {}
+
+        fn set_size(&mut self, _size: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn set_velocity(&mut self, _velocity: Vec2) {}
+
+        fn on_left_interact(&mut self, _other: &mut dyn Object) {
+            self.left += 1;
+        }
+
+        fn on_right_interact(&mut self, _other: &mut dyn Object) {
+            self.right += 1;
+        }
+
+        fn clone_box(&self) -> Box<dyn Object> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Creates a world with chunk `(0, 0)` made of levers, with a lever object at `(10, 10)`.
+    fn lever_world() -> World {
+        let (tiles, objects, biomes) = registries();
+        let mut world = World::new("test", tiles, objects, biomes);
+        let mut chunk = Chunk::new(vec2(0.0, 0.0));
+        let size = chunk.config().chunk_size;
+        for y in 0..size {
+            for x in 0..size {
+                let pos = vec2(x as f32, y as f32) * TILE_SIZE;
+                chunk.tiles.push(Box::new(Lever { pos, ..Lever::default() }));
+            }
+        }
+        chunk.objects.push(Box::new(Lever { pos: vec2(10.0, 10.0), ..Lever::default() }));
+        world.add_chunk(chunk);
+        world.visible_chunks = vec![(0, 0)];
+        world
+    }
+
+    fn tile_counts(world: &World, pos: Vec2) -> (u32, u32) {
+        let lever = (world.tile_at(pos).unwrap() as &dyn std::any::Any).downcast_ref::<Lever>().unwrap();
+        (lever.left, lever.right)
+    }
+
+    fn object_counts(world: &World) -> (u32, u32) {
+        let obj = world.get_chunk((0, 0)).unwrap().objects[0].as_ref();
+        let lever = (obj as &dyn std::any::Any).downcast_ref::<Lever>().unwrap();
+        (lever.left, lever.right)
+    }
+
+    #[test]
+    fn objects_take_clicks_before_the_tile_under_them() {
+        let mut world = lever_world();
+        let mut actor = TestObject::new(Vec2::ZERO);
+
+        assert!(world.interact_at(vec2(12.0, 12.0), MouseButton::Left, &mut actor));
+        assert!(world.interact_at(vec2(12.0, 12.0), MouseButton::Right, &mut actor));
+        assert_eq!(object_counts(&world), (1, 1));
+        assert_eq!(tile_counts(&world, vec2(12.0, 12.0)), (0, 0));
+    }
+
+    #[test]
+    fn tiles_take_clicks_where_no_object_is() {
+        let mut world = lever_world();
+        let mut actor = TestObject::new(Vec2::ZERO);
+        let beside = vec2(10.0, 10.0) + vec2(TILE_SIZE * 2.0, 0.0);
+
+        assert!(world.interact_at(beside, MouseButton::Right, &mut actor));
+        assert!(!world.interact_at(beside, MouseButton::Middle, &mut actor));
+        assert!(!world.interact_at(vec2(-5.0, -5.0), MouseButton::Left, &mut actor));
+        assert_eq!(tile_counts(&world, beside), (0, 1));
+        assert_eq!(object_counts(&world), (0, 0));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{Chunk, World};
+
+impl World {
+    /// Prepares a chunk that was just inserted into the world
+    /// - `chunk_pos`: Chunk coordinates of the chunk
+    ///
+    /// Registers the ids of its objects, then calls `on_load` on its tiles, overlays
+    /// and objects. The chunk is taken out of the world while the hooks run, so they
+    /// can read the rest of the world.
+    pub(super) fn chunk_loaded(&mut self, chunk_pos: (i32, i32)) {
+        self.register_chunk_objects(chunk_pos);
+        let Some(mut chunk) = self.chunks.remove(&chunk_pos) else {
+            return;
+        };
+        for tile in &mut chunk.tiles {
+            tile.on_load(self);
+        }
+        for (_, tile) in &mut chunk.overlays {
+            tile.on_load(self);
+        }
+        for obj in &mut chunk.objects {
+            obj.on_load(self);
+        }
+        self.chunks.insert(chunk_pos, chunk);
+    }
+
+    /// Releases a chunk that was just taken out of the world
+    /// - `chunk`: The removed chunk
+    ///
+    /// Forgets the ids of its objects and calls `on_unload` on its tiles, overlays
+    /// and objects.
+    pub(super) fn chunk_unloaded(&mut self, chunk: &mut Chunk) {
+        self.forget_objects(&chunk.objects);
+        for tile in &mut chunk.tiles {
+            tile.on_unload();
+        }
+        for (_, tile) in &mut chunk.overlays {
+            tile.on_unload();
+        }
+        for obj in &mut chunk.objects {
+            obj.on_unload();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::math::{vec2, Vec2};
+
+    use crate::core::testing::registries;
+    use crate::{Chunk, DrawBatch, Object, ObjectRegistry, Tile, TileRegistry, World, TILE_SIZE};
+
+    /// Counts how often its load and unload hooks ran
+    #[derive(Clone, Default)]
+    struct Hooked {
+        pos: Vec2,
+        loads: u32,
+        unloads: u32,
+        /// Whether the own chunk was missing from the world during `on_load`
+        own_chunk_absent: bool,
+    }
+
+    impl Tile for Hooked {
+        fn get_type_tag(&self) -> &'static str {
+            "hooked"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn on_load(&mut self, world: &World) {
+            self.loads += 1;
+            self.own_chunk_absent = world.get_chunk((0, 0)).is_none();
+        }
+
+        fn on_unload(&mut self) {
+            self.unloads += 1;
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    impl Object for Hooked {
+        fn get_type_tag(&self) -> &'static str {
+            "hooked"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(8.0)
+        }
+
+        fn get_velocity(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch) {}
+
+        fn set_size(&mut self, _size: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn set_velocity(&mut self, _velocity: Vec2) {}
+
+        fn on_load(&mut self, world: &World) {
+            self.loads += 1;
+            self.own_chunk_absent = world.get_chunk((0, 0)).is_none();
+        }
+
+        fn on_unload(&mut self) {
+            self.unloads += 1;
+        }
+
+        fn clone_box(&self) -> Box<dyn Object> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn hooked_registries() -> (TileRegistry, ObjectRegistry) {
+        let (mut tiles, mut objects, _) = registries();
+        tiles.register(Hooked::default());
+        objects.register(Hooked::default());
+        (tiles, objects)
+    }
+
+    fn hooked_world() -> World {
+        let (tiles, objects) = hooked_registries();
+        World::new("test", tiles, objects, registries().2)
+    }
+
+    /// Creates chunk `(0, 0)` filled with hooked tiles, with a hooked overlay and object.
+    fn hooked_chunk() -> Chunk {
+        let mut chunk = Chunk::new(vec2(0.0, 0.0));
+        let size = chunk.config().chunk_size;
+        for y in 0..size {
+            for x in 0..size {
+                let pos = vec2(x as f32, y as f32) * chunk.config().tile_size;
+                chunk.tiles.push(Box::new(Hooked { pos, ..Hooked::default() }));
+            }
+        }
+        chunk.overlays.push((1, Box::new(Hooked::default())));
+        chunk.objects.push(Box::new(Hooked { pos: vec2(10.0, 10.0), ..Hooked::default() }));
+        chunk
+    }
+
+    /// Returns the load and unload counts of every tile, overlay and object of a chunk.
+    fn counts(chunk: &Chunk) -> Vec<(u32, u32)> {
+        let tiles = chunk.tiles.iter().chain(chunk.overlays.iter().map(|(_, tile)| tile))
+            .map(|tile| (tile.as_ref() as &dyn std::any::Any).downcast_ref::<Hooked>().unwrap());
+        let objects = chunk.objects.iter()
+            .map(|obj| (obj.as_ref() as &dyn std::any::Any).downcast_ref::<Hooked>().unwrap());
+        tiles.chain(objects).map(|hooked| (hooked.loads, hooked.unloads)).collect()
+    }
+
+    fn all(chunk: &Chunk, expected: (u32, u32)) -> bool {
+        counts(chunk).iter().all(|&count| count == expected)
+    }
+
+    #[test]
+    fn deserialized_chunks_run_on_load_once_added_to_a_world() {
+        let (tiles, objects) = hooked_registries();
+        let mut world = hooked_world();
+        let data = hooked_chunk().serialize().unwrap();
+
+        let chunk = Chunk::deserialize(&data, &tiles, &objects).unwrap();
+        assert!(all(&chunk, (0, 0)));
+
+        assert!(world.add_chunk(chunk));
+        let chunk = world.get_chunk((0, 0)).unwrap();
+        assert_eq!(counts(chunk).len(), chunk.tiles.len() + 2);
+        assert!(all(chunk, (1, 0)));
+        let obj = (chunk.objects[0].as_ref() as &dyn std::any::Any).downcast_ref::<Hooked>().unwrap();
+        assert!(obj.own_chunk_absent);
+    }
+
+    #[test]
+    fn replaced_and_removed_chunks_run_on_unload() {
+        let mut world = hooked_world();
+        world.add_chunk(hooked_chunk());
+
+        let replaced = world.replace_chunk(hooked_chunk()).unwrap().unwrap();
+        assert!(all(&replaced, (1, 1)));
+        assert!(all(world.get_chunk((0, 0)).unwrap(), (1, 0)));
+
+        let removed = world.remove_chunk((0, 0)).unwrap();
+        assert!(all(&removed, (1, 1)));
+        assert!(world.get_chunk((0, 0)).is_none());
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+use crate::{World, LIGHT_FALLOFF};
+
+/// Per-tile brightness computed by `World::compute_light`.
+///
+/// Values range from `0.0` (dark) to `1.0` (fully lit) and cover a rectangle of
+/// whole tiles. Multiply them into the tint color of a tile or object to shade it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightMap {
+    /// Tile coordinates of the top-left tile covered by the map
+    origin: (i32, i32),
+    /// Number of tiles covered horizontally
+    width: usize,
+    /// Number of tiles covered vertically
+    height: usize,
+    /// Brightness of every covered tile, row by row
+    values: Vec<f32>,
+    /// Tile size of the world the map was computed for
+    tile_size: f32,
+}
+
+impl LightMap {
+    /// Returns the tile coordinates of the top-left tile covered by the map
+    pub fn origin(&self) -> (i32, i32) {
+        self.origin
+    }
+
+    /// Returns the number of tiles covered horizontally
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of tiles covered vertically
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the brightness of every covered tile, row by row
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// Returns the brightness of the tile at the given world position
+    /// - `world_pos`: Position in world coordinates
+    ///
+    /// Returns `0.0` for positions outside the map
+    pub fn brightness_at(&self, world_pos: Vec2) -> f32 {
+        let tile_x = (world_pos.x / self.tile_size).floor() as i32 - self.origin.0;
+        let tile_y = (world_pos.y / self.tile_size).floor() as i32 - self.origin.1;
+        if tile_x < 0 || tile_y < 0 || tile_x as usize >= self.width || tile_y as usize >= self.height {
+            return 0.0;
+        }
+        self.values[tile_y as usize * self.width + tile_x as usize]
+    }
+}
+
+impl World {
+    /// Computes a simple light map for the tiles in a region
+    /// - `region`: Area in world coordinates, usually the visible screen area
+    ///
+    /// Every tile whose `light_emission` is above zero seeds the map, and light
+    /// spreads to the four neighbors of each tile, losing `LIGHT_FALLOFF` per tile.
+    /// A tile's `light_opacity` scales down the light it passes on. Only tiles
+    /// inside the region are considered, so emitters just outside it do not light
+    /// its border; pad the region if that matters. Positions without a loaded tile
+    /// are treated as empty, transparent space.
+    ///
+    /// Returns the brightness of every tile in the region
+    pub fn compute_light(&self, region: Rect) -> LightMap {
+        let tile_size = self.config.tile_size;
+        let start_x = (region.x / tile_size).floor() as i32;
+        let start_y = (region.y / tile_size).floor() as i32;
+        let end_x = (region.right() / tile_size).ceil() as i32;
+        let end_y = (region.bottom() / tile_size).ceil() as i32;
+        let width = (end_x - start_x).max(0) as usize;
+        let height = (end_y - start_y).max(0) as usize;
+
+        let mut emission = vec![0.0; width * height];
+        let mut transmission = vec![1.0; width * height];
+        let mut values = vec![0.0; width * height];
+        let mut queue = VecDeque::new();
+
+        for y in 0..height {
+            for x in 0..width {
+                let index = y * width + x;
+                let pos = vec2((start_x + x as i32) as f32, (start_y + y as i32) as f32) * tile_size;
+                if let Some(tile) = self.tile_at(pos) {
+                    emission[index] = tile.light_emission().clamp(0.0, 1.0);
+                    transmission[index] = 1.0 - tile.light_opacity().clamp(0.0, 1.0);
+                }
+                if emission[index] > 0.0 {
+                    values[index] = emission[index];
+                    queue.push_back(index);
+                }
+            }
+        }
+
+        while let Some(index) = queue.pop_front() {
+            let outgoing = (values[index] * transmission[index]).max(emission[index]) - LIGHT_FALLOFF;
+            if outgoing <= 0.0 {
+                continue;
+            }
+
+            let (x, y) = (index % width, index / width);
+            let neighbors = [
+                (x > 0).then(|| index - 1),
+                (x + 1 < width).then(|| index + 1),
+                (y > 0).then(|| index - width),
+                (y + 1 < height).then(|| index + width),
+            ];
+            for neighbor in neighbors.into_iter().flatten() {
+                if outgoing > values[neighbor] {
+                    values[neighbor] = outgoing;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        LightMap {
+            origin: (start_x, start_y),
+            width,
+            height,
+            values,
+            tile_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{registries, TestTile};
+    use crate::{Chunk, DrawBatch, Tile, TILE_SIZE};
+
+    /// A tile that emits full light, or blocks all light when `opaque` is set
+    #[derive(Clone)]
+    struct Block {
+        pos: Vec2,
+        opaque: bool,
+    }
+
+    impl Tile for Block {
+        fn get_type_tag(&self) -> &'static str {
+            if self.opaque { "wall" } else { "lamp" }
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn light_emission(&self) -> f32 {
+            if self.opaque { 0.0 } else { 1.0 }
+        }
+
+        fn light_opacity(&self) -> f32 {
+            if self.opaque { 1.0 } else { 0.0 }
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Creates a world with the given chunks filled with grass.
+    fn grass_world(chunks: &[(i32, i32)]) -> World {
+        let (tiles, objects, biomes) = registries();
+        let mut world = World::new("test", tiles, objects, biomes);
+        for &(x, y) in chunks {
+            let mut chunk = Chunk::new(vec2(x as f32, y as f32));
+            let (origin, size) = (chunk.bounds().0, chunk.config().chunk_size);
+            for ty in 0..size {
+                for tx in 0..size {
+                    let tile = TestTile { pos: origin + vec2(tx as f32, ty as f32) * TILE_SIZE, ..TestTile::new("grass") };
+                    chunk.tiles.push(Box::new(tile));
+                }
+            }
+            world.add_chunk(chunk);
+        }
+        world
+    }
+
+    fn tile(x: i32, y: i32) -> Vec2 {
+        vec2(x as f32, y as f32) * TILE_SIZE
+    }
+
+    /// Replaces the tile at the given tile coordinates with a lamp or a wall.
+    fn place(world: &mut World, (x, y): (i32, i32), opaque: bool) {
+        let pos = tile(x, y);
+        let (chunk, index) = world.get_tile_index(pos);
+        world.get_chunk_mut(chunk).unwrap().tiles[index] = Box::new(Block { pos, opaque });
+    }
+
+    /// Returns the area covered by the given chunks of row 0, in world coordinates.
+    fn chunk_row(world: &World, first: i32, count: i32) -> Rect {
+        let chunk_pixels = world.config().chunk_pixels();
+        Rect::new(first as f32 * chunk_pixels, 0.0, count as f32 * chunk_pixels, chunk_pixels)
+    }
+
+    #[test]
+    fn light_falls_off_with_each_tile() {
+        let mut world = grass_world(&[(0, 0)]);
+        place(&mut world, (4, 4), false);
+
+        let light = world.compute_light(chunk_row(&world, 0, 1));
+        for (pos, steps) in [((4, 4), 0), ((5, 4), 1), ((4, 2), 2), ((6, 5), 3), ((9, 7), 8)] {
+            let expected = (1.0 - steps as f32 * LIGHT_FALLOFF).max(0.0);
+            assert_eq!(light.brightness_at(tile(pos.0, pos.1)), expected, "tile {:?}", pos);
+        }
+        assert_eq!(light.brightness_at(tile(-1, 0)), 0.0);
+    }
+
+    #[test]
+    fn opaque_tiles_are_lit_but_pass_no_light() {
+        let mut world = grass_world(&[(0, 0)]);
+        place(&mut world, (4, 4), false);
+        place(&mut world, (5, 4), true);
+
+        let light = world.compute_light(chunk_row(&world, 0, 1));
+        assert_eq!(light.brightness_at(tile(5, 4)), 1.0 - LIGHT_FALLOFF);
+        assert_eq!(light.brightness_at(tile(6, 4)), 1.0 - 4.0 * LIGHT_FALLOFF);
+    }
+
+    #[test]
+    fn light_crosses_chunk_borders_inside_the_region() {
+        let mut world = grass_world(&[(0, 0), (1, 0)]);
+        let size = world.config().chunk_size as i32;
+        place(&mut world, (size - 1, 0), false);
+
+        let both = world.compute_light(chunk_row(&world, 0, 2));
+        assert_eq!(both.brightness_at(tile(size, 0)), 1.0 - LIGHT_FALLOFF);
+        assert_eq!(both.brightness_at(tile(size + 1, 1)), 1.0 - 3.0 * LIGHT_FALLOFF);
+
+        let second = world.compute_light(chunk_row(&world, 1, 1));
+        assert_eq!(second.origin(), (size, 0));
+        assert_eq!(second.brightness_at(tile(size, 0)), 0.0);
+    }
+
+    #[test]
+    fn light_spreads_into_unloaded_space() {
+        let mut world = grass_world(&[(0, 0)]);
+        place(&mut world, (0, 0), false);
+
+        let light = world.compute_light(Rect::new(-2.0 * TILE_SIZE, 0.0, 4.0 * TILE_SIZE, TILE_SIZE));
+        assert_eq!((light.width(), light.height()), (4, 1));
+        assert_eq!(light.values(), [1.0 - 2.0 * LIGHT_FALLOFF, 1.0 - LIGHT_FALLOFF, 1.0, 1.0 - LIGHT_FALLOFF]);
+    }
+}
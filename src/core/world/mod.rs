@@ -1,19 +1,110 @@
 use macroquad::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use serde::{Serialize, Deserialize};
 use std::fs;
+use std::time::{Duration, Instant};
+
+mod capture;
+mod events;
+mod fill;
+mod handles;
+mod interact;
+mod lifecycle;
+mod light;
+mod path;
+mod raycast;
+mod viewport;
+
+pub use events::{Event, EventTarget};
+pub use light::LightMap;
+pub use raycast::{RayHit, RayTarget};
+pub use viewport::Viewport;
 
 use crate::{
-    Chunk, ObjectRegistry, TileRegistry, BiomeRegistry,
-    DrawBatch, CHUNK_PIXELS, log_world, Tile, Object
+    core::tile::{
+        NEIGHBOR_UP, NEIGHBOR_UP_RIGHT, NEIGHBOR_RIGHT, NEIGHBOR_DOWN_RIGHT,
+        NEIGHBOR_DOWN, NEIGHBOR_DOWN_LEFT, NEIGHBOR_LEFT, NEIGHBOR_UP_LEFT,
+    },
+    Chunk, ObjectRegistry, TileRegistry, BiomeRegistry, WorldGenerator, GenContext,
+    core::save::{decompress, migrate, world_data_from_bytes, world_data_to_bytes, write_atomic, Vec2Save, COMPRESSED_EXTENSION}, SAVE_VERSION,
+    DrawBatch, WorldConfig, COLLISION_BUFFER, log_world, log_chunk, Tile, Object, ObjectId, Direction, SaveFormat, resolve_collision,
+    core::object::will_collide
 };
 
+/// Default simulation rate for `World::update_fixed`, in ticks per second.
+const DEFAULT_TICK_RATE: f32 = 60.0;
+
+/// Default length of a full in-game day, in seconds of game time.
+const DEFAULT_DAY_LENGTH: f64 = 600.0;
+
+/// Maximum number of fixed steps run in a single `World::update_fixed` call.
+/// Prevents a long frame from stalling the game while it catches up.
+const MAX_FIXED_STEPS: u32 = 5;
+
 /// Serializable data structure representing world metadata.
 /// Used for saving and loading world information.
 #[derive(Serialize, Deserialize)]
 pub struct WorldData {
+    /// Version of the save format, `0` for saves written before it was recorded
+    #[serde(default)]
+    pub version: u32,
     /// Name of the world
     pub name: String,
+    /// Seed used for deterministic world generation
+    #[serde(default)]
+    pub seed: u64,
+    /// Game time elapsed in the world, in seconds
+    #[serde(default)]
+    pub game_time: f64,
+    /// Id the next spawned object receives
+    #[serde(default)]
+    pub next_object_id: u64,
+    /// Camera position when the world was saved, `None` if none was recorded
+    #[serde(default)]
+    pub camera: Option<Vec2Save>,
+    /// Camera zoom when the world was saved
+    #[serde(default = "default_camera_zoom")]
+    pub camera_zoom: f32,
+    /// Tile size, chunk size and activation margin of the world, the defaults for older saves
+    #[serde(default)]
+    pub config: WorldConfig,
+}
+
+/// Zoom of saves written before the camera was recorded
+fn default_camera_zoom() -> f32 {
+    1.0
+}
+
+/// Order in which the world draws objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    /// Objects are drawn in the order they are stored in their chunks.
+    #[default]
+    None,
+    /// Objects are drawn by the bottom edge of their bounds (`pos.y + size.y`),
+    /// so objects further down the screen are drawn on top.
+    YAscending,
+}
+
+/// Counts and timings of the most recent world update and draw.
+///
+/// Counts are always recorded. Timings stay zero unless enabled with
+/// `World::set_stats_timing`, since measuring them reads the clock several times per
+/// frame. With `update_fixed`, update values describe the last fixed step.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WorldStats {
+    /// Number of objects ticked in visible chunks
+    pub active_objects: usize,
+    /// Number of tiles ticked in visible chunks
+    pub visible_tiles: usize,
+    /// Number of object pairs tested by the collision narrow phase
+    pub collision_pairs: usize,
+    /// Time spent ticking chunks, objects and tiles
+    pub tick_time: Duration,
+    /// Time spent checking object and tile collisions
+    pub collision_time: Duration,
+    /// Time spent in `World::draw`
+    pub draw_time: Duration,
 }
 
 /// Represents the entire game world, containing chunks, objects, and game state.
@@ -34,6 +125,60 @@ pub struct World {
     draw_batch: DrawBatch,
     /// Name of the current world
     world_name: String,
+    /// Seed used for deterministic world generation
+    seed: u64,
+    /// Game time elapsed in the world, in seconds
+    game_time: f64,
+    /// Multiplier applied to elapsed time before it is added to the game clock
+    time_scale: f64,
+    /// Length of a full in-game day, in seconds of game time
+    day_length: f64,
+    /// Simulation rate used by `update_fixed`, in ticks per second
+    tick_rate: f32,
+    /// Frame time accumulated but not yet consumed by fixed steps
+    accumulator: f32,
+    /// Interpolation factor between the last two fixed steps
+    alpha: f32,
+    /// Generator used to create missing chunks as they become visible
+    generator: Option<Box<dyn WorldGenerator>>,
+    /// Order in which objects are drawn
+    object_sort: SortMode,
+    /// Events posted with `post_event` waiting to be delivered
+    events: Vec<(EventTarget, Event)>,
+    /// Whether the simulation is frozen
+    paused: bool,
+    /// Whether a single tick was requested while paused
+    pending_step: bool,
+    /// Counts and timings of the most recent update and draw
+    stats: WorldStats,
+    /// Whether update and draw times are measured into `stats`
+    stats_timing: bool,
+    /// Maximum number of loaded chunks, `None` for no limit
+    chunk_budget: Option<usize>,
+    /// Skin width passed to `resolve_collision` for object pairs
+    collision_buffer: f32,
+    /// Object count per chunk above which a warning is logged, `None` for no limit
+    max_objects_per_chunk: Option<usize>,
+    /// Whether `find_path` may move diagonally between tiles
+    path_diagonals: bool,
+    /// Id the next object without one receives
+    next_object_id: u64,
+    /// Chunk and index each object id was last seen at
+    object_index: HashMap<ObjectId, ((i32, i32), usize)>,
+    /// Number of visibility updates so far, used to age chunks
+    frame: u64,
+    /// Frame each loaded chunk was last visible in
+    last_visible: HashMap<(i32, i32), u64>,
+    /// Directory and format evicted chunks are saved to and reloaded from
+    save_dir: Option<(String, SaveFormat)>,
+    /// Chunks known to have no file in the save directory, so it is not probed again
+    missing_on_disk: HashSet<(i32, i32)>,
+    /// Whether chunk files are written gzip-compressed
+    compress_chunks: bool,
+    /// Tile size, chunk size and activation margin used by the world
+    config: WorldConfig,
+    /// Camera position and zoom written with the world data, see `set_camera`
+    camera: Option<(Vec2, f32)>,
 }
 
 impl World {
@@ -52,60 +197,745 @@ impl World {
             visible_chunks: Vec::new(),
             draw_batch: DrawBatch::new(),
             world_name: world_name.to_string(),
+            seed: 0,
+            game_time: 0.0,
+            time_scale: 1.0,
+            day_length: DEFAULT_DAY_LENGTH,
+            tick_rate: DEFAULT_TICK_RATE,
+            accumulator: 0.0,
+            alpha: 0.0,
+            generator: None,
+            object_sort: SortMode::None,
+            events: Vec::new(),
+            paused: false,
+            pending_step: false,
+            stats: WorldStats::default(),
+            stats_timing: false,
+            chunk_budget: None,
+            collision_buffer: COLLISION_BUFFER,
+            max_objects_per_chunk: None,
+            path_diagonals: false,
+            next_object_id: 1,
+            object_index: HashMap::new(),
+            frame: 0,
+            last_visible: HashMap::new(),
+            save_dir: None,
+            missing_on_disk: HashSet::new(),
+            compress_chunks: false,
+            config: WorldConfig::default(),
+            camera: None,
+        }
+    }
+
+    /// Sets the generator used to create chunks on demand
+    /// - `generator`: The generator to use
+    ///
+    /// Once set, any visible chunk coordinate without a loaded chunk is
+    /// generated during `update`. Without a generator, missing chunks stay empty.
+    /// Generated chunks must be laid out with the world's config, e.g. with
+    /// `GenContext::new_chunk`; other chunks are discarded with a warning.
+    pub fn set_generator(&mut self, generator: Box<dyn WorldGenerator>) {
+        self.generator = Some(generator);
+    }
+
+    /// Creates a new, empty world with the given name, registries and generation seed
+    /// - `world_name`: Name of the world
+    /// - `seed`: Seed used for deterministic world generation
+    /// - `tile_registry`: Registry of available tile types
+    /// - `object_registry`: Registry of available object types
+    /// - `biome_registry`: Registry of available biome types
+    pub fn with_seed(world_name: &str, seed: u64, tile_registry: TileRegistry, object_registry: ObjectRegistry, biome_registry: BiomeRegistry) -> Self {
+        let mut world = Self::new(world_name, tile_registry, object_registry, biome_registry);
+        world.seed = seed;
+        world
+    }
+
+    /// Returns the seed used for deterministic world generation
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Sets the seed used for deterministic world generation
+    /// - `seed`: The new seed
+    ///
+    /// Only affects chunks generated after the call.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Returns the game time elapsed in the world, in seconds
+    pub fn time(&self) -> f64 {
+        self.game_time
+    }
+
+    /// Sets the game time elapsed in the world
+    /// - `time`: The new game time in seconds
+    pub fn set_time(&mut self, time: f64) {
+        self.game_time = time;
+    }
+
+    /// Sets the speed of the game clock
+    /// - `scale`: Multiplier applied to elapsed time, `1.0` runs the clock in real time
+    ///
+    /// Only affects the game clock, not the `dt` passed to tiles and objects.
+    pub fn set_time_scale(&mut self, scale: f64) {
+        self.time_scale = scale;
+    }
+
+    /// Returns the multiplier applied to elapsed time before it is added to the game clock
+    pub fn time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    /// Sets the length of a full in-game day
+    /// - `seconds`: Day length in seconds of game time, must be positive
+    pub fn set_day_length(&mut self, seconds: f64) {
+        if seconds <= 0.0 {
+            log_world!(log::Level::Warn, "Ignoring invalid day length {}", seconds);
+            return;
+        }
+        self.day_length = seconds;
+    }
+
+    /// Returns the length of a full in-game day, in seconds of game time
+    pub fn day_length(&self) -> f64 {
+        self.day_length
+    }
+
+    /// Returns the position within the current day
+    ///
+    /// The value is in the range `0.0..1.0`, where `0.0` is the start of the day.
+    pub fn day_fraction(&self) -> f64 {
+        self.game_time.rem_euclid(self.day_length) / self.day_length
+    }
+
+    /// Sets the simulation rate used by `update_fixed`
+    /// - `hz`: Number of fixed steps per second, must be positive
+    pub fn set_tick_rate(&mut self, hz: f32) {
+        if hz <= 0.0 {
+            log_world!(log::Level::Warn, "Ignoring invalid tick rate {}", hz);
+            return;
         }
+        self.tick_rate = hz;
+    }
+
+    /// Returns the simulation rate used by `update_fixed`, in ticks per second
+    pub fn tick_rate(&self) -> f32 {
+        self.tick_rate
+    }
+
+    /// Returns the interpolation factor between the previous and the current fixed step.
+    ///
+    /// The value is in the range `0.0..1.0` and can be used to interpolate rendered
+    /// positions when the display refresh rate differs from the tick rate.
+    pub fn alpha(&self) -> f32 {
+        self.alpha
     }
 
     /// Adds a chunk to the world if it doesn't already exist
     /// - `chunk`: The chunk to add
-    pub fn add_chunk(&mut self, chunk: Chunk) {
+    ///
+    /// A warning is logged if the chunk holds more objects than the limit set with
+    /// `set_max_objects_per_chunk`.
+    ///
+    /// Returns `true` if the chunk was inserted, `false` if a chunk already
+    /// exists at the same coordinates or the chunk was laid out with a different
+    /// `WorldConfig`, and the new one was discarded
+    pub fn add_chunk(&mut self, chunk: Chunk) -> bool {
+        let chunk_key = (chunk.pos.x as i32, chunk.pos.y as i32);
+        if let Err(e) = self.check_chunk_config(&chunk) {
+            log_world!(log::Level::Warn, "{}", e);
+            return false;
+        }
+        if self.chunks.contains_key(&chunk_key) {
+            log_world!(log::Level::Debug, "Chunk {:?} already loaded, skipping insertion", chunk_key);
+            return false;
+        }
+        Self::check_object_capacity(self.max_objects_per_chunk, chunk_key, 0, chunk.objects.len());
+        self.chunks.insert(chunk_key, chunk);
+        self.chunk_loaded(chunk_key);
+        true
+    }
+
+    /// Adds a chunk to the world, overwriting any chunk at the same coordinates
+    /// - `chunk`: The chunk to add
+    ///
+    /// Returns the chunk that was replaced, if any, or an error message if the chunk
+    /// was laid out with a different `WorldConfig`
+    pub fn replace_chunk(&mut self, chunk: Chunk) -> Result<Option<Chunk>, String> {
+        self.check_chunk_config(&chunk)?;
         let chunk_key = (chunk.pos.x as i32, chunk.pos.y as i32);
-        if !self.chunks.contains_key(&chunk_key) {
-            self.chunks.insert(chunk_key, chunk);
+        let mut replaced = self.chunks.insert(chunk_key, chunk);
+        if let Some(old) = &mut replaced {
+            self.chunk_unloaded(old);
+        }
+        self.chunk_loaded(chunk_key);
+        Ok(replaced)
+    }
+
+    /// Checks that a chunk was laid out with the world's config
+    /// - `chunk`: The chunk to check
+    ///
+    /// Returns `Ok(())` if the configs match, or an error message otherwise
+    fn check_chunk_config(&self, chunk: &Chunk) -> Result<(), String> {
+        if chunk.config() != &self.config {
+            return Err(format!(
+                "Chunk {:?} was laid out with {:?}, but the world uses {:?}",
+                (chunk.pos.x as i32, chunk.pos.y as i32), chunk.config(), self.config
+            ));
+        }
+        Ok(())
+    }
+
+    /// Removes a chunk from the world
+    /// - `coords`: Chunk coordinates of the chunk to remove
+    ///
+    /// Returns the removed chunk, or `None` if no chunk was loaded at those coordinates
+    pub fn remove_chunk(&mut self, coords: (i32, i32)) -> Option<Chunk> {
+        self.visible_chunks.retain(|&pos| pos != coords);
+        self.last_visible.remove(&coords);
+        let mut removed = self.chunks.remove(&coords);
+        if let Some(chunk) = &mut removed {
+            self.chunk_unloaded(chunk);
+        }
+        removed
+    }
+
+    /// Limits how many chunks stay loaded
+    /// - `budget`: Maximum number of loaded chunks, or `None` for no limit
+    ///
+    /// After each visibility update, chunks that are not visible are evicted in
+    /// least-recently-visible order until the budget is met. Visible chunks are never
+    /// evicted, so the budget can be exceeded while more chunks than that are visible.
+    /// Evicted chunks are saved to the directory set with `set_save_dir` if there is
+    /// one, and dropped otherwise.
+    pub fn set_chunk_budget(&mut self, budget: Option<usize>) {
+        self.chunk_budget = budget;
+    }
+
+    /// Returns the maximum number of loaded chunks, `None` if unlimited
+    pub fn chunk_budget(&self) -> Option<usize> {
+        self.chunk_budget
+    }
+
+    /// Sets the number of objects a chunk may hold before a warning is logged
+    /// - `max`: Maximum number of objects per chunk, or `None` for no limit
+    ///
+    /// The limit is only a diagnostic: objects are still added, but a warning is
+    /// logged with the `chunk` target whenever a generated chunk, a spawn or an
+    /// object moving between chunks pushes a chunk past it.
+    pub fn set_max_objects_per_chunk(&mut self, max: Option<usize>) {
+        self.max_objects_per_chunk = max;
+    }
+
+    /// Returns the number of objects a chunk may hold before a warning is logged
+    pub fn max_objects_per_chunk(&self) -> Option<usize> {
+        self.max_objects_per_chunk
+    }
+
+    /// Sets the skin width used when separating colliding objects
+    /// - `buffer`: Skin width in world units, must not be negative
+    ///
+    /// Colliding objects may overlap by up to twice this amount before they are
+    /// pushed apart, see `resolve_collision`. Defaults to `COLLISION_BUFFER`.
+    pub fn set_collision_buffer(&mut self, buffer: f32) {
+        if buffer < 0.0 || !buffer.is_finite() {
+            log_world!(log::Level::Warn, "Ignoring invalid collision buffer {}", buffer);
+            return;
+        }
+        self.collision_buffer = buffer;
+    }
+
+    /// Returns the skin width used when separating colliding objects
+    pub fn collision_buffer(&self) -> f32 {
+        self.collision_buffer
+    }
+
+    /// Sets whether `find_path` may move diagonally between tiles
+    /// - `allowed`: `true` for eight directions, `false` for the four orthogonal ones
+    ///
+    /// Defaults to `false`.
+    pub fn set_path_diagonals(&mut self, allowed: bool) {
+        self.path_diagonals = allowed;
+    }
+
+    /// Returns whether `find_path` may move diagonally between tiles
+    pub fn path_diagonals(&self) -> bool {
+        self.path_diagonals
+    }
+
+    /// Sets the directory evicted chunks are saved to and reloaded from
+    /// - `save_dir`: Directory of the world save, laid out like `save_world_as` writes it
+    /// - `format`: Format chunk files are written and read in
+    ///
+    /// When a chunk becomes visible and is not loaded, it is read from this directory
+    /// before falling back to the generator, so evicted chunks reload identically.
+    ///
+    /// Chunks found to have no file are remembered and not looked up again until
+    /// they are evicted or the save directory changes.
+    pub fn set_save_dir(&mut self, save_dir: &str, format: SaveFormat) {
+        self.save_dir = Some((save_dir.to_string(), format));
+        self.missing_on_disk.clear();
+    }
+
+    /// Stops saving evicted chunks and reloading chunks from disk
+    pub fn clear_save_dir(&mut self) {
+        self.save_dir = None;
+        self.missing_on_disk.clear();
+    }
+
+    /// Sets whether chunk files are written gzip-compressed
+    /// - `enabled`: `true` to compress chunk files written from now on
+    ///
+    /// Compressed chunk files get an extra `.gz` extension, e.g. `chunk_0_0.json.gz`.
+    /// When a chunk is written, a copy of it in the other form is removed. Loading
+    /// recognizes compressed files by their extension, whatever this setting is.
+    /// Defaults to `false`.
+    #[cfg(feature = "gzip")]
+    pub fn set_chunk_compression(&mut self, enabled: bool) {
+        self.compress_chunks = enabled;
+    }
+
+    /// Returns whether chunk files are written gzip-compressed
+    pub fn chunk_compression(&self) -> bool {
+        self.compress_chunks
+    }
+
+    /// Sets the tile size, chunk size and activation margin of the world
+    /// - `config`: The configuration to use
+    ///
+    /// Chunks are laid out with the config they were created with, so the config
+    /// can only be changed while no chunks are loaded. Invalid configs and changes
+    /// made after chunks were loaded are ignored with a warning. Defaults to
+    /// `WorldConfig::default()`, which uses the `TILE_SIZE`, `CHUNK_SIZE` and
+    /// `OBJECT_ACTIVATION_MARGIN` constants.
+    pub fn set_config(&mut self, config: WorldConfig) {
+        if let Err(e) = config.validate() {
+            log_world!(log::Level::Warn, "Ignoring invalid world config: {}", e);
+            return;
+        }
+        if !self.chunks.is_empty() {
+            log_world!(log::Level::Warn, "Ignoring world config change, {} chunks are already loaded", self.chunks.len());
+            return;
+        }
+        self.config = config;
+    }
+
+    /// Returns the tile size, chunk size and activation margin of the world
+    pub fn config(&self) -> &WorldConfig {
+        &self.config
+    }
+
+    /// Returns a reference to the chunk at the given chunk coordinates, if loaded
+    /// - `coords`: Chunk coordinates of the chunk
+    pub fn get_chunk(&self, coords: (i32, i32)) -> Option<&Chunk> {
+        self.chunks.get(&coords)
+    }
+
+    /// Returns a mutable reference to the chunk at the given chunk coordinates, if loaded
+    /// - `coords`: Chunk coordinates of the chunk
+    pub fn get_chunk_mut(&mut self, coords: (i32, i32)) -> Option<&mut Chunk> {
+        self.chunks.get_mut(&coords)
+    }
+
+    /// Returns the chunk coordinates of the chunks found visible during the last update
+    ///
+    /// Useful for debug overlays that show which chunks are updated and drawn
+    pub fn visible_chunk_coords(&self) -> &[(i32, i32)] {
+        &self.visible_chunks
+    }
+
+    /// Returns one representative color per loaded chunk for drawing a minimap
+    /// - `region`: Chunk coordinates `(min_x, min_y, max_x, max_y)` of the area, inclusive
+    ///
+    /// Each chunk is represented by the `minimap_color` of its center tile, or gray
+    /// if that tile has none. Only one tile is looked at per chunk, so the cost
+    /// grows with the number of chunks, not tiles. Chunks that are not loaded are
+    /// left out.
+    ///
+    /// Returns the chunk coordinates and color of every loaded chunk in the region, row by row
+    pub fn minimap_colors(&self, region: (i32, i32, i32, i32)) -> Vec<((i32, i32), Color)> {
+        let (min_x, min_y, max_x, max_y) = region;
+        let size = self.config.chunk_size;
+        let center = (size / 2) * size + size / 2;
+        let mut colors = Vec::new();
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let Some(chunk) = self.chunks.get(&(x, y)) else {
+                    continue;
+                };
+                let color = chunk.tiles
+                    .get(center)
+                    .and_then(|tile| tile.minimap_color())
+                    .unwrap_or(GRAY);
+                colors.push(((x, y), color));
+            }
+        }
+        colors
+    }
+
+    /// Adds an object to the chunk that contains its position
+    /// - `obj`: The object to spawn
+    ///
+    /// Objects that store an id (see `Object::get_id`) and do not have one yet
+    /// are assigned the next free `ObjectId`.
+    ///
+    /// Returns the object's id, `None` if it does not store one, or an error message
+    /// if the owning chunk is not loaded
+    pub fn spawn_object(&mut self, mut obj: Box<dyn Object>) -> Result<Option<ObjectId>, String> {
+        let chunk_pos = self.get_chunk_coords(obj.get_pos());
+        let chunk = self.chunks.get_mut(&chunk_pos)
+            .ok_or_else(|| format!("Cannot spawn '{}': chunk {:?} is not loaded", obj.get_type_tag(), chunk_pos))?;
+        Self::register_object(&mut self.next_object_id, &mut self.object_index, obj.as_mut(), (chunk_pos, chunk.objects.len()));
+        let id = obj.get_id();
+        chunk.objects.push(obj);
+        chunk.mark_dirty();
+        Self::check_object_capacity(self.max_objects_per_chunk, chunk_pos, chunk.objects.len() - 1, chunk.objects.len());
+        Ok(id)
+    }
+
+    /// Removes all objects in visible chunks that match a predicate
+    /// - `pred`: Returns `true` for objects that should be removed
+    ///
+    /// Returns the number of removed objects
+    pub fn despawn_objects_where(&mut self, pred: impl Fn(&dyn Object) -> bool) -> usize {
+        let mut removed = 0;
+        for chunk_pos in self.visible_chunks.clone() {
+            if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
+                let despawned = chunk.retain_objects(|obj| !pred(obj));
+                if despawned.is_empty() {
+                    continue;
+                }
+                removed += despawned.len();
+                self.forget_objects(&despawned);
+                self.register_chunk_objects(chunk_pos);
+            }
         }
+        removed
+    }
+
+    /// Marks every loaded chunk as changed so the next save writes all of them
+    pub fn mark_all_dirty(&mut self) {
+        for chunk in self.chunks.values_mut() {
+            chunk.mark_dirty();
+        }
+    }
+
+    /// Records the camera position and zoom to store with the next save
+    /// - `center`: Camera position in world coordinates
+    /// - `zoom`: Camera zoom, e.g. `Viewport::zoom`
+    ///
+    /// Call this before `save_world` so that reloading resumes at the same view.
+    pub fn set_camera(&mut self, center: Vec2, zoom: f32) {
+        self.camera = Some((center, zoom));
+    }
+
+    /// Returns the camera position and zoom recorded with `set_camera` or loaded from a save
+    ///
+    /// Returns `None` for new worlds and for saves written without a camera
+    pub fn saved_camera(&self) -> Option<(Vec2, f32)> {
+        self.camera
+    }
+
+    /// Saves the world to the specified directory in JSON format
+    /// - `save_dir`: Directory to save the world data to
+    ///
+    /// Only chunks that changed since the last save are written, see `Chunk::is_dirty`.
+    /// Call `mark_all_dirty` first to force a full save, e.g. into a new directory.
+    ///
+    /// Returns `Ok(())` on success, or an error message on failure
+    pub fn save_world(&mut self, save_dir: &str) -> Result<(), String> {
+        self.save_world_as(save_dir, SaveFormat::Json)
     }
 
-    /// Saves the world to the specified directory
+    /// Saves the world to the specified directory in the given format
     /// - `save_dir`: Directory to save the world data to
+    /// - `format`: Format of the written files
+    ///
+    /// Only chunks that changed since the last save are written, see `Chunk::is_dirty`.
+    /// All data is serialized before anything is written, so a tile or object that
+    /// fails to serialize leaves the save directory as it was. Every file is written
+    /// to a temporary file first and then renamed into place, so an interrupted save
+    /// never leaves truncated files behind.
+    ///
     /// Returns `Ok(())` on success, or an error message on failure
-    pub fn save_world(&self, save_dir: &str) -> Result<(), String> {
+    pub fn save_world_as(&mut self, save_dir: &str, format: SaveFormat) -> Result<(), String> {
+        let world_data = WorldData {
+            version: SAVE_VERSION,
+            name: self.world_name.clone(),
+            seed: self.seed,
+            game_time: self.game_time,
+            next_object_id: self.next_object_id,
+            camera: self.camera.map(|(center, _)| Vec2Save::from(center)),
+            camera_zoom: self.camera.map_or(1.0, |(_, zoom)| zoom),
+            config: self.config,
+        };
+        let serialized_world = match format {
+            SaveFormat::Json => serde_json::to_vec(&world_data).map_err(|e| e.to_string())?,
+            SaveFormat::Bincode => world_data_to_bytes(&world_data)?,
+        };
+
+        let serialized_chunks: Vec<((i32, i32), Vec<u8>)> = self.chunks
+            .iter()
+            .filter(|(_, chunk)| chunk.is_dirty())
+            .map(|(&chunk_pos, chunk)| Ok((chunk_pos, Self::serialize_chunk(chunk, format, self.compress_chunks)?)))
+            .collect::<Result<_, String>>()?;
+
         let chunks_dir = format!("{}/chunks", save_dir);
         fs::create_dir_all(&chunks_dir).map_err(|e| e.to_string())?;
+        write_atomic(&format!("{}/world.{}", save_dir, format.extension()), &serialized_world)?;
+
+        for (chunk_pos, serialized) in serialized_chunks {
+            Self::write_chunk_file(save_dir, chunk_pos, format, self.compress_chunks, &serialized)?;
+            self.missing_on_disk.remove(&chunk_pos);
+            if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
+                chunk.clear_dirty();
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the path of a chunk file inside a save directory
+    /// - `save_dir`: Directory of the world save
+    /// - `chunk_pos`: Chunk coordinates of the chunk
+    /// - `format`: Format of the chunk file
+    /// - `compressed`: Whether the chunk file is compressed
+    fn chunk_path(save_dir: &str, chunk_pos: (i32, i32), format: SaveFormat, compressed: bool) -> String {
+        let path = format!("{}/chunks/chunk_{}_{}.{}", save_dir, chunk_pos.0, chunk_pos.1, format.extension());
+        if compressed {
+            format!("{}.{}", path, COMPRESSED_EXTENSION)
+        } else {
+            path
+        }
+    }
 
-        let world_data = WorldData { name: self.world_name.clone() };
-        let serialized = serde_json::to_string(&world_data).map_err(|e| e.to_string())?;
-        fs::write(format!("{}/world.json", save_dir), serialized).map_err(|e| e.to_string())?;
+    /// Returns the path of an existing file of a chunk inside a save directory
+    /// - `save_dir`: Directory of the world save
+    /// - `chunk_pos`: Chunk coordinates of the chunk
+    /// - `format`: Format of the chunk file
+    ///
+    /// Returns the compressed or uncompressed file, or `None` if neither exists
+    fn existing_chunk_path(save_dir: &str, chunk_pos: (i32, i32), format: SaveFormat) -> Option<String> {
+        [true, false]
+            .into_iter()
+            .map(|compressed| Self::chunk_path(save_dir, chunk_pos, format, compressed))
+            .find(|path| fs::metadata(path).is_ok_and(|meta| meta.is_file()))
+    }
 
-        for (&(x, y), chunk) in &self.chunks {
-            let chunk_path = format!("{}/chunk_{}_{}.json", chunks_dir, x, y);
-            fs::write(chunk_path, chunk.serialize()).map_err(|e| e.to_string())?;
+    /// Writes a serialized chunk to its file and removes its file in the other form
+    /// - `save_dir`: Directory of the world save
+    /// - `chunk_pos`: Chunk coordinates of the chunk
+    /// - `format`: Format of the chunk file
+    /// - `compressed`: Whether `data` is compressed
+    /// - `data`: Output of `serialize_chunk`
+    ///
+    /// Returns `Ok(())` on success, or an error message on failure
+    fn write_chunk_file(save_dir: &str, chunk_pos: (i32, i32), format: SaveFormat, compressed: bool, data: &[u8]) -> Result<(), String> {
+        write_atomic(&Self::chunk_path(save_dir, chunk_pos, format, compressed), data)?;
+        let stale = Self::chunk_path(save_dir, chunk_pos, format, !compressed);
+        if fs::metadata(&stale).is_ok() {
+            fs::remove_file(&stale).map_err(|e| format!("Failed to remove {}: {}", stale, e))?;
         }
         Ok(())
     }
 
-    /// Loads a world from the specified directory
+    /// Serializes a chunk in the given format
+    /// - `chunk`: The chunk to serialize
+    /// - `format`: Format to serialize in
+    /// - `compressed`: Whether to compress the serialized chunk
+    ///
+    /// Returns the contents of the chunk file or an error message on failure
+    fn serialize_chunk(chunk: &Chunk, format: SaveFormat, compressed: bool) -> Result<Vec<u8>, String> {
+        let data = match format {
+            SaveFormat::Json => chunk.serialize()?.into_bytes(),
+            SaveFormat::Bincode => chunk.serialize_bytes()?,
+        };
+        #[cfg(feature = "gzip")]
+        if compressed {
+            return crate::core::save::compress(&data);
+        }
+        #[cfg(not(feature = "gzip"))]
+        let _ = compressed;
+        Ok(data)
+    }
+
+    /// Reads a chunk from a chunk file
+    /// - `path`: Path of the chunk file
+    /// - `format`: Format of the chunk file
+    ///
+    /// Files ending in `.gz` are decompressed first.
+    ///
+    /// Returns the chunk or an error message on failure
+    fn read_chunk(&self, path: &str, format: SaveFormat) -> Result<Chunk, String> {
+        let mut chunk_data = fs::read(path).map_err(|e| e.to_string())?;
+        if path.ends_with(&format!(".{}", COMPRESSED_EXTENSION)) {
+            chunk_data = decompress(&chunk_data)?;
+        }
+        match format {
+            SaveFormat::Json => String::from_utf8(chunk_data)
+                .map_err(|e| e.to_string())
+                .and_then(|data| Chunk::deserialize_with_config(&data, self.config, &self.tile_registry, &self.object_registry)),
+            SaveFormat::Bincode => Chunk::deserialize_bytes_with_config(&chunk_data, self.config, &self.tile_registry, &self.object_registry),
+        }
+    }
+
+    /// Loads a world saved in JSON format from the specified directory
     /// - `save_dir`: Directory containing the world data
     /// - `tile_registry`: Registry of available tile types
     /// - `object_registry`: Registry of available object types
     /// - `biome_registry`: Registry of available biome types
+    ///
     /// Returns a new World instance or an error message on failure
     pub fn load_world(save_dir: &str, tile_registry: TileRegistry, object_registry: ObjectRegistry, biome_registry: BiomeRegistry) -> Result<Self, String> {
-        let world_data_path = format!("{}/world.json", save_dir);
-        let data = fs::read_to_string(world_data_path).map_err(|e| e.to_string())?;
-        let world_data: WorldData = serde_json::from_str(&data).map_err(|e| e.to_string())?;
+        Self::load_world_as(save_dir, SaveFormat::Json, tile_registry, object_registry, biome_registry)
+    }
+
+    /// Loads a world saved in the given format from the specified directory
+    /// - `save_dir`: Directory containing the world data
+    /// - `format`: Format the world was saved in
+    /// - `tile_registry`: Registry of available tile types
+    /// - `object_registry`: Registry of available object types
+    /// - `biome_registry`: Registry of available biome types
+    ///
+    /// Chunk files with an extension that does not match the format, and other files
+    /// not named like `chunk_<x>_<y>`, are skipped. Compressed chunk files, e.g.
+    /// `chunk_0_0.json.gz`, are decompressed. A chunk whose stored position does not
+    /// match its file name is loaded at the stored position with a warning, unless a
+    /// correctly named file already provides that chunk. Chunk files that fail to
+    /// load are skipped with a warning. Saves written by older versions of the crate
+    /// are upgraded with `save::migrate`.
+    ///
+    /// Returns a new World instance or an error message on failure
+    pub fn load_world_as(save_dir: &str, format: SaveFormat, tile_registry: TileRegistry, object_registry: ObjectRegistry, biome_registry: BiomeRegistry) -> Result<Self, String> {
+        let mut world = Self::load_world_data(save_dir, format, tile_registry, object_registry, biome_registry)?;
+        world.load_chunk_files(save_dir, format, |_| true);
+        Ok(world)
+    }
+
+    /// Loads a world from the specified directory, reading only the chunks near a position
+    /// - `save_dir`: Directory containing the world data
+    /// - `format`: Format the world was saved in
+    /// - `tile_registry`: Registry of available tile types
+    /// - `object_registry`: Registry of available object types
+    /// - `biome_registry`: Registry of available biome types
+    /// - `center`: Position in world coordinates to load around, e.g. the spawn point
+    /// - `radius`: Number of chunks to load in every direction from the chunk containing `center`
+    ///
+    /// Only chunk files named for chunks within `radius` are read, so large saves load
+    /// quickly. The directory becomes the world's save directory, see `set_save_dir`,
+    /// so the remaining chunks are read from disk as they become visible and are only
+    /// generated if no file exists. Otherwise behaves like `load_world_as`.
+    ///
+    /// Returns a new World instance or an error message on failure
+    pub fn load_world_streaming(
+        save_dir: &str,
+        format: SaveFormat,
+        tile_registry: TileRegistry,
+        object_registry: ObjectRegistry,
+        biome_registry: BiomeRegistry,
+        center: Vec2,
+        radius: i32,
+    ) -> Result<Self, String> {
+        let mut world = Self::load_world_data(save_dir, format, tile_registry, object_registry, biome_registry)?;
+        let (center_x, center_y) = world.get_chunk_coords(center);
+        world.load_chunk_files(save_dir, format, |(x, y)| {
+            (x - center_x).abs() <= radius && (y - center_y).abs() <= radius
+        });
+        world.set_save_dir(save_dir, format);
+        Ok(world)
+    }
+
+    /// Reads the world data of a save and creates a world from it, without any chunks
+    /// - `save_dir`: Directory containing the world data
+    /// - `format`: Format the world was saved in
+    /// - `tile_registry`: Registry of available tile types
+    /// - `object_registry`: Registry of available object types
+    /// - `biome_registry`: Registry of available biome types
+    ///
+    /// Returns the new world or an error message on failure
+    fn load_world_data(save_dir: &str, format: SaveFormat, tile_registry: TileRegistry, object_registry: ObjectRegistry, biome_registry: BiomeRegistry) -> Result<Self, String> {
+        let world_data_path = format!("{}/world.{}", save_dir, format.extension());
+        let data = fs::read(world_data_path).map_err(|e| e.to_string())?;
+        let world_data: WorldData = match format {
+            SaveFormat::Json => serde_json::from_slice(&data).map_err(|e| e.to_string())?,
+            SaveFormat::Bincode => world_data_from_bytes(&data)?,
+        };
+        let world_data = migrate(world_data.version, world_data)?;
+        world_data.config.validate()?;
 
-        let mut world = Self::new(&world_data.name, tile_registry, object_registry, biome_registry);
+        let mut world = Self::with_seed(&world_data.name, world_data.seed, tile_registry, object_registry, biome_registry);
+        world.game_time = world_data.game_time;
+        world.next_object_id = world_data.next_object_id.max(1);
+        world.camera = world_data.camera.map(|center| (Vec2::from(center), world_data.camera_zoom));
+        world.config = world_data.config;
+        Ok(world)
+    }
 
+    /// Reads the chunk files of a save and adds the chunks to the world
+    /// - `save_dir`: Directory containing the world data
+    /// - `format`: Format the world was saved in
+    /// - `include`: Decides by chunk coordinates whether a chunk is loaded
+    ///
+    /// Files that are not chunk files or fail to load are skipped, see `load_world_as`.
+    /// A file is only read if `include` accepts the coordinates in its name, and a
+    /// misnamed file's chunk is only added if `include` accepts its stored position too.
+    fn load_chunk_files(&mut self, save_dir: &str, format: SaveFormat, include: impl Fn((i32, i32)) -> bool) {
         let chunks_dir = format!("{}/chunks", save_dir);
+        let mut misnamed = Vec::new();
         if let Ok(entries) = fs::read_dir(chunks_dir) {
             for entry in entries.flatten() {
-                if let Ok(chunk_data) = fs::read_to_string(entry.path()) {
-                    if let Ok(chunk) = Chunk::deserialize(&chunk_data, &world.tile_registry, &world.object_registry) {
-                        world.add_chunk(chunk);
+                let path = entry.path();
+                let Some(name_pos) = path.file_name().and_then(|name| Self::parse_chunk_file_name(&name.to_string_lossy(), format)) else {
+                    log_world!(log::Level::Debug, "Skipping non-chunk file {}", path.display());
+                    continue;
+                };
+                if !include(name_pos) {
+                    continue;
+                }
+                let chunk = match self.read_chunk(&path.to_string_lossy(), format) {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        log_world!(log::Level::Warn, "Failed to load chunk file {}: {}", path.display(), e);
+                        continue;
                     }
+                };
+                let stored_pos = (chunk.pos.x as i32, chunk.pos.y as i32);
+                if stored_pos == name_pos {
+                    self.add_chunk(chunk);
+                } else if !include(stored_pos) {
+                    log_world!(log::Level::Warn, "Chunk file {} holds chunk {:?}, which is not loaded", path.display(), stored_pos);
+                } else {
+                    log_world!(log::Level::Warn, "Chunk file {} holds chunk {:?}, using the stored position", path.display(), stored_pos);
+                    misnamed.push(chunk);
                 }
             }
         }
-        Ok(world)
+
+        for chunk in misnamed {
+            let chunk_pos = chunk.pos;
+            if !self.add_chunk(chunk) {
+                log_world!(log::Level::Warn, "Ignoring misnamed file of chunk {:?}, the chunk was already loaded", chunk_pos);
+            }
+        }
+    }
+
+    /// Parses the chunk coordinates from the name of a chunk file
+    /// - `file_name`: Name of the file without its directory, e.g. `chunk_-1_2.json.gz`
+    /// - `format`: Format chunk files are expected in
+    ///
+    /// Returns the coordinates, or `None` if the name is not the name of a chunk file
+    /// in the given format
+    fn parse_chunk_file_name(file_name: &str, format: SaveFormat) -> Option<(i32, i32)> {
+        let file_name = file_name
+            .strip_suffix(COMPRESSED_EXTENSION)
+            .and_then(|name| name.strip_suffix('.'))
+            .unwrap_or(file_name);
+        let coords = file_name
+            .strip_prefix("chunk_")?
+            .strip_suffix(format.extension())?
+            .strip_suffix('.')?;
+        let (x, y) = coords.split_once('_')?;
+        Some((x.parse().ok()?, y.parse().ok()?))
     }
 
     /// Updates the world state
@@ -113,65 +943,250 @@ impl World {
     /// - `screen_size`: Size of the game window
     /// 
     /// This method handles:
+    /// - Advancing the game clock
     /// - Updating visible chunks based on camera position
     /// - Moving objects between chunks as needed
     /// - Checking and resolving object collisions
+    /// - Stopping objects from moving into impassable tiles
     /// - Updating all active chunks and their contents
+    /// - Delivering events posted with `post_event`
+    ///
+    /// While paused, only the visible chunks are refreshed, unless `step` requested a tick.
+    /// Equivalent to `update_with_viewport` with an unzoomed `Viewport`.
     pub fn update(&mut self, camera_pos: Vec2, screen_size: Vec2) {
-        let current_chunk_coords = self.get_chunk_coords(camera_pos);
-        self.update_visible_chunks(current_chunk_coords);
+        self.update_with_viewport(Viewport::new(camera_pos, screen_size));
+    }
 
-        let mut movements = Vec::new();
-        for &chunk_pos in &self.visible_chunks {
+    /// Updates the world state for an explicit viewport
+    /// - `viewport`: Area of the world shown on screen
+    ///
+    /// Works like `update`, but chunk visibility and tile culling follow the world
+    /// area the viewport covers, so zoomed views update exactly what they show.
+    pub fn update_with_viewport(&mut self, viewport: Viewport) {
+        if self.paused {
+            self.update_paused(viewport);
+            return;
+        }
+        self.advance(viewport, get_frame_time());
+    }
+
+    /// Updates the world state using a fixed timestep
+    /// - `camera_pos`: Current camera position in world coordinates
+    /// - `screen_size`: Size of the game window
+    ///
+    /// Frame time is accumulated and consumed in steps of `1 / tick_rate` seconds,
+    /// so the simulation speed does not depend on the display refresh rate.
+    /// After stepping, `alpha` holds the fraction of a step left in the accumulator.
+    ///
+    /// While paused, no frame time is accumulated and only the visible chunks are
+    /// refreshed, unless `step` requested a tick.
+    pub fn update_fixed(&mut self, camera_pos: Vec2, screen_size: Vec2) {
+        self.update_fixed_with_viewport(Viewport::new(camera_pos, screen_size));
+    }
+
+    /// Updates the world state using a fixed timestep for an explicit viewport
+    /// - `viewport`: Area of the world shown on screen
+    ///
+    /// Works like `update_fixed`, with visibility following the viewport as in
+    /// `update_with_viewport`.
+    pub fn update_fixed_with_viewport(&mut self, viewport: Viewport) {
+        if self.paused {
+            self.update_paused(viewport);
+            return;
+        }
+
+        let step = 1.0 / self.tick_rate;
+        self.accumulator = (self.accumulator + get_frame_time()).min(step * MAX_FIXED_STEPS as f32);
+
+        while self.accumulator >= step {
+            self.advance(viewport, step);
+            self.accumulator -= step;
+        }
+        self.alpha = self.accumulator / step;
+    }
+
+    /// Pauses or resumes the simulation
+    /// - `paused`: `true` to freeze the simulation, `false` to resume it
+    ///
+    /// While paused, `update` and `update_fixed` still load and generate visible
+    /// chunks, but the game clock stands still and no objects or tiles tick or
+    /// collide. `draw` is unaffected, so the frozen frame keeps rendering.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        if !paused {
+            self.pending_step = false;
+        }
+    }
+
+    /// Returns whether the simulation is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Advances a paused world by exactly one tick
+    ///
+    /// The tick runs during the next call to `update` or `update_fixed` and lasts
+    /// `1 / tick_rate` seconds. Has no effect while the world is not paused.
+    pub fn step(&mut self) {
+        if self.paused {
+            self.pending_step = true;
+        }
+    }
+
+    /// Updates a paused world, running a single tick if one was requested
+    /// - `viewport`: Area of the world shown on screen
+    fn update_paused(&mut self, viewport: Viewport) {
+        if self.pending_step {
+            self.pending_step = false;
+            self.advance(viewport, 1.0 / self.tick_rate);
+        } else {
+            self.update_visible_chunks(viewport);
+        }
+    }
+
+    /// Advances the world state by a single step
+    /// - `viewport`: Area of the world shown on screen
+    /// - `dt`: Time to advance the simulation by in seconds
+    fn advance(&mut self, viewport: Viewport, dt: f32) {
+        self.game_time += dt as f64 * self.time_scale;
+        let area = viewport.bounds();
+
+        self.update_visible_chunks(viewport);
+
+        self.migrate_objects();
+
+        let collision_start = self.start_timing();
+        self.check_obj_collisions();
+        self.check_tile_collisions();
+        self.stats.collision_time = Self::elapsed(collision_start);
+
+        let tick_start = self.start_timing();
+        let mut active_objects = 0;
+        let mut visible_tiles = 0;
+        let visible_chunks_copy = self.visible_chunks.clone();
+        for chunk_pos in visible_chunks_copy {
+            self.tick_chunk(chunk_pos, area, dt);
             if let Some(chunk) = self.chunks.get(&chunk_pos) {
-                for (obj_index, obj) in chunk.objects.iter().enumerate() {
-                    let new_chunk_pos = self.get_chunk_coords(obj.get_pos());
-                    if new_chunk_pos != chunk_pos {
-                        movements.push((chunk_pos, new_chunk_pos, obj_index));
-                    }
-                }
+                active_objects += chunk.active_object_count();
+                visible_tiles += chunk.visible_tile_count();
             }
         }
+        self.stats.tick_time = Self::elapsed(tick_start);
+        self.stats.active_objects = active_objects;
+        self.stats.visible_tiles = visible_tiles;
+
+        self.dispatch_events();
+    }
+    /// Ticks the objects and tiles of a chunk that lie in a world-space area
+    /// - `chunk_pos`: Chunk coordinates of the chunk
+    /// - `area`: Visible area in world coordinates
+    /// - `dt`: Time to advance the simulation by in seconds
+    ///
+    /// Each object is taken out of the chunk only for its own tick, so it can find
+    /// the other objects of its chunk, e.g. with `get_object`. Tiles are ticked while
+    /// the chunk is taken out of the world, except tiles whose `reads_neighbors`
+    /// returns `true`: those are swapped for a copy of themselves during their tick,
+    /// so they can look at and change the tiles next to them, e.g. with
+    /// `tile_neighbors` and `tile_at_mut`. The chunk is reindexed afterwards in case
+    /// ticks spawned or despawned objects.
+    fn tick_chunk(&mut self, chunk_pos: (i32, i32), area: Rect, dt: f32) {
+        let Some(chunk) = self.chunks.get_mut(&chunk_pos) else {
+            return;
+        };
+        if !chunk.prepare_update(area) {
+            return;
+        }
+        chunk.queue_object_ticks();
 
-        movements.sort_by(|a, b| {
-            if a.0 == b.0 {
-                b.2.cmp(&a.2)
-            } else {
-                std::cmp::Ordering::Equal
+        while let Some(mut obj) = self.chunks.get_mut(&chunk_pos).and_then(Chunk::take_next_object) {
+            let changed = obj.tick(dt, self);
+            match self.chunks.get_mut(&chunk_pos) {
+                Some(chunk) => match chunk.restore_object(obj) {
+                    Ok(()) if changed => chunk.mark_changed(),
+                    Ok(()) => {}
+                    Err(despawned) => self.forget_objects([&despawned]),
+                },
+                None => log_world!(log::Level::Warn, "Chunk {:?} was unloaded while '{}' was ticking", chunk_pos, obj.get_type_tag()),
             }
-        });
+        }
 
-        for (old_pos, new_pos, obj_index) in movements {
-            if let Some(mut chunk) = self.chunks.remove(&old_pos) {
-                if obj_index < chunk.objects.len() {
-                    let obj = chunk.objects.remove(obj_index);
-                    self.chunks.insert(old_pos, chunk);
-                    if let Some(new_chunk) = self.chunks.get_mut(&new_pos) {
-                        new_chunk.objects.push(obj);
-                    }
-                } else {
-                    self.chunks.insert(old_pos, chunk);
+        let slots = match self.chunks.remove(&chunk_pos) {
+            Some(mut chunk) => {
+                let slots = chunk.tick_own_tiles(self, dt);
+                self.chunks.insert(chunk_pos, chunk);
+                slots
+            }
+            None => Vec::new(),
+        };
+        for slot in slots {
+            let Some(mut tile) = self.chunks.get_mut(&chunk_pos).and_then(|chunk| chunk.take_tile(slot)) else {
+                continue;
+            };
+            let changed = tile.tick(dt, self);
+            if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
+                chunk.restore_tile(slot, tile);
+                if changed {
+                    chunk.mark_changed();
                 }
             }
         }
+        self.register_chunk_objects(chunk_pos);
+    }
 
-        self.check_obj_collisions();
+    /// Moves objects whose position left their chunk into the chunk that now contains them
+    ///
+    /// Objects are first taken out of every visible chunk they no longer belong to,
+    /// then inserted into their destination chunks. Each chunk is scanned from the
+    /// back and only leaving objects are removed with `swap_remove`, so chunks where
+    /// nothing moved are left untouched. Objects whose destination chunk is not loaded stay where they are.
+    /// Every chunk that lost or gained objects is reindexed for `get_object`.
+    fn migrate_objects(&mut self) {
+        let chunk_pixels = self.config.chunk_pixels();
+        let mut movements = Vec::new();
+        let mut touched = HashSet::new();
+        for &chunk_pos in &self.visible_chunks {
+            if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
+                let moved = movements.len();
+                for index in (0..chunk.objects.len()).rev() {
+                    if Self::chunk_coords_in(chunk.objects[index].get_pos(), chunk_pixels) != chunk_pos {
+                        movements.push((chunk_pos, chunk.objects.swap_remove(index)));
+                    }
+                }
+                if movements.len() > moved {
+                    movements[moved..].reverse();
+                    chunk.mark_dirty();
+                    touched.insert(chunk_pos);
+                }
+            }
+        }
 
-        let visible_chunks_copy = self.visible_chunks.clone();
-        for chunk_pos in visible_chunks_copy {
-            if let Some(mut chunk) = self.chunks.remove(&chunk_pos) {
-                chunk.update(self, camera_pos, screen_size, get_frame_time());
-                self.chunks.insert(chunk_pos, chunk);
+        for (old_pos, obj) in movements {
+            let new_pos = self.get_chunk_coords(obj.get_pos());
+            let target = if self.chunks.contains_key(&new_pos) { new_pos } else { old_pos };
+            if let Some(chunk) = self.chunks.get_mut(&target) {
+                chunk.objects.push(obj);
+                chunk.mark_dirty();
+                touched.insert(target);
+                Self::check_object_capacity(self.max_objects_per_chunk, target, chunk.objects.len() - 1, chunk.objects.len());
             }
         }
+
+        for chunk_pos in touched {
+            self.register_chunk_objects(chunk_pos);
+        }
     }
+
     /// Checks for and handles collisions between all active objects
     /// 
     /// This method:
     /// 1. Collects all active objects from visible chunks
-    /// 2. Checks for collisions between each pair of objects
+    /// 2. Checks for collisions between each pair of objects sharing a broad-phase grid cell
     /// 3. Calls the collision handlers for colliding objects
     /// 4. Returns objects to their respective chunks after processing
+    ///
+    /// Collision handlers may move any object of a pair, including objects that
+    /// are not active, so chunks where an object moved have their object grid
+    /// rebuilt on the next update.
     fn check_obj_collisions(&mut self) {
         let mut objects: Vec<Box<dyn Object>> = Vec::new();
         let mut chunk_positions = Vec::new();
@@ -185,85 +1200,510 @@ impl World {
             }
         }
 
-        for i in 0..objects.len() {
-            for j in (i + 1)..objects.len() {
-                let (obj1, obj2) = objects.split_at_mut(j);
-                let obj1 = &mut obj1[i];
-                let obj2 = &mut obj2[0];
-
-                let pos1 = obj1.get_pos();
-                let velocity1 = obj1.get_velocity();
-                let size1 = obj1.get_size();
-                let next_pos1 = pos1 + velocity1;
+        let positions: Vec<Vec2> = objects.iter().map(|obj| obj.get_pos()).collect();
+        let candidates = Self::collision_candidates(&objects, self.config.collision_cell_size());
+        self.stats.collision_pairs = candidates.len();
+        let mut changed = HashSet::new();
+        for (i, j) in candidates {
+            let (obj1, obj2) = objects.split_at_mut(j);
+            let obj1 = &mut obj1[i];
+            let obj2 = &mut obj2[0];
 
-                let pos2 = obj2.get_pos();
-                let velocity2 = obj2.get_velocity();
-                let size2 = obj2.get_size();
-                let next_pos2 = pos2 + velocity2;
+            let obj1: &mut dyn Object = &mut **obj1;
+            let obj2: &mut dyn Object = &mut **obj2;
 
-                let will_collide = next_pos1.x < next_pos2.x + size2.x &&
-                                 next_pos1.x + size1.x > next_pos2.x &&
-                                 next_pos1.y < next_pos2.y + size2.y &&
-                                 next_pos1.y + size1.y > next_pos2.y;
-
-                let moving_towards_each_other = {
-                    let relative_velocity = velocity1 - velocity2;
-                    let direction = pos2 - pos1;
-                    relative_velocity.dot(direction) > 0.0
-                };
-
-                if will_collide && moving_towards_each_other {
-                    let obj1: &mut dyn Object = &mut **obj1;
-                    let obj2: &mut dyn Object = &mut **obj2;
-                    
-                    obj1.collision(obj2);
-                    obj2.collision(obj1);
-                }
+            if will_collide(obj1, obj2, self.collision_buffer) {
+                resolve_collision(obj1, obj2, self.collision_buffer);
+                obj1.collision(obj2);
+                obj2.collision(obj1);
+                changed.extend([chunk_positions[i], chunk_positions[j]]);
             }
         }
 
-        for (obj, &chunk_pos) in objects.into_iter().zip(chunk_positions.iter()) {
+        let mut moved = HashSet::new();
+        for ((obj, &chunk_pos), &pos) in objects.into_iter().zip(chunk_positions.iter()).zip(positions.iter()) {
+            if obj.get_pos() != pos {
+                moved.insert(chunk_pos);
+            }
             if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
                 chunk.objects.push(obj);
             }
         }
-    }
-
-    /// Draws all visible world elements
-    /// - `camera_pos`: Current camera position in world coordinates
-    /// - `screen_size`: Size of the game window
-    pub fn draw(&mut self, camera_pos: Vec2, screen_size: Vec2) {
-        self.draw_batch.clear();
-        for &chunk_pos in &self.visible_chunks {
+        for chunk_pos in changed {
             if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
-                chunk.draw_tiles(camera_pos, screen_size, &mut self.draw_batch);
+                if moved.contains(&chunk_pos) {
+                    chunk.mark_dirty();
+                } else {
+                    chunk.mark_changed();
+                }
             }
         }
-        self.draw_batch.draw();
+    }
+
+    /// Stops objects from moving into tiles they may not pass
+    ///
+    /// For every object in a visible chunk, the area it would occupy after applying
+    /// its velocity is tested against the tiles it overlaps, one axis at a time.
+    /// If the move along an axis would enter a tile whose `may_pass` returns `false`,
+    /// the velocity on that axis is zeroed. Objects already overlapping a solid tile
+    /// are left alone so they can move out of it.
+    fn check_tile_collisions(&mut self) {
+        let mut blocked = Vec::new();
 
-        self.draw_batch.clear();
         for &chunk_pos in &self.visible_chunks {
+            if let Some(chunk) = self.chunks.get(&chunk_pos) {
+                for (index, obj) in chunk.objects.iter().enumerate() {
+                    let velocity = obj.get_velocity();
+                    if velocity == Vec2::ZERO {
+                        continue;
+                    }
+
+                    let pos = obj.get_pos();
+                    let size = obj.get_size();
+                    if self.is_area_blocked(pos, size) {
+                        continue;
+                    }
+
+                    let mut new_velocity = velocity;
+                    if new_velocity.x != 0.0 && self.is_area_blocked(pos + vec2(new_velocity.x, 0.0), size) {
+                        new_velocity.x = 0.0;
+                    }
+                    if new_velocity.y != 0.0 && self.is_area_blocked(pos + new_velocity, size) {
+                        new_velocity.y = 0.0;
+                    }
+
+                    if new_velocity != velocity {
+                        blocked.push((chunk_pos, index, new_velocity));
+                    }
+                }
+            }
+        }
+
+        for (chunk_pos, index, velocity) in blocked {
             if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
-                chunk.draw_objects(&mut self.draw_batch);
+                if let Some(obj) = chunk.objects.get_mut(index) {
+                    obj.set_velocity(velocity);
+                    chunk.mark_changed();
+                }
+            }
+        }
+    }
+
+    /// Checks whether the tile at a world position may not be passed
+    /// - `world_pos`: Position in world coordinates
+    ///
+    /// Returns `true` if a tile is loaded there and its `may_pass` returns `false`
+    pub fn is_solid_at(&self, world_pos: Vec2) -> bool {
+        self.tile_at(world_pos).is_some_and(|tile| !tile.may_pass())
+    }
+
+    /// Checks whether an area overlaps any loaded tile that may not be passed
+    /// - `pos`: Top-left corner of the area in world coordinates
+    /// - `size`: Size of the area in world units
+    ///
+    /// Returns `true` if any tile covered by the area returns `false` from `may_pass`
+    pub fn is_area_blocked(&self, pos: Vec2, size: Vec2) -> bool {
+        let tile_size = self.config.tile_size;
+        let start_x = (pos.x / tile_size).floor() as i32;
+        let end_x = ((pos.x + size.x) / tile_size).ceil() as i32;
+        let start_y = (pos.y / tile_size).floor() as i32;
+        let end_y = ((pos.y + size.y) / tile_size).ceil() as i32;
+
+        for y in start_y..end_y {
+            for x in start_x..end_x {
+                if self.is_solid_at(vec2(x as f32, y as f32) * tile_size) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Finds the pairs of objects that may collide during this frame
+    /// - `objects`: All objects taking part in collision checks
+    /// - `cell_size`: Size of a grid cell, see `WorldConfig::collision_cell_size`
+    ///
+    /// Every object is bucketed into a uniform grid of `cell_size` cells
+    /// covering the area it sweeps from its current to its next position. Only
+    /// objects sharing at least one cell are returned as candidates, which avoids
+    /// testing every pair of objects against each other.
+    ///
+    /// Returns the candidate pairs as `(i, j)` indices with `i < j`, sorted in the
+    /// same order a naive pairwise loop would visit them
+    fn collision_candidates(objects: &[Box<dyn Object>], cell_size: f32) -> Vec<(usize, usize)> {
+        let mut grid: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+
+        for (index, obj) in objects.iter().enumerate() {
+            let pos = obj.get_pos();
+            let next_pos = pos + obj.get_velocity();
+            let min = pos.min(next_pos);
+            let max = pos.max(next_pos) + obj.get_size();
+
+            let start_x = (min.x / cell_size).floor() as i32;
+            let end_x = (max.x / cell_size).floor() as i32;
+            let start_y = (min.y / cell_size).floor() as i32;
+            let end_y = (max.y / cell_size).floor() as i32;
+
+            for y in start_y..=end_y {
+                for x in start_x..=end_x {
+                    grid.entry((x, y)).or_default().push(index);
+                }
+            }
+        }
+
+        let mut pairs = Vec::new();
+        for cell in grid.values() {
+            for (offset, &i) in cell.iter().enumerate() {
+                for &j in &cell[offset + 1..] {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// Draws all visible world elements
+    /// - `camera_pos`: Current camera position in world coordinates
+    /// - `screen_size`: Size of the game window
+    ///
+    /// Equivalent to `draw_with_viewport` with an unzoomed `Viewport`.
+    pub fn draw(&mut self, camera_pos: Vec2, screen_size: Vec2) {
+        self.draw_with_viewport(Viewport::new(camera_pos, screen_size));
+    }
+
+    /// Draws all visible world elements for an explicit viewport
+    /// - `viewport`: Area of the world shown on screen
+    ///
+    /// Tiles, overlays and objects are culled against the world area the viewport
+    /// covers. Objects are drawn when any part of their bounds is in that area,
+    /// independent of the activation margin that decides which objects tick.
+    pub fn draw_with_viewport(&mut self, viewport: Viewport) {
+        let draw_start = self.start_timing();
+        let visible_chunks = std::mem::take(&mut self.visible_chunks);
+        self.draw_chunks(&visible_chunks, viewport.bounds());
+        self.visible_chunks = visible_chunks;
+        self.stats.draw_time = Self::elapsed(draw_start);
+    }
+
+    /// Draws the tiles, overlays and objects of the given chunks
+    /// - `chunks`: Chunk coordinates of the chunks to draw, in drawing order
+    /// - `area`: World area tiles, overlays and object bounds are culled against
+    ///
+    /// Chunks entirely outside the area are skipped before their tiles and overlays
+    /// are looked at. Their objects are still checked, since an object can reach
+    /// into the area from a chunk that is not in it.
+    fn draw_chunks(&mut self, chunks: &[(i32, i32)], area: Rect) {
+        let on_screen: Vec<(i32, i32)> = chunks
+            .iter()
+            .copied()
+            .filter(|chunk_pos| self.chunks.get(chunk_pos).is_some_and(|chunk| chunk.is_visible_in(area)))
+            .collect();
+        self.update_neighbor_masks(&on_screen);
+        self.draw_batch.clear();
+        self.draw_batch.reserve(self.stats.visible_tiles);
+        for chunk_pos in &on_screen {
+            if let Some(chunk) = self.chunks.get_mut(chunk_pos) {
+                chunk.draw_tiles_in(area, &mut self.draw_batch);
             }
         }
         self.draw_batch.draw();
+
+        let mut layers: Vec<u8> = on_screen
+            .iter()
+            .filter_map(|chunk_pos| self.chunks.get(chunk_pos))
+            .flat_map(|chunk| chunk.overlay_layers())
+            .collect();
+        layers.sort_unstable();
+        layers.dedup();
+        for layer in layers {
+            for chunk_pos in &on_screen {
+                if let Some(chunk) = self.chunks.get(chunk_pos) {
+                    chunk.draw_overlays_in(layer, area, &mut self.draw_batch);
+                }
+            }
+            self.draw_batch.draw();
+        }
+
+        self.draw_batch.clear();
+        let objects = Self::objects_to_draw(&self.chunks, chunks, area, self.object_sort);
+        // Ordered mode keeps the sorted order across textures while objects
+        // that share a texture in a row are still drawn as one group.
+        self.draw_batch.set_ordered(self.object_sort != SortMode::None);
+        for obj in objects {
+            obj.draw(&mut self.draw_batch);
+        }
+        self.draw_batch.draw();
+        self.draw_batch.set_ordered(false);
+    }
+
+    /// Collects the objects to draw in a world-space area, in drawing order
+    /// - `loaded`: The loaded chunks of the world
+    /// - `chunks`: Chunk coordinates of the chunks whose objects are considered
+    /// - `area`: World area object bounds are culled against
+    /// - `sort`: Order to draw the objects in
+    ///
+    /// Objects are culled by their full bounds, so a tall object is drawn even when
+    /// the chunk holding its position is outside the area.
+    ///
+    /// Returns the objects overlapping the area
+    fn objects_to_draw<'a>(loaded: &'a HashMap<(i32, i32), Chunk>, chunks: &[(i32, i32)], area: Rect, sort: SortMode) -> Vec<&'a dyn Object> {
+        let mut objects: Vec<&dyn Object> = Vec::new();
+        for chunk in chunks.iter().filter_map(|chunk_pos| loaded.get(chunk_pos)) {
+            objects.extend(chunk.objects_in(area));
+        }
+        if sort == SortMode::YAscending {
+            objects.sort_by(|a, b| {
+                let a_bottom = a.get_pos().y + a.get_size().y;
+                let b_bottom = b.get_pos().y + b.get_size().y;
+                a_bottom.total_cmp(&b_bottom)
+            });
+        }
+        objects
+    }
+
+    /// Returns counts and timings of the most recent update and draw
+    pub fn stats(&self) -> &WorldStats {
+        &self.stats
+    }
+
+    /// Sets whether update and draw times are measured into `stats`
+    /// - `enabled`: `true` to measure `tick_time`, `collision_time` and `draw_time`
+    ///
+    /// Disabled by default. While disabled, the times are reported as zero.
+    pub fn set_stats_timing(&mut self, enabled: bool) {
+        self.stats_timing = enabled;
+        if !enabled {
+            self.stats.tick_time = Duration::ZERO;
+            self.stats.collision_time = Duration::ZERO;
+            self.stats.draw_time = Duration::ZERO;
+        }
+    }
+
+    /// Returns whether update and draw times are measured into `stats`
+    pub fn stats_timing(&self) -> bool {
+        self.stats_timing
+    }
+
+    /// Returns the start of a measured section, or `None` if timing is disabled
+    fn start_timing(&self) -> Option<Instant> {
+        self.stats_timing.then(Instant::now)
+    }
+
+    /// Returns the time since `start`, or zero if timing is disabled
+    fn elapsed(start: Option<Instant>) -> Duration {
+        start.map_or(Duration::ZERO, |start| start.elapsed())
+    }
+
+    /// Sets the order in which objects are drawn
+    /// - `mode`: The sort mode to use
+    pub fn set_object_sort(&mut self, mode: SortMode) {
+        self.object_sort = mode;
     }
 
     /// Updates the list of chunks that are currently visible on screen
-    /// - `camera_chunk`: Current chunk coordinates of the camera
+    /// - `viewport`: Area of the world shown on screen
     /// 
     /// Determines which chunks should be loaded and rendered based on the camera's
-    /// current position and a fixed render distance. This helps optimize performance
-    /// by only processing chunks that are potentially visible.
-    fn update_visible_chunks(&mut self, camera_chunk: (i32, i32)) {
+    /// current position and a fixed render distance, extended to every chunk the
+    /// viewport overlaps when it shows more than that. This helps optimize performance
+    /// by only processing chunks that are potentially visible. If a generator is set,
+    /// visible chunks that are not loaded yet are generated.
+    fn update_visible_chunks(&mut self, viewport: Viewport) {
         self.visible_chunks.clear();
+        self.frame += 1;
         let render_dist = 2;
-        for y in -render_dist..=render_dist {
-            for x in -render_dist..=render_dist {
-                self.visible_chunks.push((camera_chunk.0 + x, camera_chunk.1 + y));
+        let camera_chunk = self.get_chunk_coords(viewport.center);
+        let bounds = viewport.bounds();
+        let min_chunk = self.get_chunk_coords(bounds.point());
+        let max_chunk = self.get_chunk_coords(bounds.point() + bounds.size());
+        let (start_x, end_x) = (min_chunk.0.min(camera_chunk.0 - render_dist), max_chunk.0.max(camera_chunk.0 + render_dist));
+        let (start_y, end_y) = (min_chunk.1.min(camera_chunk.1 - render_dist), max_chunk.1.max(camera_chunk.1 + render_dist));
+        for y in start_y..=end_y {
+            for x in start_x..=end_x {
+                self.visible_chunks.push((x, y));
             }
         }
+
+        let visible_chunks = std::mem::take(&mut self.visible_chunks);
+        self.fill_missing_chunks(&visible_chunks);
+        for chunk_pos in &visible_chunks {
+            if self.chunks.contains_key(chunk_pos) {
+                self.last_visible.insert(*chunk_pos, self.frame);
+            }
+        }
+        self.visible_chunks = visible_chunks;
+        self.evict_chunks();
+    }
+
+    /// Loads or generates every missing chunk in a rectangle of chunk coordinates
+    /// - `min`: Chunk coordinates of the first corner, inclusive
+    /// - `max`: Chunk coordinates of the opposite corner, inclusive
+    ///
+    /// Replaces looping over `add_chunk` to pre-generate an area. Chunks that are
+    /// already loaded are skipped. Missing chunks are read from the save directory
+    /// when one is set and has them, and generated with the registered generator
+    /// otherwise. With the `rayon` feature, missing chunks are generated in parallel
+    /// on rayon's thread pool, otherwise one after another on the calling thread.
+    /// The chunk budget is not applied here but on the next update.
+    ///
+    /// Returns the number of chunks that were added
+    pub fn generate_region(&mut self, min: (i32, i32), max: (i32, i32)) -> usize {
+        let loaded = self.chunks.len();
+        let mut chunks = Vec::new();
+        for y in min.1.min(max.1)..=min.1.max(max.1) {
+            for x in min.0.min(max.0)..=min.0.max(max.0) {
+                chunks.push((x, y));
+            }
+        }
+        self.fill_missing_chunks(&chunks);
+        let added = self.chunks.len() - loaded;
+        log_world!(log::Level::Info, "Generated region {:?} to {:?}: {} new chunks", min, max, added);
+        added
+    }
+
+    /// Reloads from disk or generates the chunks that are not loaded
+    /// - `chunks`: Chunk coordinates of the chunks that should be loaded
+    fn fill_missing_chunks(&mut self, chunks: &[(i32, i32)]) {
+        if let Some((save_dir, format)) = self.save_dir.clone() {
+            for &chunk_pos in chunks {
+                if self.chunks.contains_key(&chunk_pos) || self.missing_on_disk.contains(&chunk_pos) {
+                    continue;
+                }
+                let Some(path) = Self::existing_chunk_path(&save_dir, chunk_pos, format) else {
+                    self.missing_on_disk.insert(chunk_pos);
+                    continue;
+                };
+                match self.read_chunk(&path, format) {
+                    Ok(chunk) => {
+                        if self.add_missing_chunk(chunk_pos, chunk, "Reloaded") {
+                            log_world!(log::Level::Debug, "Reloaded chunk {:?} from disk", chunk_pos);
+                        }
+                    }
+                    Err(e) => log_world!(log::Level::Warn, "Failed to reload chunk {:?}: {}", chunk_pos, e),
+                }
+            }
+        }
+
+        let mut generated = Vec::new();
+        if let Some(generator) = &self.generator {
+            let ctx = GenContext {
+                tile_registry: &self.tile_registry,
+                object_registry: &self.object_registry,
+                biome_registry: &self.biome_registry,
+                seed: self.seed,
+                config: self.config,
+            };
+            let missing: Vec<(i32, i32)> = chunks.iter()
+                .copied()
+                .filter(|chunk_pos| !self.chunks.contains_key(chunk_pos))
+                .collect();
+            let generate = |&chunk_pos: &(i32, i32)| {
+                log_world!(log::Level::Debug, "Generating chunk {:?}", chunk_pos);
+                (chunk_pos, generator.generate(vec2(chunk_pos.0 as f32, chunk_pos.1 as f32), &ctx))
+            };
+            #[cfg(feature = "rayon")]
+            {
+                use rayon::prelude::*;
+                generated = missing.par_iter().map(generate).collect();
+            }
+            #[cfg(not(feature = "rayon"))]
+            {
+                generated = missing.iter().map(generate).collect();
+            }
+        }
+        for (chunk_pos, chunk) in generated {
+            self.add_missing_chunk(chunk_pos, chunk, "Generated");
+        }
+    }
+
+    /// Adds a chunk reloaded from disk or generated for missing coordinates
+    /// - `chunk_pos`: Chunk coordinates the chunk was requested for
+    /// - `chunk`: The reloaded or generated chunk
+    /// - `source`: How the chunk was created, for the log
+    ///
+    /// A chunk whose stored position differs from the requested coordinates is
+    /// discarded, the rest is validated by `add_chunk`.
+    ///
+    /// Returns `true` if the chunk was added
+    fn add_missing_chunk(&mut self, chunk_pos: (i32, i32), chunk: Chunk, source: &str) -> bool {
+        let stored_pos = (chunk.pos.x as i32, chunk.pos.y as i32);
+        if stored_pos != chunk_pos {
+            log_world!(log::Level::Warn, "{} chunk {:?} holds chunk {:?}, discarding it", source, chunk_pos, stored_pos);
+            return false;
+        }
+        self.add_chunk(chunk)
+    }
+
+    /// Logs a warning when a chunk's object count crosses the per-chunk limit
+    /// - `max`: Limit set with `set_max_objects_per_chunk`
+    /// - `chunk_pos`: Chunk coordinates of the chunk
+    /// - `previous`: Number of objects before the change
+    /// - `count`: Number of objects after the change
+    ///
+    /// Only crossing the limit is reported, so a chunk that stays above it does not
+    /// log again for every further object.
+    fn check_object_capacity(max: Option<usize>, chunk_pos: (i32, i32), previous: usize, count: usize) {
+        if let Some(max) = max {
+            if previous <= max && count > max {
+                log_chunk!(log::Level::Warn, "Chunk {:?} holds {} objects, more than the limit of {}", chunk_pos, count, max);
+            }
+        }
+    }
+
+    /// Evicts the least recently visible chunks until the chunk budget is met
+    ///
+    /// Dirty chunks are saved first when a save directory is set. A chunk that fails
+    /// to save stays loaded so its changes are not lost.
+    fn evict_chunks(&mut self) {
+        let Some(budget) = self.chunk_budget else {
+            return;
+        };
+        if self.chunks.len() <= budget {
+            return;
+        }
+
+        let mut candidates: Vec<((i32, i32), u64)> = self.chunks
+            .keys()
+            .filter(|chunk_pos| !self.visible_chunks.contains(chunk_pos))
+            .map(|&chunk_pos| (chunk_pos, self.last_visible.get(&chunk_pos).copied().unwrap_or(0)))
+            .collect();
+        candidates.sort_by_key(|&(chunk_pos, frame)| (frame, chunk_pos));
+
+        let excess = self.chunks.len() - budget;
+        for (chunk_pos, _) in candidates.into_iter().take(excess) {
+            if let Err(e) = self.save_evicted_chunk(chunk_pos) {
+                log_world!(log::Level::Error, "Keeping chunk {:?} loaded, saving it failed: {}", chunk_pos, e);
+                continue;
+            }
+
+            log_world!(log::Level::Debug, "Evicting chunk {:?}", chunk_pos);
+            if let Some(mut chunk) = self.chunks.remove(&chunk_pos) {
+                self.chunk_unloaded(&mut chunk);
+            }
+            self.last_visible.remove(&chunk_pos);
+            self.missing_on_disk.remove(&chunk_pos);
+        }
+    }
+
+    /// Saves a dirty chunk to the save directory before it is evicted
+    /// - `chunk_pos`: Chunk coordinates of the chunk
+    ///
+    /// Does nothing if no save directory is set or the chunk has no unsaved changes.
+    ///
+    /// Returns `Ok(())` on success, or an error message on failure
+    fn save_evicted_chunk(&self, chunk_pos: (i32, i32)) -> Result<(), String> {
+        let (Some((save_dir, format)), Some(chunk)) = (&self.save_dir, self.chunks.get(&chunk_pos)) else {
+            return Ok(());
+        };
+        if !chunk.is_dirty() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(format!("{}/chunks", save_dir)).map_err(|e| e.to_string())?;
+        let serialized = Self::serialize_chunk(chunk, *format, self.compress_chunks)?;
+        Self::write_chunk_file(save_dir, chunk_pos, *format, self.compress_chunks, &serialized)
     }
 
     /// Converts world coordinates to chunk coordinates
@@ -271,22 +1711,267 @@ impl World {
     /// 
     /// Returns the chunk coordinates as (x, y) where the given position is located.
     /// Chunk coordinates are calculated by dividing world coordinates by chunk size
-    /// and flooring the result to get the containing chunk.
+    /// and flooring the result to get the containing chunk, so negative positions
+    /// map to negative chunks (e.g. `x = -1.0` lies in chunk `-1`, not `0`).
     fn get_chunk_coords(&self, pos: Vec2) -> (i32, i32) {
+        Self::chunk_coords_in(pos, self.config.chunk_pixels())
+    }
+
+    /// Converts world coordinates to chunk coordinates for chunks of the given size
+    /// - `pos`: Position in world coordinates
+    /// - `chunk_pixels`: Size of a chunk in world units
+    ///
+    /// Same as `get_chunk_coords`, for callers that cannot borrow the whole world
+    fn chunk_coords_in(pos: Vec2, chunk_pixels: f32) -> (i32, i32) {
         (
-            (pos.x / CHUNK_PIXELS).floor() as i32,
-            (pos.y / CHUNK_PIXELS).floor() as i32,
+            (pos.x / chunk_pixels).floor() as i32,
+            (pos.y / chunk_pixels).floor() as i32,
         )
     }
 
+    /// Converts world coordinates to the chunk coordinates and local tile index
+    /// - `pos`: Position in world coordinates
+    ///
+    /// Returns the containing chunk coordinates and the index of the tile within
+    /// that chunk's `tiles` vector. Negative coordinates are handled by flooring,
+    /// so the local index always lies within `0..chunk_size * chunk_size`.
+    /// The chunk is derived from the same tile coordinates with `div_euclid`, so
+    /// chunk and local index always agree, even for positions on a chunk border.
+    fn get_tile_index(&self, pos: Vec2) -> ((i32, i32), usize) {
+        let size = self.config.chunk_size as i32;
+        let tile_x = (pos.x / self.config.tile_size).floor() as i32;
+        let tile_y = (pos.y / self.config.tile_size).floor() as i32;
+        let chunk = (tile_x.div_euclid(size), tile_y.div_euclid(size));
+        let local_x = tile_x.rem_euclid(size) as usize;
+        let local_y = tile_y.rem_euclid(size) as usize;
+        (chunk, local_y * self.config.chunk_size + local_x)
+    }
+
+    /// Returns the tile at the given world position
+    /// - `world_pos`: Position in world coordinates
+    ///
+    /// Returns `None` if the containing chunk is not loaded or has no tile at that index
+    pub fn tile_at(&self, world_pos: Vec2) -> Option<&dyn Tile> {
+        let (chunk_pos, index) = self.get_tile_index(world_pos);
+        self.chunks.get(&chunk_pos)?.tiles.get(index).map(|tile| tile.as_ref())
+    }
+
+    /// Returns a mutable reference to the tile at the given world position
+    /// - `world_pos`: Position in world coordinates
+    ///
+    /// Returns `None` if the containing chunk is not loaded or has no tile at that index
+    pub fn tile_at_mut(&mut self, world_pos: Vec2) -> Option<&mut dyn Tile> {
+        let (chunk_pos, index) = self.get_tile_index(world_pos);
+        let chunk = self.chunks.get_mut(&chunk_pos)?;
+        if index >= chunk.tiles.len() {
+            return None;
+        }
+        chunk.mark_dirty();
+        Some(chunk.tiles[index].as_mut())
+    }
+
+    /// Returns the four tiles adjacent to the tile at the given world position
+    /// - `world_pos`: Position in world coordinates
+    ///
+    /// Neighbors are looked up across chunk boundaries, and also work from inside
+    /// `Tile::tick`, where they show neighbors as they are at that point of the update.
+    /// Neighbors in the ticking tile's own chunk are only found if its
+    /// `Tile::reads_neighbors` returns `true`.
+    ///
+    /// Returns the up, down, left and right neighbors, `None` where no tile is loaded
+    pub fn tile_neighbors(&self, world_pos: Vec2) -> [Option<&dyn Tile>; 4] {
+        let tile_size = self.config.tile_size;
+        [
+            self.tile_at(world_pos - vec2(0.0, tile_size)),
+            self.tile_at(world_pos + vec2(0.0, tile_size)),
+            self.tile_at(world_pos - vec2(tile_size, 0.0)),
+            self.tile_at(world_pos + vec2(tile_size, 0.0)),
+        ]
+    }
+
+    /// Returns the tile an object is standing on
+    /// - `obj`: The object to look under
+    ///
+    /// Samples the row of tiles just below the object's bottom edge. The tile under
+    /// the bottom-center is preferred, but if the object spans several tiles and the
+    /// center is over a passable tile, the impassable tile under its feet nearest to
+    /// the center is returned instead, so an object hanging over a ledge still stands.
+    ///
+    /// Returns `None` if no tile is loaded below the bottom-center
+    pub fn tile_under(&self, obj: &dyn Object) -> Option<&dyn Tile> {
+        let tile_size = self.config.tile_size;
+        let (pos, size) = (obj.get_pos(), obj.get_size());
+        let row = ((pos.y + size.y) / tile_size).ceil() as i32;
+        let center_x = ((pos.x + size.x / 2.0) / tile_size).floor() as i32;
+        let under = self.tile_at(vec2(center_x as f32, row as f32) * tile_size);
+        if under.is_some_and(|tile| !tile.may_pass()) {
+            return under;
+        }
+
+        let (start_x, end_x) = Self::tile_span(pos.x, size.x, tile_size);
+        let mut columns: Vec<i32> = (start_x..end_x).filter(|&x| x != center_x).collect();
+        columns.sort_by_key(|&x| (x - center_x).abs());
+        columns
+            .into_iter()
+            .filter_map(|x| self.tile_at(vec2(x as f32, row as f32) * tile_size))
+            .find(|tile| !tile.may_pass())
+            .or(under)
+    }
+
+    /// Checks which sides of an object touch impassable tiles
+    /// - `obj`: The object to check around
+    ///
+    /// Every tile along each side, just outside the object's bounds, is sampled, so
+    /// objects larger than a tile are blocked on a side if any tile next to it
+    /// returns `false` from `may_pass`. Tiles that are not loaded count as passable.
+    ///
+    /// Returns whether the up, down, left and right sides are blocked
+    pub fn solid_neighbors(&self, obj: &dyn Object) -> [bool; 4] {
+        let tile_size = self.config.tile_size;
+        let (pos, size) = (obj.get_pos(), obj.get_size());
+        let (start_x, end_x) = Self::tile_span(pos.x, size.x, tile_size);
+        let (start_y, end_y) = Self::tile_span(pos.y, size.y, tile_size);
+        let is_solid = |x: i32, y: i32| self.is_solid_at(vec2(x as f32, y as f32) * tile_size);
+
+        [
+            (start_x..end_x).any(|x| is_solid(x, start_y - 1)),
+            (start_x..end_x).any(|x| is_solid(x, end_y)),
+            (start_y..end_y).any(|y| is_solid(start_x - 1, y)),
+            (start_y..end_y).any(|y| is_solid(end_x, y)),
+        ]
+    }
+
+    /// Returns the range of tile coordinates covered along one axis
+    /// - `start`: Start of the span in world coordinates
+    /// - `length`: Length of the span in world units
+    /// - `tile_size`: Size of a tile in world units
+    ///
+    /// The range always covers at least one tile, even for zero-sized spans
+    fn tile_span(start: f32, length: f32, tile_size: f32) -> (i32, i32) {
+        let first = (start / tile_size).floor() as i32;
+        let end = ((start + length) / tile_size).ceil() as i32;
+        (first, end.max(first + 1))
+    }
+
+    /// Returns the topmost object in a visible chunk whose bounds contain a point
+    /// - `world_pos`: Position in world coordinates
+    ///
+    /// See `object_index_at` for how the topmost object is chosen.
+    ///
+    /// Returns `None` if no object covers the point
+    pub fn object_at(&self, world_pos: Vec2) -> Option<&dyn Object> {
+        let (chunk_pos, index) = self.object_index_at(world_pos)?;
+        self.chunks.get(&chunk_pos)?.objects.get(index).map(|obj| obj.as_ref())
+    }
+
+    /// Finds the topmost object in a visible chunk whose bounds contain a point
+    /// - `world_pos`: Position in world coordinates
+    ///
+    /// Objects are ordered the way `draw` orders them, so the object drawn last at
+    /// that point wins: the one with the lowest bottom edge with
+    /// `SortMode::YAscending`, otherwise the last one in chunk order. The returned
+    /// location can be used with `get_chunk_mut` or `EventTarget::Object` until
+    /// objects move between chunks during the next update.
+    ///
+    /// Returns the chunk coordinates and index of the object, or `None` if no
+    /// object covers the point
+    pub fn object_index_at(&self, world_pos: Vec2) -> Option<((i32, i32), usize)> {
+        let mut topmost: Option<((i32, i32), usize, f32)> = None;
+
+        for &chunk_pos in &self.visible_chunks {
+            let Some(chunk) = self.chunks.get(&chunk_pos) else {
+                continue;
+            };
+            for (index, obj) in chunk.objects.iter().enumerate() {
+                let bounds = Rect::new(obj.get_pos().x, obj.get_pos().y, obj.get_size().x, obj.get_size().y);
+                if !bounds.contains(world_pos) {
+                    continue;
+                }
+                let order = match self.object_sort {
+                    SortMode::None => 0.0,
+                    SortMode::YAscending => bounds.bottom(),
+                };
+                if topmost.is_none_or(|(_, _, best)| order >= best) {
+                    topmost = Some((chunk_pos, index, order));
+                }
+            }
+        }
+
+        topmost.map(|(chunk_pos, index, _)| (chunk_pos, index))
+    }
+
+    /// Returns which of the eight surrounding tiles share the type of a tile
+    /// - `world_pos`: Position in world coordinates
+    ///
+    /// Neighbors are looked up across chunk boundaries; unloaded neighbors count
+    /// as different. Orthogonal-only autotiling can mask the result with
+    /// `NEIGHBOR_UP | NEIGHBOR_RIGHT | NEIGHBOR_DOWN | NEIGHBOR_LEFT`.
+    ///
+    /// Returns the combined `NEIGHBOR_*` bits, `0` if there is no tile at the position
+    pub fn neighbor_mask(&self, world_pos: Vec2) -> u8 {
+        let Some(tile) = self.tile_at(world_pos) else {
+            return 0;
+        };
+        let type_tag = tile.get_type_tag();
+        let offsets = [
+            (NEIGHBOR_UP, vec2(0.0, -1.0)),
+            (NEIGHBOR_UP_RIGHT, vec2(1.0, -1.0)),
+            (NEIGHBOR_RIGHT, vec2(1.0, 0.0)),
+            (NEIGHBOR_DOWN_RIGHT, vec2(1.0, 1.0)),
+            (NEIGHBOR_DOWN, vec2(0.0, 1.0)),
+            (NEIGHBOR_DOWN_LEFT, vec2(-1.0, 1.0)),
+            (NEIGHBOR_LEFT, vec2(-1.0, 0.0)),
+            (NEIGHBOR_UP_LEFT, vec2(-1.0, -1.0)),
+        ];
+
+        offsets
+            .into_iter()
+            .filter(|(_, offset)| {
+                self.tile_at(world_pos + *offset * self.config.tile_size)
+                    .is_some_and(|neighbor| neighbor.get_type_tag() == type_tag)
+            })
+            .fold(0, |mask, (bit, _)| mask | bit)
+    }
+
+    /// Recomputes the neighbor masks of connected tiles in the given chunks
+    /// - `chunks`: Chunk coordinates of the chunks about to be drawn
+    ///
+    /// Masks are recomputed every draw so they follow tile changes and chunks
+    /// loading next to each other. Chunks without connected tiles are skipped.
+    fn update_neighbor_masks(&mut self, chunks: &[(i32, i32)]) {
+        let mut updates = Vec::new();
+        for &chunk_pos in chunks {
+            let Some(chunk) = self.chunks.get(&chunk_pos) else {
+                continue;
+            };
+            if !chunk.tiles.iter().any(|tile| tile.is_connected()) {
+                continue;
+            }
+            let masks: Vec<u8> = chunk.tiles
+                .iter()
+                .map(|tile| if tile.is_connected() { self.neighbor_mask(tile.get_pos()) } else { 0 })
+                .collect();
+            updates.push((chunk_pos, masks));
+        }
+
+        for (chunk_pos, masks) in updates {
+            if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
+                chunk.neighbor_masks = masks;
+            }
+        }
+    }
+
     /// Returns all objects of the specified type in visible chunks
     /// - `type_tag`: The type of objects to find (must match exactly)
     /// 
     /// This is useful for finding all instances of a specific object type
     /// that are currently loaded in visible chunks. Searches through all
-    /// visible chunks and collects matching objects.
+    /// visible chunks and collects matching objects. Objects in chunks that
+    /// are loaded but not visible are not included, use `all_objects` or
+    /// `object_count_by_type` to include them.
     /// 
     /// Returns a vector of references to matching objects
+    #[allow(clippy::borrowed_box)]
     pub fn get_objects_by_type(&self, type_tag: &str) -> Vec<&Box<dyn Object>> {
         let mut objects = Vec::new();
         for &chunk_pos in &self.visible_chunks {
@@ -301,6 +1986,50 @@ impl World {
         objects
     }
 
+    /// Returns mutable references to all objects of the specified type in visible chunks
+    /// - `type_tag`: The type of objects to find (must match exactly)
+    ///
+    /// Chunks containing a matching object are marked dirty.
+    ///
+    /// Returns a vector of mutable references to matching objects
+    pub fn get_objects_by_type_mut(&mut self, type_tag: &str) -> Vec<&mut dyn Object> {
+        let visible_chunks = &self.visible_chunks;
+        self.chunks
+            .iter_mut()
+            .filter(|(chunk_pos, _)| visible_chunks.contains(chunk_pos))
+            .flat_map(|(_, chunk)| {
+                if chunk.objects.iter().any(|obj| obj.get_type_tag() == type_tag) {
+                    chunk.mark_dirty();
+                }
+                chunk.objects.iter_mut()
+            })
+            .filter(|obj| obj.get_type_tag() == type_tag)
+            .map(|obj| obj.as_mut())
+            .collect()
+    }
+
+    /// Returns all objects in every loaded chunk, visible or not
+    ///
+    /// Unlike the visible-only queries such as `get_objects_by_type`, this covers
+    /// chunks that are loaded but off screen. Visibility is not recomputed.
+    pub fn all_objects(&self) -> impl Iterator<Item = &dyn Object> {
+        self.chunks
+            .values()
+            .flat_map(|chunk| chunk.objects.iter())
+            .map(|obj| obj.as_ref())
+    }
+
+    /// Returns the number of objects in every loaded chunk, visible or not
+    pub fn object_count(&self) -> usize {
+        self.chunks.values().map(|chunk| chunk.objects.len()).sum()
+    }
+
+    /// Returns the number of objects of the specified type in every loaded chunk, visible or not
+    /// - `type_tag`: The type of objects to count (must match exactly)
+    pub fn object_count_by_type(&self, type_tag: &str) -> usize {
+        self.all_objects().filter(|obj| obj.get_type_tag() == type_tag).count()
+    }
+
     /// Returns all tiles of the specified type in visible chunks
     /// - `type_tag`: The type of tiles to find (must match exactly)
     /// 
@@ -308,6 +2037,7 @@ impl World {
     /// This is useful for finding specific terrain features or interactive elements.
     /// 
     /// Returns a vector of references to matching tiles
+    #[allow(clippy::borrowed_box)]
     pub fn get_tiles_by_type(&self, type_tag: &str) -> Vec<&Box<dyn Tile>> {
         let mut tiles = Vec::new();
 
@@ -322,4 +2052,982 @@ impl World {
         }
         tiles
     }
+
+    /// Returns mutable references to all tiles of the specified type in visible chunks
+    /// - `type_tag`: The type of tiles to find (must match exactly)
+    ///
+    /// Chunks containing a matching tile are marked dirty.
+    ///
+    /// Returns a vector of mutable references to matching tiles
+    pub fn get_tiles_by_type_mut(&mut self, type_tag: &str) -> Vec<&mut dyn Tile> {
+        let visible_chunks = &self.visible_chunks;
+        self.chunks
+            .iter_mut()
+            .filter(|(chunk_pos, _)| visible_chunks.contains(chunk_pos))
+            .flat_map(|(_, chunk)| {
+                if chunk.tiles.iter().any(|tile| tile.get_type_tag() == type_tag) {
+                    chunk.mark_dirty();
+                }
+                chunk.tiles.iter_mut()
+            })
+            .filter(|tile| tile.get_type_tag() == type_tag)
+            .map(|tile| tile.as_mut())
+            .collect()
+    }
+
+    /// Returns all objects in visible chunks whose center lies within a radius of a point
+    /// - `center`: Center of the query circle in world coordinates
+    /// - `radius`: Radius of the query circle in world units
+    ///
+    /// Useful for area effects and aggro ranges.
+    ///
+    /// Returns a vector of references to matching objects
+    pub fn objects_in_radius(&self, center: Vec2, radius: f32) -> Vec<&dyn Object> {
+        let mut objects = Vec::new();
+        for &chunk_pos in &self.visible_chunks {
+            if let Some(chunk) = self.chunks.get(&chunk_pos) {
+                for obj in &chunk.objects {
+                    if Self::is_in_radius(obj.as_ref(), center, radius) {
+                        objects.push(obj.as_ref());
+                    }
+                }
+            }
+        }
+        objects
+    }
+
+    /// Returns mutable references to all objects in visible chunks whose center lies within
+    /// a radius of a point
+    /// - `center`: Center of the query circle in world coordinates
+    /// - `radius`: Radius of the query circle in world units
+    ///
+    /// Returns a vector of mutable references to matching objects. Chunks holding a
+    /// match are marked dirty.
+    pub fn objects_in_radius_mut(&mut self, center: Vec2, radius: f32) -> Vec<&mut dyn Object> {
+        let visible_chunks = &self.visible_chunks;
+        self.chunks
+            .iter_mut()
+            .filter(|(chunk_pos, _)| visible_chunks.contains(chunk_pos))
+            .flat_map(|(_, chunk)| {
+                if chunk.objects.iter().any(|obj| Self::is_in_radius(obj.as_ref(), center, radius)) {
+                    chunk.mark_dirty();
+                }
+                chunk.objects.iter_mut()
+            })
+            .filter(|obj| Self::is_in_radius(obj.as_ref(), center, radius))
+            .map(|obj| obj.as_mut())
+            .collect()
+    }
+
+    /// Calls `hurt` on every object in visible chunks whose center lies within a radius of a point
+    /// - `center`: Center of the damage area in world coordinates
+    /// - `radius`: Radius of the damage area in world units
+    /// - `damage`: Amount of damage dealt to each object
+    /// - `dir`: Direction the attack came from
+    ///
+    /// Returns the number of objects that were hurt
+    pub fn apply_damage_in_radius(&mut self, center: Vec2, radius: f32, damage: i32, dir: Direction) -> usize {
+        let objects = self.objects_in_radius_mut(center, radius);
+        let count = objects.len();
+        for obj in objects {
+            obj.hurt(damage, dir);
+        }
+        count
+    }
+
+    /// Checks whether the center of an object lies within a radius of a point
+    /// - `obj`: The object to check
+    /// - `center`: Center of the circle in world coordinates
+    /// - `radius`: Radius of the circle in world units
+    fn is_in_radius(obj: &dyn Object, center: Vec2, radius: f32) -> bool {
+        let obj_center = obj.get_pos() + obj.get_size() / 2.0;
+        obj_center.distance_squared(center) <= radius * radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{registries, TestObject, TestTile};
+    use crate::{CHUNK_SIZE, OBJECT_INDEX_THRESHOLD, TILE_SIZE};
+
+    fn small_config() -> WorldConfig {
+        WorldConfig { tile_size: 8.0, chunk_size: 4, ..Default::default() }
+    }
+
+    fn test_world() -> World {
+        let (tiles, objects, biomes) = registries();
+        World::new("test", tiles, objects, biomes)
+    }
+
+    /// Returns an empty directory for a test to save a world in.
+    fn temp_save_dir(name: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("gaymwtf-core-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.to_string_lossy().into_owned()
+    }
+
+    /// Creates a chunk with the default config filled with copies of a tile.
+    fn filled_chunk(pos: (i32, i32), prototype: &dyn Tile) -> Chunk {
+        let mut chunk = Chunk::new(vec2(pos.0 as f32, pos.1 as f32));
+        let (origin, size) = (chunk.bounds().0, chunk.config().chunk_size);
+        for y in 0..size {
+            for x in 0..size {
+                let mut tile = prototype.clone_box();
+                tile.set_pos(origin + vec2(x as f32, y as f32) * TILE_SIZE);
+                chunk.tiles.push(tile);
+            }
+        }
+        chunk
+    }
+
+    fn grass_chunk(pos: (i32, i32)) -> Chunk {
+        filled_chunk(pos, &TestTile::new("grass"))
+    }
+
+    /// Returns a viewport that shows the given world-space area.
+    fn viewport_of(area: Rect) -> Viewport {
+        Viewport::new(area.center(), area.size())
+    }
+
+    #[test]
+    fn negative_positions_map_to_the_right_chunk_and_tile() {
+        let world = test_world();
+        let cases = [
+            (vec2(-1.0, -1.0), (-1, -1), 15 * CHUNK_SIZE + 15),
+            (vec2(-256.0, 0.0), (-1, 0), 0),
+            (vec2(-256.5, 0.0), (-2, 0), 15),
+        ];
+        for (pos, chunk, index) in cases {
+            assert_eq!(world.get_chunk_coords(pos), chunk, "chunk of {}", pos);
+            assert_eq!(world.get_tile_index(pos), (chunk, index), "tile of {}", pos);
+        }
+    }
+
+    #[test]
+    fn chunk_size_above_256_is_rejected() {
+        let sized = |chunk_size| WorldConfig { chunk_size, ..Default::default() };
+        assert!(sized(256).validate().is_ok());
+        assert!(sized(257).validate().is_err());
+
+        let mut world = test_world();
+        world.set_config(sized(257));
+        assert_eq!(world.config(), &WorldConfig::default());
+    }
+
+    #[test]
+    fn chunks_with_another_config_are_rejected() {
+        let mut world = test_world();
+        world.set_config(small_config());
+
+        assert!(!world.add_chunk(grass_chunk((0, 0))));
+        assert!(world.replace_chunk(grass_chunk((0, 0))).is_err());
+        assert!(world.add_chunk(Chunk::with_config(Vec2::ZERO, small_config())));
+    }
+
+    /// Generates empty chunks, laid out with the world's config if `use_context` is set
+    struct EmptyGenerator {
+        use_context: bool,
+    }
+
+    impl WorldGenerator for EmptyGenerator {
+        fn generate(&self, chunk_pos: Vec2, ctx: &GenContext) -> Chunk {
+            if self.use_context { ctx.new_chunk(chunk_pos) } else { Chunk::new(chunk_pos) }
+        }
+    }
+
+    #[test]
+    fn generated_chunks_with_another_config_are_discarded() {
+        for use_context in [false, true] {
+            let mut world = test_world();
+            world.set_config(small_config());
+            world.set_generator(Box::new(EmptyGenerator { use_context }));
+
+            world.fill_missing_chunks(&[(0, 0)]);
+            assert_eq!(world.get_chunk((0, 0)).is_some(), use_context);
+        }
+    }
+
+    #[test]
+    fn timings_are_only_measured_when_enabled() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        let chunk_pixels = world.config().chunk_pixels();
+        let viewport = viewport_of(Rect::new(0.0, 0.0, chunk_pixels, chunk_pixels));
+
+        world.advance(viewport, 0.1);
+        assert_eq!(world.stats().tick_time, Duration::ZERO);
+        assert!(world.stats().visible_tiles > 0);
+
+        world.set_stats_timing(true);
+        world.advance(viewport, 0.1);
+        assert!(world.stats().tick_time > Duration::ZERO);
+
+        world.set_stats_timing(false);
+        assert_eq!(world.stats().tick_time, Duration::ZERO);
+    }
+
+    #[test]
+    fn generate_region_fills_only_missing_chunks() {
+        let mut world = test_world();
+        world.set_generator(Box::new(EmptyGenerator { use_context: true }));
+        world.add_chunk(grass_chunk((0, 0)));
+
+        assert_eq!(world.generate_region((1, 1), (-1, -1)), 8);
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let chunk = world.get_chunk((x, y)).unwrap();
+                assert_eq!((chunk.pos.x as i32, chunk.pos.y as i32), (x, y));
+                assert_eq!(chunk.tiles.is_empty(), (x, y) != (0, 0));
+            }
+        }
+        assert_eq!(world.generate_region((-1, -1), (1, 1)), 0);
+    }
+
+    #[test]
+    fn ticks_only_dirty_chunks_that_changed() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.spawn_object(Box::new(TestObject::new(vec2(10.0, 10.0)))).unwrap();
+        world.get_chunk_mut((0, 0)).unwrap().clear_dirty();
+        let chunk_pixels = world.config().chunk_pixels();
+        let viewport = viewport_of(Rect::new(0.0, 0.0, chunk_pixels, chunk_pixels));
+
+        world.advance(viewport, 0.1);
+        assert!(!world.get_chunk((0, 0)).unwrap().is_dirty());
+
+        let mut moving = TestObject::new(vec2(30.0, 30.0));
+        moving.velocity = vec2(1.0, 0.0);
+        world.spawn_object(Box::new(moving)).unwrap();
+        world.get_chunk_mut((0, 0)).unwrap().clear_dirty();
+        world.advance(viewport, 0.1);
+        assert!(world.get_chunk((0, 0)).unwrap().is_dirty());
+    }
+
+    /// Gains one health on every tick and saves its health as custom state
+    #[derive(Clone)]
+    struct Healing {
+        pos: Vec2,
+        health: u32,
+    }
+
+    impl Object for Healing {
+        fn get_type_tag(&self) -> &'static str {
+            "healing"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(8.0)
+        }
+
+        fn get_velocity(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+
+        fn tick(&mut self, _dt: f32, _world: &mut World) -> bool {
+            self.health += 1;
+            true
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch) {}
+
+        fn set_size(&mut self, _size: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn set_velocity(&mut self, _velocity: Vec2) {}
+
+        fn serialize_state(&self) -> Result<Option<serde_json::Value>, String> {
+            Ok(Some(serde_json::json!({ "health": self.health })))
+        }
+
+        fn load_state(&mut self, state: &serde_json::Value) {
+            self.health = state["health"].as_u64().unwrap_or(0) as u32;
+        }
+
+        fn clone_box(&self) -> Box<dyn Object> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn state_changed_in_a_tick_is_saved() {
+        let save_dir = temp_save_dir("tick-state");
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.spawn_object(Box::new(Healing { pos: vec2(10.0, 10.0), health: 0 })).unwrap();
+        world.save_world(&save_dir).unwrap();
+        assert!(!world.get_chunk((0, 0)).unwrap().is_dirty());
+
+        let chunk_pixels = world.config().chunk_pixels();
+        world.advance(viewport_of(Rect::new(0.0, 0.0, chunk_pixels, chunk_pixels)), 0.1);
+        assert!(world.get_chunk((0, 0)).unwrap().is_dirty());
+        world.save_world(&save_dir).unwrap();
+
+        let (tiles, mut objects, biomes) = registries();
+        objects.register(Healing { pos: Vec2::ZERO, health: 0 });
+        let loaded = World::load_world(&save_dir, tiles, objects, biomes).unwrap();
+        let obj = &loaded.get_chunk((0, 0)).unwrap().objects[0];
+        assert_eq!(obj.serialize_state(), Ok(Some(serde_json::json!({ "health": 1 }))));
+        let _ = fs::remove_dir_all(&save_dir);
+    }
+
+    /// Fails to serialize its custom state
+    #[derive(Clone)]
+    struct BrokenState {
+        pos: Vec2,
+    }
+
+    impl Object for BrokenState {
+        fn get_type_tag(&self) -> &'static str {
+            "broken_state"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(8.0)
+        }
+
+        fn get_velocity(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch) {}
+
+        fn set_size(&mut self, _size: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn set_velocity(&mut self, _velocity: Vec2) {}
+
+        fn serialize_state(&self) -> Result<Option<serde_json::Value>, String> {
+            Err("broken state".to_string())
+        }
+
+        fn clone_box(&self) -> Box<dyn Object> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn failing_object_state_fails_the_save_without_writing() {
+        let save_dir = temp_save_dir("broken-state");
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.spawn_object(Box::new(TestObject::new(vec2(10.0, 10.0)))).unwrap();
+        world.save_world(&save_dir).unwrap();
+        let chunk_file = format!("{}/chunks/chunk_0_0.json", save_dir);
+        let saved = fs::read(&chunk_file).unwrap();
+
+        let chunk = world.get_chunk_mut((0, 0)).unwrap();
+        chunk.objects[0] = Box::new(BrokenState { pos: vec2(10.0, 10.0) });
+        chunk.mark_dirty();
+        assert!(world.save_world(&save_dir).is_err());
+        assert_eq!(fs::read(&chunk_file).unwrap(), saved);
+        assert!(world.get_chunk((0, 0)).unwrap().is_dirty());
+
+        let fresh_dir = temp_save_dir("broken-state-fresh");
+        assert!(world.save_world(&fresh_dir).is_err());
+        assert!(fs::metadata(&fresh_dir).is_err());
+        let _ = fs::remove_dir_all(&save_dir);
+    }
+
+    #[test]
+    fn misnamed_chunk_files_do_not_corrupt_the_load() {
+        let save_dir = temp_save_dir("misnamed-chunks");
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((1, 0)));
+        world.save_world(&save_dir).unwrap();
+        let chunks_dir = format!("{}/chunks", save_dir);
+        // A stale copy of chunk (0, 0) from before it held an object
+        fs::copy(format!("{}/chunk_0_0.json", chunks_dir), format!("{}/chunk_0_1.json", chunks_dir)).unwrap();
+        fs::rename(format!("{}/chunk_1_0.json", chunks_dir), format!("{}/chunk_7_3.json", chunks_dir)).unwrap();
+        fs::write(format!("{}/notes.txt", chunks_dir), "not a chunk").unwrap();
+        world.spawn_object(Box::new(TestObject::new(vec2(10.0, 10.0)))).unwrap();
+        world.save_world(&save_dir).unwrap();
+
+        let (tiles, objects, biomes) = registries();
+        let loaded = World::load_world(&save_dir, tiles, objects, biomes).unwrap();
+        let mut positions: Vec<_> = loaded.chunks.keys().copied().collect();
+        positions.sort();
+        assert_eq!(positions, vec![(0, 0), (1, 0)]);
+        assert_eq!(loaded.get_chunk((0, 0)).unwrap().objects.len(), 1);
+        assert_eq!(loaded.get_chunk((1, 0)).unwrap().pos, vec2(1.0, 0.0));
+        let _ = fs::remove_dir_all(&save_dir);
+    }
+
+    #[test]
+    fn streaming_loads_skip_misnamed_chunks_outside_the_radius() {
+        let save_dir = temp_save_dir("streaming-misnamed");
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((9, 0)));
+        world.save_world(&save_dir).unwrap();
+        let chunks_dir = format!("{}/chunks", save_dir);
+        fs::rename(format!("{}/chunk_9_0.json", chunks_dir), format!("{}/chunk_1_0.json", chunks_dir)).unwrap();
+
+        let (tiles, objects, biomes) = registries();
+        let loaded = World::load_world_streaming(&save_dir, SaveFormat::Json, tiles, objects, biomes, Vec2::ZERO, 2).unwrap();
+        assert_eq!(loaded.chunks.keys().copied().collect::<Vec<_>>(), vec![(0, 0)]);
+        let _ = fs::remove_dir_all(&save_dir);
+    }
+
+    #[test]
+    fn reloaded_chunks_with_another_stored_position_are_discarded() {
+        let save_dir = temp_save_dir("reload-misnamed");
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((5, 0)));
+        world.save_world(&save_dir).unwrap();
+        let chunks_dir = format!("{}/chunks", save_dir);
+        fs::rename(format!("{}/chunk_5_0.json", chunks_dir), format!("{}/chunk_1_0.json", chunks_dir)).unwrap();
+
+        let (tiles, objects, biomes) = registries();
+        let mut loaded = World::load_world_streaming(&save_dir, SaveFormat::Json, tiles, objects, biomes, Vec2::ZERO, 0).unwrap();
+        loaded.fill_missing_chunks(&[(1, 0)]);
+        assert!(loaded.get_chunk((1, 0)).is_none());
+        assert_eq!(loaded.chunks.keys().copied().collect::<Vec<_>>(), vec![(0, 0)]);
+        let _ = fs::remove_dir_all(&save_dir);
+    }
+
+    #[test]
+    fn tall_objects_from_chunks_outside_the_area_are_drawn() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((0, -1)));
+        let chunk_pixels = world.config().chunk_pixels();
+        let tall = TestObject { size: vec2(16.0, 64.0), ..TestObject::new(vec2(10.0, -40.0)) };
+        for obj in [tall, TestObject::new(vec2(50.0, 50.0)), TestObject::new(vec2(10.0, -100.0))] {
+            world.spawn_object(Box::new(obj)).unwrap();
+        }
+        assert_eq!(world.get_chunk((0, -1)).unwrap().objects.len(), 2);
+
+        let area = Rect::new(0.0, 0.0, chunk_pixels, chunk_pixels);
+        let objects = World::objects_to_draw(&world.chunks, &[(0, 0), (0, -1)], area, SortMode::YAscending);
+        let positions: Vec<Vec2> = objects.iter().map(|obj| obj.get_pos()).collect();
+        assert_eq!(positions, vec![vec2(10.0, -40.0), vec2(50.0, 50.0)]);
+    }
+
+    #[test]
+    fn objects_and_tiles_of_a_type_can_be_mutated() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((1, 0)));
+        let chunk_pixels = world.config().chunk_pixels();
+        for x in [10.0, 20.0, chunk_pixels + 10.0] {
+            world.spawn_object(Box::new(TestObject::new(vec2(x, 10.0)))).unwrap();
+        }
+        for chunk in [(0, 0), (1, 0)] {
+            world.get_chunk_mut(chunk).unwrap().clear_dirty();
+        }
+        world.visible_chunks = vec![(0, 0), (1, 0)];
+
+        let matched = world.get_objects_by_type_mut("test_object");
+        assert_eq!(matched.len(), 3);
+        for obj in matched {
+            obj.set_velocity(vec2(0.0, -1.0));
+        }
+        for tile in world.get_tiles_by_type_mut("grass") {
+            if let Some(tile) = (tile as &mut dyn std::any::Any).downcast_mut::<TestTile>() {
+                tile.tag = "dirt";
+            }
+        }
+
+        assert!(world.get_objects_by_type("test_object").iter().all(|obj| obj.get_velocity() == vec2(0.0, -1.0)));
+        assert_eq!(world.get_tiles_by_type("dirt").len(), 2 * world.get_chunk((0, 0)).unwrap().tiles.len());
+        assert!(world.get_chunk((0, 0)).unwrap().is_dirty());
+        assert!(world.get_chunk((1, 0)).unwrap().is_dirty());
+    }
+
+    /// Loses health when hurt or hit by an event and saves its health as custom state
+    #[derive(Clone)]
+    struct Target {
+        pos: Vec2,
+        health: u32,
+    }
+
+    impl Object for Target {
+        fn get_type_tag(&self) -> &'static str {
+            "target"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(8.0)
+        }
+
+        fn get_velocity(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+
+        fn hurt(&mut self, damage: i32, _attack_dir: Direction) {
+            self.health = self.health.saturating_sub(damage as u32);
+        }
+
+        fn on_event(&mut self, event: &Event, _world: &mut World) {
+            if let Event::Hit { damage, direction } = event {
+                self.hurt(*damage, *direction);
+            }
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch) {}
+
+        fn set_size(&mut self, _size: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn set_velocity(&mut self, _velocity: Vec2) {}
+
+        fn serialize_state(&self) -> Result<Option<serde_json::Value>, String> {
+            Ok(Some(serde_json::json!({ "health": self.health })))
+        }
+
+        fn clone_box(&self) -> Box<dyn Object> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn damage_marks_hit_chunks_dirty() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((1, 0)));
+        world.spawn_object(Box::new(Target { pos: vec2(10.0, 10.0), health: 5 })).unwrap();
+        for chunk in [(0, 0), (1, 0)] {
+            world.get_chunk_mut(chunk).unwrap().clear_dirty();
+        }
+        world.visible_chunks = vec![(0, 0), (1, 0)];
+
+        assert_eq!(world.apply_damage_in_radius(vec2(14.0, 14.0), 8.0, 2, Direction::Left), 1);
+        let chunk = world.get_chunk((0, 0)).unwrap();
+        assert!(chunk.is_dirty());
+        assert_eq!(chunk.objects[0].serialize_state(), Ok(Some(serde_json::json!({ "health": 3 }))));
+        assert!(!world.get_chunk((1, 0)).unwrap().is_dirty());
+    }
+
+    #[test]
+    fn delivered_events_mark_chunks_dirty() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((1, 0)));
+        world.spawn_object(Box::new(Target { pos: vec2(10.0, 10.0), health: 5 })).unwrap();
+        for chunk in [(0, 0), (1, 0)] {
+            world.get_chunk_mut(chunk).unwrap().clear_dirty();
+        }
+        world.visible_chunks = vec![(0, 0), (1, 0)];
+
+        world.post_event(EventTarget::Type("target".to_string()), Event::Hit { damage: 2, direction: Direction::Left });
+        world.dispatch_events();
+        assert!(world.get_chunk((0, 0)).unwrap().is_dirty());
+        assert!(!world.get_chunk((1, 0)).unwrap().is_dirty());
+    }
+
+    #[test]
+    fn broad_phase_finds_every_colliding_pair() {
+        let mut rng = crate::SeededRng::new(7);
+        let objects: Vec<Box<dyn Object>> = (0..1000)
+            .map(|_| {
+                let mut obj = TestObject::new(vec2(rng.next_f32(), rng.next_f32()) * 400.0);
+                obj.size = vec2(rng.next_f32(), rng.next_f32()) * 24.0 + Vec2::ONE;
+                obj.velocity = (vec2(rng.next_f32(), rng.next_f32()) - 0.5) * 40.0;
+                Box::new(obj) as Box<dyn Object>
+            })
+            .collect();
+        let collide = |&(i, j): &(usize, usize)| will_collide(objects[i].as_ref(), objects[j].as_ref(), COLLISION_BUFFER);
+
+        let found: Vec<_> = World::collision_candidates(&objects, WorldConfig::default().collision_cell_size())
+            .into_iter()
+            .filter(collide)
+            .collect();
+        let naive: Vec<_> = (0..objects.len())
+            .flat_map(|i| (i + 1..objects.len()).map(move |j| (i, j)))
+            .filter(collide)
+            .collect();
+        assert!(!naive.is_empty());
+        assert_eq!(found, naive);
+    }
+
+    /// A tile that is drawn with a neighbor mask if `connected` is set
+    #[derive(Clone)]
+    struct Wall {
+        pos: Vec2,
+        connected: bool,
+    }
+
+    impl Tile for Wall {
+        fn get_type_tag(&self) -> &'static str {
+            "wall"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn is_connected(&self) -> bool {
+            self.connected
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn neighbor_masks_have_a_bit_per_matching_neighbor() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((1, 0)));
+        for (x, y) in [(5.0, 5.0), (5.0, 4.0), (6.0, 5.0), (15.0, 9.0), (16.0, 9.0)] {
+            let pos = vec2(x, y) * TILE_SIZE;
+            let (chunk, index) = world.get_tile_index(pos);
+            world.get_chunk_mut(chunk).unwrap().tiles[index] = Box::new(Wall { pos, connected: false });
+        }
+
+        assert_eq!(world.neighbor_mask(vec2(5.0, 5.0) * TILE_SIZE), NEIGHBOR_UP | NEIGHBOR_RIGHT);
+        assert_eq!(world.neighbor_mask(vec2(6.0, 5.0) * TILE_SIZE), NEIGHBOR_LEFT | NEIGHBOR_UP_LEFT);
+        assert_eq!(world.neighbor_mask(vec2(15.0, 9.0) * TILE_SIZE), NEIGHBOR_RIGHT);
+        assert_eq!(world.neighbor_mask(vec2(0.0, 0.0)), NEIGHBOR_RIGHT | NEIGHBOR_DOWN_RIGHT | NEIGHBOR_DOWN);
+    }
+
+    #[test]
+    fn chunks_outside_the_drawn_area_are_skipped() {
+        let mut world = test_world();
+        let wall = Wall { pos: Vec2::ZERO, connected: true };
+        world.add_chunk(filled_chunk((0, 0), &wall));
+        world.add_chunk(filled_chunk((1, 0), &wall));
+
+        world.draw_chunks(&[(0, 0), (1, 0)], Rect::new(0.0, 0.0, 200.0, 200.0));
+        assert!(!world.get_chunk((0, 0)).unwrap().neighbor_masks.is_empty());
+        assert!(world.get_chunk((1, 0)).unwrap().neighbor_masks.is_empty());
+    }
+
+    #[test]
+    fn culling_follows_the_viewport_zoom() {
+        let mut world = test_world();
+        for x in -4..4 {
+            for y in -4..4 {
+                world.add_chunk(grass_chunk((x, y)));
+            }
+        }
+        let viewport = Viewport::new(Vec2::ZERO, vec2(800.0, 600.0));
+        // 400x300 world units at 2x and 1600x1200 at 0.5x, edge tiles included
+        for (zoom, columns, rows) in [(1.0, 50, 38), (2.0, 26, 20), (0.5, 100, 76)] {
+            world.advance(viewport.with_zoom(zoom), 0.0);
+            assert_eq!(world.stats.visible_tiles, columns * rows, "zoom {zoom}");
+        }
+    }
+
+    /// A tile that stops objects
+    #[derive(Clone)]
+    struct Stone {
+        pos: Vec2,
+    }
+
+    impl Tile for Stone {
+        fn get_type_tag(&self) -> &'static str {
+            "stone"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn may_pass(&self) -> bool {
+            false
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn moving_objects_stop_at_solid_tiles() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        let wall = vec2(5.0, 0.0) * TILE_SIZE;
+        let (chunk, index) = world.get_tile_index(wall);
+        world.get_chunk_mut(chunk).unwrap().tiles[index] = Box::new(Stone { pos: wall });
+        let mut mob = TestObject::new(vec2(wall.x - 20.0, 4.0));
+        mob.velocity = vec2(4.0, 0.0);
+        let mob = world.spawn_object(Box::new(mob)).unwrap().unwrap();
+
+        let chunk_pixels = world.config().chunk_pixels();
+        for _ in 0..10 {
+            world.advance(viewport_of(Rect::new(0.0, 0.0, chunk_pixels, chunk_pixels)), 1.0);
+        }
+        let mob = world.get_object(mob).unwrap();
+        assert_eq!(mob.get_velocity(), Vec2::ZERO);
+        assert!(mob.get_pos().x + mob.get_size().x <= wall.x);
+        assert!(mob.get_pos().x >= wall.x - 12.0);
+    }
+
+    /// Jumps to a fixed position when it collides with another object
+    #[derive(Clone)]
+    struct Pushed {
+        pos: Vec2,
+        id: Option<ObjectId>,
+        knockback: Vec2,
+    }
+
+    impl Object for Pushed {
+        fn get_type_tag(&self) -> &'static str {
+            "pushed"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(8.0)
+        }
+
+        fn get_velocity(&self) -> Vec2 {
+            Vec2::ZERO
+        }
+
+        fn collision(&mut self, _other: &mut dyn Object) {
+            self.pos = self.knockback;
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch) {}
+
+        fn set_size(&mut self, _size: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn set_velocity(&mut self, _velocity: Vec2) {}
+
+        fn get_id(&self) -> Option<ObjectId> {
+            self.id
+        }
+
+        fn set_id(&mut self, id: ObjectId) {
+            self.id = Some(id);
+        }
+
+        fn clone_box(&self) -> Box<dyn Object> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn objects_moved_by_collisions_are_regridded() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        for index in 0..OBJECT_INDEX_THRESHOLD + 6 {
+            let mut filler = TestObject::new(vec2((index % 35) as f32 * 7.0, 200.0 + (index / 35) as f32 * 15.0));
+            filler.size = Vec2::splat(4.0);
+            world.spawn_object(Box::new(filler)).unwrap();
+        }
+        let pushed = Pushed { pos: vec2(230.0, 230.0), id: None, knockback: vec2(20.0, 20.0) };
+        let pushed = world.spawn_object(Box::new(pushed)).unwrap().unwrap();
+        world.spawn_object(Box::new(TestObject::new(vec2(232.0, 232.0)))).unwrap();
+        world.visible_chunks = vec![(0, 0)];
+
+        let area = Rect::new(0.0, 0.0, 64.0, 64.0);
+        let chunk = world.get_chunk_mut((0, 0)).unwrap();
+        chunk.prepare_update(area);
+        assert_eq!(chunk.active_object_count(), 0);
+
+        world.check_obj_collisions();
+        assert_eq!(world.get_object(pushed).unwrap().get_pos(), vec2(20.0, 20.0));
+        let chunk = world.get_chunk_mut((0, 0)).unwrap();
+        chunk.prepare_update(area);
+        let active: Vec<Vec2> = chunk.iter_active_objects().map(|obj| obj.get_pos()).collect();
+        assert_eq!(active, vec![vec2(20.0, 20.0)]);
+    }
+
+    /// Soil that becomes wet when a neighbor is wet, tracked in its variant
+    #[derive(Clone)]
+    struct Soil {
+        pos: Vec2,
+        wet: bool,
+    }
+
+    impl Tile for Soil {
+        fn get_type_tag(&self) -> &'static str {
+            "soil"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn tick(&mut self, _dt: f32, world: &mut World) -> bool {
+            let was_wet = self.wet;
+            self.wet |= world.tile_neighbors(self.pos).iter().flatten().any(|tile| tile.get_variant() == 1);
+            self.wet != was_wet
+        }
+
+        fn reads_neighbors(&self) -> bool {
+            true
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn get_variant(&self) -> u32 {
+            self.wet as u32
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Counts how often tiles of this type are cloned
+    static CLONES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    #[derive(Clone)]
+    struct Counted {
+        pos: Vec2,
+    }
+
+    impl Tile for Counted {
+        fn get_type_tag(&self) -> &'static str {
+            "counted"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            CLONES.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Box::new(self.clone())
+        }
+    }
+
+    #[test]
+    fn tiles_that_do_not_read_neighbors_are_not_copied_for_their_tick() {
+        let mut world = test_world();
+        world.add_chunk(filled_chunk((0, 0), &Counted { pos: Vec2::ZERO }));
+        let chunk_pixels = world.config().chunk_pixels();
+        let before = CLONES.load(std::sync::atomic::Ordering::Relaxed);
+
+        world.advance(viewport_of(Rect::new(0.0, 0.0, chunk_pixels, chunk_pixels)), 0.1);
+        assert!(world.get_chunk((0, 0)).unwrap().visible_tile_count() > 0);
+        assert_eq!(CLONES.load(std::sync::atomic::Ordering::Relaxed), before);
+    }
+
+    #[test]
+    fn wet_tiles_spread_to_dry_neighbors() {
+        let mut world = test_world();
+        let dry = Soil { pos: Vec2::ZERO, wet: false };
+        world.add_chunk(filled_chunk((0, 0), &dry));
+        world.add_chunk(filled_chunk((1, 0), &dry));
+        let edge = vec2(15.0, 0.0) * TILE_SIZE;
+        let (chunk, index) = world.get_tile_index(edge);
+        world.get_chunk_mut(chunk).unwrap().tiles[index] = Box::new(Soil { pos: edge, wet: true });
+
+        let chunk_pixels = world.config().chunk_pixels();
+        let viewport = viewport_of(Rect::new(0.0, 0.0, chunk_pixels * 2.0, chunk_pixels));
+        let is_wet = |world: &World, x: f32| world.tile_at(vec2(x, 0.0) * TILE_SIZE).is_some_and(|tile| tile.get_variant() == 1);
+
+        world.advance(viewport, 0.1);
+        assert!(is_wet(&world, 14.0), "same-chunk neighbor gets wet");
+        assert!(is_wet(&world, 16.0), "neighbor in the next chunk gets wet");
+        assert!(!is_wet(&world, 13.0));
+
+        world.advance(viewport, 0.1);
+        assert!(is_wet(&world, 13.0));
+        assert!(world.get_chunk((0, 0)).unwrap().is_dirty());
+    }
+
+    #[test]
+    fn objects_leaving_one_chunk_together_all_migrate() {
+        let mut world = test_world();
+        world.add_chunk(grass_chunk((0, 0)));
+        world.add_chunk(grass_chunk((1, 0)));
+        world.add_chunk(grass_chunk((0, 1)));
+        for x in [10.0, 20.0, 30.0, 40.0] {
+            world.spawn_object(Box::new(TestObject::new(vec2(x, 10.0)))).unwrap();
+        }
+        let chunk_pixels = world.config().chunk_pixels();
+        let chunk = world.get_chunk_mut((0, 0)).unwrap();
+        chunk.objects[0].set_pos(vec2(chunk_pixels + 10.0, 10.0));
+        chunk.objects[1].set_pos(vec2(20.0, chunk_pixels + 20.0));
+        chunk.objects[3].set_pos(vec2(chunk_pixels + 40.0, 40.0));
+        world.visible_chunks = vec![(0, 0), (1, 0), (0, 1)];
+
+        world.migrate_objects();
+
+        let positions = |world: &World, chunk_pos| -> Vec<Vec2> {
+            world.get_chunk(chunk_pos).unwrap().objects.iter().map(|obj| obj.get_pos()).collect()
+        };
+        assert_eq!(positions(&world, (0, 0)), vec![vec2(30.0, 10.0)]);
+        assert_eq!(positions(&world, (1, 0)), vec![vec2(chunk_pixels + 10.0, 10.0), vec2(chunk_pixels + 40.0, 40.0)]);
+        assert_eq!(positions(&world, (0, 1)), vec![vec2(20.0, chunk_pixels + 20.0)]);
+    }
+
+    #[test]
+    fn config_is_restored_on_load() {
+        let save_dir = temp_save_dir("config");
+        let mut world = test_world();
+        world.set_config(small_config());
+        world.save_world_as(&save_dir, SaveFormat::Bincode).unwrap();
+
+        let (tiles, objects, biomes) = registries();
+        let loaded = World::load_world_as(&save_dir, SaveFormat::Bincode, tiles, objects, biomes).unwrap();
+        assert_eq!(loaded.config(), &small_config());
+        let _ = fs::remove_dir_all(&save_dir);
+    }
 }
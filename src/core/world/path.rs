@@ -0,0 +1,225 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::f32::consts::SQRT_2;
+
+use macroquad::prelude::*;
+
+use crate::World;
+
+/// Orthogonal steps on the tile grid
+const ORTHOGONAL: [(i32, i32); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Diagonal steps on the tile grid
+const DIAGONAL: [(i32, i32); 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+
+impl World {
+    /// Finds a walkable path between two positions with A* over the tile grid
+    /// - `start`: Start position in world coordinates
+    /// - `goal`: Goal position in world coordinates
+    /// - `max_nodes`: Maximum number of tiles to expand before giving up
+    ///
+    /// A tile is walkable if it is loaded and its `may_pass` returns `true`, so
+    /// paths cross chunk borders but never enter unloaded chunks. Diagonal steps
+    /// are only taken when enabled with `set_path_diagonals`, and never cut the
+    /// corner of an impassable tile. The heuristic is the Manhattan distance, or
+    /// the octile distance with diagonals.
+    ///
+    /// Returns the centers of the tiles to walk through after the start tile, ending
+    /// with the goal tile, or `None` if the goal is unreachable within `max_nodes`
+    pub fn find_path(&self, start: Vec2, goal: Vec2, max_nodes: usize) -> Option<Vec<Vec2>> {
+        let start = self.tile_coords(start);
+        let goal = self.tile_coords(goal);
+        if !self.is_walkable(goal) {
+            return None;
+        }
+
+        let diagonals = self.path_diagonals;
+        let heuristic = |(x, y): (i32, i32)| {
+            let (dx, dy) = ((x - goal.0).abs() as f32, (y - goal.1).abs() as f32);
+            if diagonals {
+                dx.max(dy) + (SQRT_2 - 1.0) * dx.min(dy)
+            } else {
+                dx + dy
+            }
+        };
+
+        // Costs are never negative, so the bits of an f32 order like the value
+        let mut open = BinaryHeap::new();
+        let mut costs: HashMap<(i32, i32), f32> = HashMap::new();
+        let mut came_from: HashMap<(i32, i32), (i32, i32)> = HashMap::new();
+        open.push(Reverse((heuristic(start).to_bits(), start)));
+        costs.insert(start, 0.0);
+
+        let mut expanded = 0;
+        while let Some(Reverse((_, current))) = open.pop() {
+            if current == goal {
+                let mut path = vec![current];
+                let mut node = current;
+                while let Some(&previous) = came_from.get(&node) {
+                    if previous == start {
+                        break;
+                    }
+                    path.push(previous);
+                    node = previous;
+                }
+                if current == start {
+                    path.clear();
+                }
+                path.reverse();
+                return Some(path.into_iter().map(|tile| self.tile_center(tile)).collect());
+            }
+
+            expanded += 1;
+            if expanded > max_nodes {
+                return None;
+            }
+
+            let cost = costs[&current];
+            let steps = ORTHOGONAL
+                .iter()
+                .map(|&step| (step, 1.0))
+                .chain(DIAGONAL.iter().filter(|_| diagonals).map(|&step| (step, SQRT_2)));
+            for ((dx, dy), step_cost) in steps {
+                let next = (current.0 + dx, current.1 + dy);
+                if !self.is_walkable(next) {
+                    continue;
+                }
+                if dx != 0 && dy != 0
+                    && !(self.is_walkable((current.0 + dx, current.1)) && self.is_walkable((current.0, current.1 + dy)))
+                {
+                    continue;
+                }
+
+                let next_cost = cost + step_cost;
+                if costs.get(&next).is_some_and(|&known| known <= next_cost) {
+                    continue;
+                }
+                costs.insert(next, next_cost);
+                came_from.insert(next, current);
+                open.push(Reverse(((next_cost + heuristic(next)).to_bits(), next)));
+            }
+        }
+        None
+    }
+
+    /// Returns the tile coordinates of the tile containing a world position
+    pub(super) fn tile_coords(&self, world_pos: Vec2) -> (i32, i32) {
+        let tile_size = self.config.tile_size;
+        ((world_pos.x / tile_size).floor() as i32, (world_pos.y / tile_size).floor() as i32)
+    }
+
+    /// Returns the center of a tile in world coordinates
+    pub(super) fn tile_center(&self, (x, y): (i32, i32)) -> Vec2 {
+        (vec2(x as f32, y as f32) + 0.5) * self.config.tile_size
+    }
+
+    /// Returns whether the tile at the given tile coordinates is loaded and passable
+    fn is_walkable(&self, (x, y): (i32, i32)) -> bool {
+        self.tile_at(vec2(x as f32, y as f32) * self.config.tile_size)
+            .is_some_and(|tile| tile.may_pass())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{registries, TestTile};
+    use crate::{Chunk, DrawBatch, Tile, TILE_SIZE};
+
+    /// A tile that is not walkable
+    #[derive(Clone)]
+    struct Stone {
+        pos: Vec2,
+    }
+
+    impl Tile for Stone {
+        fn get_type_tag(&self) -> &'static str {
+            "stone"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn may_pass(&self) -> bool {
+            false
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Creates a world with the given chunks filled with grass.
+    fn grass_world(chunks: &[(i32, i32)]) -> World {
+        let (tiles, objects, biomes) = registries();
+        let mut world = World::new("test", tiles, objects, biomes);
+        for &(x, y) in chunks {
+            let mut chunk = Chunk::new(vec2(x as f32, y as f32));
+            let (origin, size) = (chunk.bounds().0, chunk.config().chunk_size);
+            for ty in 0..size {
+                for tx in 0..size {
+                    let tile = TestTile { pos: origin + vec2(tx as f32, ty as f32) * TILE_SIZE, ..TestTile::new("grass") };
+                    chunk.tiles.push(Box::new(tile));
+                }
+            }
+            world.add_chunk(chunk);
+        }
+        world
+    }
+
+    /// Replaces the tile at tile coordinates with an impassable one.
+    fn wall(world: &mut World, (x, y): (i32, i32)) {
+        let pos = vec2(x as f32, y as f32) * world.config.tile_size;
+        let (chunk, index) = world.get_tile_index(pos);
+        world.get_chunk_mut(chunk).unwrap().tiles[index] = Box::new(Stone { pos });
+    }
+
+    /// Two chunks side by side, split by a wall at column 8 with a gap in row 12.
+    fn walled_world(gap: bool) -> World {
+        let mut world = grass_world(&[(0, 0), (1, 0)]);
+        for y in 0..16 {
+            if !(gap && y == 12) {
+                wall(&mut world, (8, y));
+            }
+        }
+        world
+    }
+
+    #[test]
+    fn paths_route_through_a_gap_in_a_wall() {
+        let mut world = walled_world(true);
+        let (start, goal) = (world.tile_center((2, 2)), world.tile_center((20, 2)));
+        for diagonals in [false, true] {
+            world.set_path_diagonals(diagonals);
+            let path = world.find_path(start, goal, 10_000).unwrap();
+            assert_eq!(path.last(), Some(&goal));
+            assert!(path.contains(&world.tile_center((8, 12))));
+
+            let mut previous = world.tile_coords(start);
+            for &waypoint in &path {
+                let tile = world.tile_coords(waypoint);
+                assert!(world.is_walkable(tile));
+                let (dx, dy) = (tile.0 - previous.0, tile.1 - previous.1);
+                assert!(dx.abs() <= 1 && dy.abs() <= 1 && (diagonals || dx == 0 || dy == 0));
+                previous = tile;
+            }
+        }
+        assert_eq!(world.find_path(start, goal, 10), None);
+    }
+
+    #[test]
+    fn closed_walls_have_no_path() {
+        let world = walled_world(false);
+        assert_eq!(world.find_path(world.tile_center((2, 2)), world.tile_center((20, 2)), 10_000), None);
+    }
+}
@@ -0,0 +1,269 @@
+use macroquad::prelude::*;
+
+use crate::World;
+
+/// What a ray cast with `World::raycast` struck.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RayTarget {
+    /// An impassable tile.
+    Tile {
+        /// Position of the tile in world coordinates
+        pos: Vec2,
+    },
+    /// An object.
+    Object {
+        /// Chunk coordinates of the chunk holding the object
+        chunk: (i32, i32),
+        /// Index of the object in the chunk's `objects` vector
+        index: usize,
+        /// Type tag of the object
+        type_tag: &'static str,
+    },
+}
+
+/// Result of a successful `World::raycast`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RayHit {
+    /// Point where the ray struck, in world coordinates
+    pub point: Vec2,
+    /// Distance from the ray origin to the hit point
+    pub distance: f32,
+    /// The tile or object that was struck
+    pub target: RayTarget,
+}
+
+impl World {
+    /// Casts a ray against impassable tiles and objects in visible chunks
+    /// - `origin`: Start of the ray in world coordinates
+    /// - `dir`: Direction of the ray, does not need to be normalized
+    /// - `max_dist`: Maximum distance the ray travels, must be finite
+    ///
+    /// Tiles are traversed along the tile grid of the world's config with a DDA walk and only
+    /// tiles whose `may_pass` returns `false` stop the ray. Objects are tested
+    /// by their bounding boxes.
+    ///
+    /// Returns the nearest hit, or `None` if nothing was struck within `max_dist`
+    pub fn raycast(&self, origin: Vec2, dir: Vec2, max_dist: f32) -> Option<RayHit> {
+        let dir = dir.normalize_or_zero();
+        if dir == Vec2::ZERO || max_dist <= 0.0 || !max_dist.is_finite() {
+            return None;
+        }
+
+        let tile_hit = self.raycast_tiles(origin, dir, max_dist);
+        let max_dist = tile_hit.as_ref().map_or(max_dist, |hit| hit.distance);
+        self.raycast_objects(origin, dir, max_dist).or(tile_hit)
+    }
+
+    /// Walks the tile grid along a ray until an impassable tile is found
+    /// - `origin`: Start of the ray in world coordinates
+    /// - `dir`: Normalized direction of the ray
+    /// - `max_dist`: Maximum distance the ray travels
+    fn raycast_tiles(&self, origin: Vec2, dir: Vec2, max_dist: f32) -> Option<RayHit> {
+        let tile_size = self.config.tile_size;
+        let mut tile_x = (origin.x / tile_size).floor() as i32;
+        let mut tile_y = (origin.y / tile_size).floor() as i32;
+        let step_x = if dir.x > 0.0 { 1 } else { -1 };
+        let step_y = if dir.y > 0.0 { 1 } else { -1 };
+
+        let boundary = |tile: i32, step: i32, origin: f32, dir: f32| {
+            if dir == 0.0 {
+                f32::INFINITY
+            } else {
+                let edge = if step > 0 { tile + 1 } else { tile } as f32 * tile_size;
+                (edge - origin) / dir
+            }
+        };
+        let mut t_max_x = boundary(tile_x, step_x, origin.x, dir.x);
+        let mut t_max_y = boundary(tile_y, step_y, origin.y, dir.y);
+        let t_delta_x = if dir.x == 0.0 { f32::INFINITY } else { tile_size / dir.x.abs() };
+        let t_delta_y = if dir.y == 0.0 { f32::INFINITY } else { tile_size / dir.y.abs() };
+
+        let mut distance = 0.0;
+        while distance <= max_dist {
+            let tile_pos = vec2(tile_x as f32, tile_y as f32) * tile_size;
+            if self.tile_at(tile_pos).is_some_and(|tile| !tile.may_pass()) {
+                return Some(RayHit {
+                    point: origin + dir * distance,
+                    distance,
+                    target: RayTarget::Tile { pos: tile_pos },
+                });
+            }
+
+            if t_max_x < t_max_y {
+                distance = t_max_x;
+                t_max_x += t_delta_x;
+                tile_x += step_x;
+            } else {
+                distance = t_max_y;
+                t_max_y += t_delta_y;
+                tile_y += step_y;
+            }
+        }
+        None
+    }
+
+    /// Finds the nearest object in visible chunks struck by a ray
+    /// - `origin`: Start of the ray in world coordinates
+    /// - `dir`: Normalized direction of the ray
+    /// - `max_dist`: Maximum distance the ray travels
+    fn raycast_objects(&self, origin: Vec2, dir: Vec2, max_dist: f32) -> Option<RayHit> {
+        let mut nearest: Option<RayHit> = None;
+
+        for &chunk_pos in &self.visible_chunks {
+            let Some(chunk) = self.chunks.get(&chunk_pos) else {
+                continue;
+            };
+            for (index, obj) in chunk.objects.iter().enumerate() {
+                let min = obj.get_pos();
+                let Some(t_enter) = ray_box_entry(origin, dir, min, min + obj.get_size()) else {
+                    continue;
+                };
+
+                let limit = nearest.as_ref().map_or(max_dist, |hit| hit.distance);
+                if t_enter <= limit {
+                    nearest = Some(RayHit {
+                        point: origin + dir * t_enter,
+                        distance: t_enter,
+                        target: RayTarget::Object {
+                            chunk: chunk_pos,
+                            index,
+                            type_tag: obj.get_type_tag(),
+                        },
+                    });
+                }
+            }
+        }
+        nearest
+    }
+}
+
+/// Finds where a ray enters a box
+/// - `origin`: Start of the ray
+/// - `dir`: Normalized direction of the ray
+/// - `min`: Minimum corner of the box
+/// - `max`: Maximum corner of the box
+///
+/// Axes the ray does not move along only check that the origin lies between the
+/// box's edges, so rays parallel to an edge never divide zero by zero.
+///
+/// Returns the distance along the ray at which it enters the box, `0.0` if the
+/// origin is inside it, or `None` if the ray misses it
+fn ray_box_entry(origin: Vec2, dir: Vec2, min: Vec2, max: Vec2) -> Option<f32> {
+    let mut t_enter = 0.0f32;
+    let mut t_exit = f32::INFINITY;
+    for axis in 0..2 {
+        if dir[axis] == 0.0 {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+        } else {
+            let t1 = (min[axis] - origin[axis]) / dir[axis];
+            let t2 = (max[axis] - origin[axis]) / dir[axis];
+            t_enter = t_enter.max(t1.min(t2));
+            t_exit = t_exit.min(t1.max(t2));
+        }
+    }
+    (t_enter <= t_exit).then_some(t_enter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{registries, TestObject, TestTile};
+    use crate::{Chunk, DrawBatch, Tile, TILE_SIZE};
+
+    /// A tile that stops objects and rays
+    #[derive(Clone)]
+    struct Stone {
+        pos: Vec2,
+    }
+
+    impl Tile for Stone {
+        fn get_type_tag(&self) -> &'static str {
+            "stone"
+        }
+
+        fn get_pos(&self) -> Vec2 {
+            self.pos
+        }
+
+        fn get_size(&self) -> Vec2 {
+            Vec2::splat(TILE_SIZE)
+        }
+
+        fn draw(&self, _batch: &mut DrawBatch, _pos: Vec2) {}
+
+        fn set_pos(&mut self, pos: Vec2) {
+            self.pos = pos;
+        }
+
+        fn may_pass(&self) -> bool {
+            false
+        }
+
+        fn clone_box(&self) -> Box<dyn Tile> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Creates a world with the given chunks filled with grass.
+    fn grass_world(chunks: &[(i32, i32)]) -> World {
+        let (tiles, objects, biomes) = registries();
+        let mut world = World::new("test", tiles, objects, biomes);
+        for &(x, y) in chunks {
+            let mut chunk = Chunk::new(vec2(x as f32, y as f32));
+            let (origin, size) = (chunk.bounds().0, chunk.config().chunk_size);
+            for ty in 0..size {
+                for tx in 0..size {
+                    let tile = TestTile { pos: origin + vec2(tx as f32, ty as f32) * TILE_SIZE, ..TestTile::new("grass") };
+                    chunk.tiles.push(Box::new(tile));
+                }
+            }
+            world.add_chunk(chunk);
+        }
+        world
+    }
+
+    fn world_with_wall(wall: Vec2) -> World {
+        let mut world = grass_world(&[(0, 0)]);
+        let (chunk, index) = world.get_tile_index(wall);
+        world.get_chunk_mut(chunk).unwrap().tiles[index] = Box::new(Stone { pos: wall });
+        world.visible_chunks = vec![(0, 0)];
+        world
+    }
+
+    #[test]
+    fn rays_stop_at_solid_tiles() {
+        let tile_size = TILE_SIZE;
+        let world = world_with_wall(vec2(5.0, 2.0) * tile_size);
+        let origin = vec2(8.0, 2.5 * tile_size);
+
+        let hit = world.raycast(origin, vec2(1.0, 0.0), 200.0).unwrap();
+        assert_eq!(hit.target, RayTarget::Tile { pos: vec2(5.0, 2.0) * tile_size });
+        assert_eq!(hit.point, vec2(5.0 * tile_size, origin.y));
+        assert!(world.raycast(origin, vec2(1.0, 0.0), 50.0).is_none());
+        assert!(world.raycast(origin, vec2(-1.0, 0.0), 200.0).is_none());
+    }
+
+    #[test]
+    fn rays_hit_objects_before_tiles_behind_them() {
+        let tile_size = TILE_SIZE;
+        let mut world = world_with_wall(vec2(5.0, 2.0) * tile_size);
+        world.spawn_object(Box::new(TestObject::new(vec2(40.0, 36.0)))).unwrap();
+
+        let hit = world.raycast(vec2(8.0, 40.0), vec2(1.0, 0.0), 200.0).unwrap();
+        assert_eq!(hit.target, RayTarget::Object { chunk: (0, 0), index: 0, type_tag: "test_object" });
+        assert_eq!(hit.distance, 32.0);
+    }
+
+    #[test]
+    fn rays_along_an_object_edge_hit_it() {
+        let mut world = grass_world(&[(0, 0)]);
+        world.spawn_object(Box::new(TestObject::new(vec2(40.0, 60.0)))).unwrap();
+        world.visible_chunks = vec![(0, 0)];
+
+        let hit = world.raycast(vec2(40.0, 10.0), vec2(0.0, 1.0), 200.0).unwrap();
+        assert_eq!(hit.point, vec2(40.0, 60.0));
+        assert!(world.raycast(vec2(39.0, 10.0), vec2(0.0, 1.0), 200.0).is_none());
+    }
+}
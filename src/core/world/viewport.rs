@@ -0,0 +1,57 @@
+use macroquad::prelude::*;
+
+/// The area of the world that is shown on screen.
+///
+/// `World::update` and `World::draw` assume the visible area is `screen_size`
+/// world units centered on the camera position. A viewport describes it
+/// explicitly instead, which keeps chunk visibility and tile culling correct when
+/// rendering zoomed or into a scaled render target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    /// Center of the visible area in world coordinates
+    pub center: Vec2,
+    /// Size of the visible area in screen pixels
+    pub size: Vec2,
+    /// Screen pixels per world unit; `2.0` shows half as much of the world
+    pub zoom: f32,
+}
+
+impl Viewport {
+    /// Creates a viewport without zoom
+    /// - `center`: Center of the visible area in world coordinates
+    /// - `size`: Size of the visible area in screen pixels
+    pub fn new(center: Vec2, size: Vec2) -> Self {
+        Self { center, size, zoom: 1.0 }
+    }
+
+    /// Creates a viewport matching what a macroquad camera shows
+    /// - `camera`: The camera used to draw the world
+    ///
+    /// Rotation and offset of the camera are not taken into account.
+    pub fn from_camera(camera: &Camera2D) -> Self {
+        Self::new(camera.target, vec2(2.0 / camera.zoom.x.abs(), 2.0 / camera.zoom.y.abs()))
+    }
+
+    /// Returns a copy of this viewport with a different zoom
+    /// - `zoom`: Screen pixels per world unit, must be positive
+    pub fn with_zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    /// Returns the size of the visible area in world units
+    pub fn world_size(&self) -> Vec2 {
+        if self.zoom > 0.0 {
+            self.size / self.zoom
+        } else {
+            self.size
+        }
+    }
+
+    /// Returns the visible area in world coordinates
+    pub fn bounds(&self) -> Rect {
+        let size = self.world_size();
+        let min = self.center - size / 2.0;
+        Rect::new(min.x, min.y, size.x, size.y)
+    }
+}
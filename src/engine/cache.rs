@@ -0,0 +1,101 @@
+use macroquad::prelude::*;
+use std::collections::HashMap;
+use anyhow::Result;
+
+use crate::engine::texture::load_texture_sync;
+
+/// A cache of loaded textures keyed by file path.
+///
+/// Each path is loaded and decoded only once. `Texture2D` is a cheap handle, so
+/// cache hits return clones that share the same GPU texture.
+pub struct TextureCache {
+    textures: HashMap<String, Texture2D>,
+}
+
+impl Default for TextureCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextureCache {
+    /// Creates a new, empty texture cache.
+    pub fn new() -> Self {
+        Self {
+            textures: HashMap::new(),
+        }
+    }
+
+    /// Gets the texture for a path, loading it with `load_texture_sync` on the first request.
+    ///
+    /// - `path`: The file path of the image to load.
+    ///
+    /// Returns `Result<Texture2D>` containing the cached texture, or an error if loading failed.
+    /// Failed loads are not cached, so the next request tries again.
+    pub fn get_or_load(&mut self, path: &str) -> Result<Texture2D> {
+        self.get_or_load_with(path, load_texture_sync)
+    }
+
+    /// Gets the texture for a path, loading it with the given loader on the first request.
+    ///
+    /// - `path`: The file path of the image to load.
+    /// - `load`: Loads the texture at a path.
+    fn get_or_load_with(&mut self, path: &str, load: impl FnOnce(&str) -> Result<Texture2D>) -> Result<Texture2D> {
+        if let Some(texture) = self.textures.get(path) {
+            return Ok(texture.clone());
+        }
+
+        let texture = load(path)?;
+        self.textures.insert(path.to_string(), texture.clone());
+        Ok(texture)
+    }
+
+    /// Gets the texture for a path if it has already been loaded.
+    ///
+    /// - `path`: The file path the texture was loaded from.
+    pub fn get(&self, path: &str) -> Option<Texture2D> {
+        self.textures.get(path).cloned()
+    }
+
+    /// Returns the number of cached textures.
+    pub fn len(&self) -> usize {
+        self.textures.len()
+    }
+
+    /// Checks whether the cache holds no textures.
+    pub fn is_empty(&self) -> bool {
+        self.textures.is_empty()
+    }
+
+    /// Removes all cached textures.
+    ///
+    /// Textures still referenced elsewhere stay alive until those handles are dropped.
+    pub fn clear(&mut self) {
+        self.textures.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::miniquad::{RawId, TextureId};
+
+    #[test]
+    fn repeated_requests_load_once() {
+        let mut cache = TextureCache::new();
+        let mut loads = 0;
+        let mut load = |_: &str| {
+            loads += 1;
+            Ok(Texture2D::from_miniquad_texture(TextureId::from_raw_id(RawId::OpenGl(1))))
+        };
+
+        for _ in 0..3 {
+            cache.get_or_load_with("tiles.png", &mut load).unwrap();
+        }
+        assert!(cache.get_or_load_with("missing.png", |_| Err(anyhow::anyhow!("missing"))).is_err());
+        cache.get_or_load_with("missing.png", &mut load).unwrap();
+
+        assert_eq!(loads, 2);
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -0,0 +1,474 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use macroquad::prelude::*;
+use serde::Deserialize;
+
+use crate::{log_world, Chunk, ObjectRegistry, TileRegistry, WorldConfig};
+
+/// Maps names used in an LDtk project to the type tags of registered tiles and objects.
+///
+/// Keys are looked up as follows:
+/// - IntGrid cells: the identifier of the IntGrid value, or `"<layer>:<value>"` for
+///   values without an identifier.
+/// - Tiles and AutoLayer tiles: `"<tileset>:<tile id>"`, using the identifier of the tileset.
+/// - Entities: the identifier of the entity.
+///
+/// Anything without a mapping is skipped.
+#[derive(Debug, Clone)]
+pub struct LdtkMapping {
+    /// Type tag of the tile used for cells no layer provides a tile for
+    fill: String,
+    /// LDtk tile names to tile type tags
+    tiles: HashMap<String, String>,
+    /// LDtk entity identifiers to object type tags
+    objects: HashMap<String, String>,
+}
+
+impl LdtkMapping {
+    /// Creates an empty mapping.
+    ///
+    /// - `fill`: Type tag of the tile placed where no layer provides a tile. Chunks
+    ///   always hold a full grid of tiles, so levels that do not line up with chunk
+    ///   borders are padded with it.
+    pub fn new(fill: &str) -> Self {
+        Self {
+            fill: fill.to_string(),
+            tiles: HashMap::new(),
+            objects: HashMap::new(),
+        }
+    }
+
+    /// Maps an IntGrid value or tileset tile to a tile type.
+    ///
+    /// - `name`: The LDtk name, see the type documentation for its format.
+    /// - `type_tag`: Type tag of the registered tile to create.
+    pub fn with_tile(mut self, name: &str, type_tag: &str) -> Self {
+        self.tiles.insert(name.to_string(), type_tag.to_string());
+        self
+    }
+
+    /// Maps an entity to an object type.
+    ///
+    /// - `identifier`: Identifier of the LDtk entity.
+    /// - `type_tag`: Type tag of the registered object to create.
+    pub fn with_object(mut self, identifier: &str, type_tag: &str) -> Self {
+        self.objects.insert(identifier.to_string(), type_tag.to_string());
+        self
+    }
+}
+
+/// Root of an LDtk project file.
+#[derive(Deserialize)]
+struct LdtkProject {
+    defs: LdtkDefs,
+    levels: Vec<LdtkLevel>,
+    /// `Free`, `GridVania`, `LinearHorizontal` or `LinearVertical`
+    #[serde(rename = "worldLayout", default)]
+    world_layout: Option<String>,
+}
+
+/// Definitions shared by all levels of a project.
+#[derive(Deserialize)]
+struct LdtkDefs {
+    layers: Vec<LdtkLayerDef>,
+    tilesets: Vec<LdtkTilesetDef>,
+}
+
+/// Definition of a layer, used to name IntGrid values.
+#[derive(Deserialize)]
+struct LdtkLayerDef {
+    uid: i64,
+    #[serde(rename = "intGridValues", default)]
+    int_grid_values: Vec<LdtkIntGridValue>,
+}
+
+/// A named value of an IntGrid layer.
+#[derive(Deserialize)]
+struct LdtkIntGridValue {
+    value: i64,
+    identifier: Option<String>,
+}
+
+/// Definition of a tileset, used to name tiles.
+#[derive(Deserialize)]
+struct LdtkTilesetDef {
+    uid: i64,
+    identifier: String,
+}
+
+/// A level with its layers, or a reference to the file holding them.
+#[derive(Deserialize)]
+struct LdtkLevel {
+    identifier: String,
+    #[serde(rename = "worldX")]
+    world_x: i64,
+    #[serde(rename = "worldY")]
+    world_y: i64,
+    #[serde(rename = "layerInstances")]
+    layer_instances: Option<Vec<LdtkLayer>>,
+    #[serde(rename = "externalRelPath")]
+    external_rel_path: Option<String>,
+}
+
+/// A layer instance of a level.
+#[derive(Deserialize)]
+struct LdtkLayer {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__type")]
+    layer_type: String,
+    #[serde(rename = "__cWid")]
+    c_wid: i64,
+    #[serde(rename = "__gridSize")]
+    grid_size: i64,
+    #[serde(rename = "__tilesetDefUid")]
+    tileset_def_uid: Option<i64>,
+    #[serde(rename = "layerDefUid")]
+    layer_def_uid: i64,
+    #[serde(rename = "pxTotalOffsetX", default)]
+    px_offset_x: i64,
+    #[serde(rename = "pxTotalOffsetY", default)]
+    px_offset_y: i64,
+    #[serde(rename = "intGridCsv", default)]
+    int_grid_csv: Vec<i64>,
+    #[serde(rename = "gridTiles", default)]
+    grid_tiles: Vec<LdtkTile>,
+    #[serde(rename = "autoLayerTiles", default)]
+    auto_layer_tiles: Vec<LdtkTile>,
+    #[serde(rename = "entityInstances", default)]
+    entity_instances: Vec<LdtkEntity>,
+}
+
+/// A tile of a Tiles or AutoLayer layer, positioned in layer pixels.
+#[derive(Deserialize)]
+struct LdtkTile {
+    px: [i64; 2],
+    t: i64,
+}
+
+/// An entity instance of an Entities layer.
+#[derive(Deserialize)]
+struct LdtkEntity {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__pivot")]
+    pivot: [f32; 2],
+    px: [i64; 2],
+    width: i64,
+    height: i64,
+}
+
+/// Tiles and objects of one level, collected before they are split into chunks.
+struct LevelBuilder {
+    /// Tile type tags per world tile coordinate, bottom layer first
+    cells: HashMap<(i32, i32), Vec<String>>,
+    /// Objects with their world position and size
+    objects: Vec<(String, Vec2, Vec2)>,
+}
+
+/// Loads every level of an LDtk project as chunks.
+///
+/// - `path`: Path to the `.ldtk` project file. Levels saved as separate files are
+///   loaded relative to it.
+/// - `tile_registry`: Registry the mapped tile types are created from.
+/// - `object_registry`: Registry the mapped object types are created from.
+/// - `mapping`: Translation of LDtk names to type tags.
+/// - `config`: Tile and chunk size of the world the chunks are added to, e.g. `World::config`.
+///
+/// Handled layer types:
+/// - `IntGrid`: each cell value becomes a tile. Auto-layer tiles generated from the
+///   grid are ignored in favor of the values.
+/// - `Tiles` and `AutoLayer`: each tile becomes a tile.
+/// - `Entities`: each entity instance becomes an object, positioned by its top-left
+///   corner and sized to its width and height.
+///
+/// LDtk cells are mapped one to one onto tiles, scaling positions by
+/// `config.tile_size / grid size`. For each cell, the tile of the lowest layer becomes the
+/// chunk tile and tiles of the layers above become overlays, numbered from 1 upwards.
+/// Levels of `Free` and `GridVania` layouts keep their positions relative to each
+/// other, shifted so the top-left corner of the leftmost and topmost level lands at
+/// the origin. Levels of linear layouts have no position and are all placed at the
+/// origin. Chunks are laid out with `config`,
+/// so they can be added to a world using the same config.
+///
+/// Returns the chunks of each level keyed by the level identifier, or an error
+/// message if the file cannot be read or parsed, a mapped type is not registered or
+/// a cell has more layers than overlays can be numbered with
+pub fn load_level(
+    path: &str,
+    tile_registry: &TileRegistry,
+    object_registry: &ObjectRegistry,
+    mapping: &LdtkMapping,
+    config: WorldConfig,
+) -> Result<HashMap<String, Vec<Chunk>>, String> {
+    config.validate()?;
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read LDtk project {}: {}", path, e))?;
+    let project: LdtkProject = serde_json::from_str(&json)
+        .map_err(|e| format!("Failed to parse LDtk project {}: {}", path, e))?;
+    let base_dir = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+
+    let mut project_levels = Vec::with_capacity(project.levels.len());
+    for mut level in project.levels {
+        if level.layer_instances.is_none() {
+            if let Some(rel_path) = &level.external_rel_path {
+                let level_path = base_dir.join(rel_path);
+                let json = std::fs::read_to_string(&level_path)
+                    .map_err(|e| format!("Failed to read LDtk level {}: {}", level_path.display(), e))?;
+                level = serde_json::from_str(&json)
+                    .map_err(|e| format!("Failed to parse LDtk level {}: {}", level_path.display(), e))?;
+            }
+        }
+        project_levels.push(level);
+    }
+
+    // Linear layouts store -1 as the position of every level
+    let linear = matches!(project.world_layout.as_deref(), Some("LinearHorizontal" | "LinearVertical"));
+    let min_x = project_levels.iter().map(|level| level.world_x).min().unwrap_or(0);
+    let min_y = project_levels.iter().map(|level| level.world_y).min().unwrap_or(0);
+
+    let mut levels = HashMap::new();
+    for level in project_levels {
+        let level_pos = if linear { (0, 0) } else { (level.world_x - min_x, level.world_y - min_y) };
+        let builder = collect_level(&project.defs, &level, level_pos, mapping, config.tile_size);
+        let chunks = build_chunks(builder, tile_registry, object_registry, mapping, config)?;
+        log_world!(log::Level::Info, "Imported LDtk level {} with {} chunks", level.identifier, chunks.len());
+        levels.insert(level.identifier, chunks);
+    }
+    Ok(levels)
+}
+
+/// Collects the mapped tiles and objects of a level in world tile coordinates.
+///
+/// - `(level_x, level_y)`: Position of the level's top-left corner in LDtk pixels.
+fn collect_level(defs: &LdtkDefs, level: &LdtkLevel, (level_x, level_y): (i64, i64), mapping: &LdtkMapping, tile_size: f32) -> LevelBuilder {
+    let mut builder = LevelBuilder {
+        cells: HashMap::new(),
+        objects: Vec::new(),
+    };
+
+    // LDtk lists layers from the top down
+    for layer in level.layer_instances.iter().flatten().rev() {
+        let grid = layer.grid_size.max(1);
+        let scale = tile_size / grid as f32;
+        let origin_x = level_x + layer.px_offset_x;
+        let origin_y = level_y + layer.px_offset_y;
+        let cell_at = |px: [i64; 2]| {
+            (
+                (origin_x + px[0]).div_euclid(grid) as i32,
+                (origin_y + px[1]).div_euclid(grid) as i32,
+            )
+        };
+        let mut layer_cells: HashMap<(i32, i32), String> = HashMap::new();
+
+        match layer.layer_type.as_str() {
+            "IntGrid" => {
+                let names = defs.layers
+                    .iter()
+                    .find(|def| def.uid == layer.layer_def_uid)
+                    .map(|def| &def.int_grid_values);
+                let width = layer.c_wid.max(1);
+                for (index, &value) in layer.int_grid_csv.iter().enumerate() {
+                    if value == 0 {
+                        continue;
+                    }
+                    let name = names
+                        .and_then(|values| values.iter().find(|v| v.value == value))
+                        .and_then(|v| v.identifier.clone())
+                        .unwrap_or_else(|| format!("{}:{}", layer.identifier, value));
+                    if let Some(type_tag) = mapping.tiles.get(&name) {
+                        let px = [(index as i64 % width) * grid, (index as i64 / width) * grid];
+                        layer_cells.insert(cell_at(px), type_tag.clone());
+                    }
+                }
+            }
+            "Tiles" | "AutoLayer" => {
+                let tileset = layer.tileset_def_uid
+                    .and_then(|uid| defs.tilesets.iter().find(|tileset| tileset.uid == uid))
+                    .map_or("", |tileset| tileset.identifier.as_str());
+                for tile in layer.grid_tiles.iter().chain(&layer.auto_layer_tiles) {
+                    if let Some(type_tag) = mapping.tiles.get(&format!("{}:{}", tileset, tile.t)) {
+                        layer_cells.insert(cell_at(tile.px), type_tag.clone());
+                    }
+                }
+            }
+            "Entities" => {
+                for entity in &layer.entity_instances {
+                    let Some(type_tag) = mapping.objects.get(&entity.identifier) else {
+                        continue;
+                    };
+                    let size = vec2(entity.width as f32, entity.height as f32);
+                    let pos = vec2((origin_x + entity.px[0]) as f32, (origin_y + entity.px[1]) as f32)
+                        - size * Vec2::from(entity.pivot);
+                    builder.objects.push((type_tag.clone(), pos * scale, size * scale));
+                }
+            }
+            other => {
+                log_world!(log::Level::Warn, "Skipping LDtk layer {} of unsupported type {}", layer.identifier, other);
+            }
+        }
+
+        for (cell, type_tag) in layer_cells {
+            builder.cells.entry(cell).or_default().push(type_tag);
+        }
+    }
+    builder
+}
+
+/// Turns the collected tiles and objects of a level into filled chunks.
+fn build_chunks(
+    builder: LevelBuilder,
+    tile_registry: &TileRegistry,
+    object_registry: &ObjectRegistry,
+    mapping: &LdtkMapping,
+    config: WorldConfig,
+) -> Result<Vec<Chunk>, String> {
+    let chunk_size = config.chunk_size;
+    let chunk_of = |tile: (i32, i32)| {
+        (tile.0.div_euclid(chunk_size as i32), tile.1.div_euclid(chunk_size as i32))
+    };
+    let tile_of = |pos: Vec2| ((pos.x / config.tile_size).floor() as i32, (pos.y / config.tile_size).floor() as i32);
+    let new_chunk = |chunk_pos: (i32, i32)| Chunk::with_config(vec2(chunk_pos.0 as f32, chunk_pos.1 as f32), config);
+
+    let mut chunks: HashMap<(i32, i32), Chunk> = HashMap::new();
+    for chunk_pos in builder.cells.keys().map(|&cell| chunk_of(cell)) {
+        chunks.entry(chunk_pos).or_insert_with(|| new_chunk(chunk_pos));
+    }
+    for (_, pos, _) in &builder.objects {
+        let chunk_pos = chunk_of(tile_of(*pos));
+        chunks.entry(chunk_pos).or_insert_with(|| new_chunk(chunk_pos));
+    }
+
+    let create_tile = |type_tag: &str| {
+        tile_registry
+            .create_tile_by_id(type_tag)
+            .ok_or_else(|| format!("Unknown tile type: {}", type_tag))
+    };
+
+    for (&(chunk_x, chunk_y), chunk) in chunks.iter_mut() {
+        for y in 0..chunk_size {
+            for x in 0..chunk_size {
+                let cell = (chunk_x * chunk_size as i32 + x as i32, chunk_y * chunk_size as i32 + y as i32);
+                let layers = builder.cells.get(&cell).map(Vec::as_slice).unwrap_or_default();
+                let ground = layers.first().map_or(mapping.fill.as_str(), String::as_str);
+                chunk.set_tile(x, y, create_tile(ground)?)?;
+                for (layer, type_tag) in layers.iter().enumerate().skip(1) {
+                    let layer = u8::try_from(layer)
+                        .map_err(|_| format!("Cell ({}, {}) has more than {} overlay layers", cell.0, cell.1, u8::MAX))?;
+                    chunk.add_overlay(x, y, layer, create_tile(type_tag)?)?;
+                }
+            }
+        }
+    }
+
+    for (type_tag, pos, size) in builder.objects {
+        let mut obj = object_registry
+            .create_object_by_id(&type_tag)
+            .ok_or_else(|| format!("Unknown object type: {}", type_tag))?;
+        obj.set_pos(pos);
+        obj.set_size(size);
+        let chunk_pos = chunk_of(tile_of(pos));
+        if let Some(chunk) = chunks.get_mut(&chunk_pos) {
+            chunk.objects.push(obj);
+        }
+    }
+
+    let mut chunks: Vec<Chunk> = chunks.into_values().collect();
+    chunks.sort_by(|a, b| (a.pos.y, a.pos.x).partial_cmp(&(b.pos.y, b.pos.x)).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{registries, TestTile};
+    use crate::Chunk;
+
+    /// Project with a "West" level holding an IntGrid layer and a "Start" level one
+    /// level width further right holding an entity, a tile and IntGrid values.
+    fn project(world_layout: &str, west_x: i64) -> String {
+        let start_x = if world_layout.starts_with("Linear") { -1 } else { west_x + 64 };
+        format!(r#"{{
+            "worldLayout": "{world_layout}",
+            "defs": {{
+                "layers": [{{ "uid": 1, "intGridValues": [{{ "value": 1, "identifier": "wall" }}] }}],
+                "tilesets": [{{ "uid": 2, "identifier": "Terrain" }}]
+            }},
+            "levels": [
+                {{
+                    "identifier": "West", "worldX": {west_x}, "worldY": 0,
+                    "layerInstances": [
+                        {{ "__identifier": "Walls", "__type": "IntGrid", "__cWid": 2, "__gridSize": 16, "layerDefUid": 1,
+                           "intGridCsv": [1, 1, 1, 1] }}
+                    ]
+                }},
+                {{
+                    "identifier": "Start", "worldX": {start_x}, "worldY": 0,
+                    "layerInstances": [
+                        {{ "__identifier": "Entities", "__type": "Entities", "__cWid": 2, "__gridSize": 16, "layerDefUid": 3,
+                           "entityInstances": [{{ "__identifier": "Mob", "__pivot": [0.5, 1.0], "px": [24, 40], "width": 16, "height": 16 }}] }},
+                        {{ "__identifier": "Decor", "__type": "Tiles", "__cWid": 2, "__gridSize": 16, "__tilesetDefUid": 2, "layerDefUid": 4,
+                           "gridTiles": [{{ "px": [0, 0], "t": 5 }}] }},
+                        {{ "__identifier": "Walls", "__type": "IntGrid", "__cWid": 2, "__gridSize": 16, "layerDefUid": 1,
+                           "intGridCsv": [1, 0, 0, 1] }}
+                    ]
+                }}
+            ]
+        }}"#)
+    }
+
+    fn load(name: &str, json: &str) -> Result<HashMap<String, Vec<Chunk>>, String> {
+        let path = std::env::temp_dir().join(format!("gaymwtf-core-{}-{}.ldtk", name, std::process::id()));
+        std::fs::write(&path, json).unwrap();
+        let (mut tiles, objects, _) = registries();
+        tiles.register(TestTile::new("stone"));
+        tiles.register(TestTile::new("flower"));
+        let mapping = LdtkMapping::new("grass")
+            .with_tile("wall", "stone")
+            .with_tile("Terrain:5", "flower")
+            .with_object("Mob", "test_object");
+        let config = WorldConfig { chunk_size: 4, ..Default::default() };
+        let levels = load_level(path.to_str().unwrap(), &tiles, &objects, &mapping, config);
+        let _ = std::fs::remove_file(&path);
+        levels
+    }
+
+    fn tag_at(chunk: &Chunk, x: usize, y: usize) -> &'static str {
+        chunk.tiles[y * chunk.config().chunk_size + x].get_type_tag()
+    }
+
+    #[test]
+    fn cells_map_to_tiles_and_overlays() {
+        let levels = load("ldtk-tiles", &project("Free", 0)).unwrap();
+        let start = &levels["Start"];
+        assert_eq!(start.len(), 1);
+        let chunk = &start[0];
+        assert_eq!(chunk.pos, vec2(1.0, 0.0));
+        assert_eq!([tag_at(chunk, 0, 0), tag_at(chunk, 1, 0), tag_at(chunk, 1, 1)], ["stone", "grass", "stone"]);
+        let overlays: Vec<_> = chunk.overlays_at(0, 0).into_iter().map(|(layer, tile)| (layer, tile.get_type_tag())).collect();
+        assert_eq!(overlays, vec![(1, "flower")]);
+    }
+
+    #[test]
+    fn entities_spawn_at_their_pivot() {
+        let levels = load("ldtk-entities", &project("Free", 0)).unwrap();
+        let objects = &levels["Start"][0].objects;
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].get_type_tag(), "test_object");
+        assert_eq!(objects[0].get_pos(), vec2(64.0 + 16.0, 24.0));
+        assert_eq!(objects[0].get_size(), vec2(16.0, 16.0));
+    }
+
+    #[test]
+    fn levels_at_negative_positions_keep_their_offsets() {
+        let levels = load("ldtk-negative", &project("GridVania", -64)).unwrap();
+        assert_eq!(levels["West"][0].pos, vec2(0.0, 0.0));
+        assert_eq!(tag_at(&levels["West"][0], 1, 1), "stone");
+        assert_eq!(levels["Start"][0].pos, vec2(1.0, 0.0));
+
+        let levels = load("ldtk-linear", &project("LinearHorizontal", -1)).unwrap();
+        assert_eq!(levels["West"][0].pos, vec2(0.0, 0.0));
+        assert_eq!(levels["Start"][0].pos, vec2(0.0, 0.0));
+    }
+}
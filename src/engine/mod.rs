@@ -1 +1,3 @@
+pub mod cache;
+pub mod ldtk;
 pub mod texture;
@@ -2,6 +2,7 @@ use macroquad::prelude::*;
 use std::fs;
 use image;
 use anyhow::{Context, Result};
+use crate::log_render;
 
 /// Loads a file from the given path synchronously.
 ///
@@ -19,7 +20,71 @@ pub fn load_file_sync(path: &str) -> Result<Vec<u8>> {
 /// Returns `Result<Texture2D>` containing the loaded texture on success, or an error on failure.
 pub fn load_texture_sync(path: &str) -> Result<Texture2D> {
     let bytes = load_file_sync(path)?;
-    let image = image::load_from_memory(&bytes)
+    texture_from_bytes(&bytes, path)
+}
+
+/// Loads a texture from an image file asynchronously.
+///
+/// The file is read through macroquad's async file API, so this must be awaited
+/// within the macroquad main loop. The texture uses the `Nearest` filter, like
+/// `load_texture_sync`.
+///
+/// - `path`: The file path of the image to load.
+///
+/// Returns `Result<Texture2D>` containing the loaded texture on success, or an error on failure.
+pub async fn load_texture(path: &str) -> Result<Texture2D> {
+    let bytes = load_file(path)
+        .await
+        .with_context(|| format!("Failed to read file: {}", path))?;
+    texture_from_bytes(&bytes, path)
+}
+
+/// Loads a texture from an image file synchronously, falling back to a placeholder.
+///
+/// If the file cannot be loaded, the error is logged and the texture from
+/// `placeholder_texture` is returned instead, so one missing asset does not
+/// stop the game.
+///
+/// - `path`: The file path of the image to load.
+///
+/// Returns the loaded texture, or the placeholder texture on failure.
+pub fn load_texture_or_placeholder(path: &str) -> Texture2D {
+    load_texture_sync(path).unwrap_or_else(|e| {
+        log_render!(log::Level::Error, "Using placeholder texture for {}: {:#}", path, e);
+        placeholder_texture()
+    })
+}
+
+/// Creates a magenta and black checkerboard texture marking a missing texture.
+///
+/// Returns a 16x16 texture with the `Nearest` filter.
+pub fn placeholder_texture() -> Texture2D {
+    const SIZE: u16 = 16;
+    const CHECK: u16 = SIZE / 2;
+
+    let mut pixels = Vec::with_capacity(SIZE as usize * SIZE as usize * 4);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let magenta = (x / CHECK + y / CHECK).is_multiple_of(2);
+            if magenta {
+                pixels.extend_from_slice(&[255, 0, 255, 255]);
+            } else {
+                pixels.extend_from_slice(&[0, 0, 0, 255]);
+            }
+        }
+    }
+
+    let texture = Texture2D::from_rgba8(SIZE, SIZE, &pixels);
+    texture.set_filter(FilterMode::Nearest);
+    texture
+}
+
+/// Decodes image bytes into a texture with the `Nearest` filter.
+///
+/// - `bytes`: The encoded image.
+/// - `path`: The file path the bytes were read from, used in error messages.
+fn texture_from_bytes(bytes: &[u8], path: &str) -> Result<Texture2D> {
+    let image = image::load_from_memory(bytes)
         .with_context(|| format!("Failed to decode image from file: {}", path))?;
     let rgba_image = image.to_rgba8();
     let (width, height) = rgba_image.dimensions();
@@ -27,3 +92,64 @@ pub fn load_texture_sync(path: &str) -> Result<Texture2D> {
     texture.set_filter(FilterMode::Nearest);
     Ok(texture)
 }
+
+/// Splits a sprite sheet into frames of a fixed size.
+///
+/// Pixels left over at the right or bottom edge when the sheet size is not an
+/// exact multiple of the frame size are ignored and a warning is logged.
+///
+/// - `texture`: The sprite sheet texture.
+/// - `frame_w`: Width of a single frame in pixels.
+/// - `frame_h`: Height of a single frame in pixels.
+///
+/// Returns the source rectangles of all frames in row-major order, or an empty
+/// list if either frame dimension is zero.
+pub fn split_sheet(texture: &Texture2D, frame_w: u16, frame_h: u16) -> Vec<Rect> {
+    let (columns, rows) = sheet_grid(texture, frame_w, frame_h);
+    let mut frames = Vec::with_capacity((columns * rows) as usize);
+
+    for row in 0..rows {
+        for column in 0..columns {
+            frames.push(Rect::new(
+                (column * frame_w as u32) as f32,
+                (row * frame_h as u32) as f32,
+                frame_w as f32,
+                frame_h as f32,
+            ));
+        }
+    }
+
+    frames
+}
+
+/// Counts the frames `split_sheet` would return for a sprite sheet.
+///
+/// - `texture`: The sprite sheet texture.
+/// - `frame_w`: Width of a single frame in pixels.
+/// - `frame_h`: Height of a single frame in pixels.
+///
+/// Returns the number of whole frames in the sheet.
+pub fn sheet_frame_count(texture: &Texture2D, frame_w: u16, frame_h: u16) -> usize {
+    let (columns, rows) = sheet_grid(texture, frame_w, frame_h);
+    (columns * rows) as usize
+}
+
+/// Returns the number of whole frame columns and rows in a sprite sheet.
+fn sheet_grid(texture: &Texture2D, frame_w: u16, frame_h: u16) -> (u32, u32) {
+    if frame_w == 0 || frame_h == 0 {
+        log_render!(log::Level::Warn, "Cannot split sprite sheet into {}x{} frames", frame_w, frame_h);
+        return (0, 0);
+    }
+
+    let width = texture.width() as u32;
+    let height = texture.height() as u32;
+    if !width.is_multiple_of(frame_w as u32) || !height.is_multiple_of(frame_h as u32) {
+        log_render!(
+            log::Level::Warn,
+            "Sprite sheet of {}x{} is not a multiple of {}x{} frames, ignoring the remainder",
+            width, height, frame_w, frame_h
+        );
+    }
+
+    (width / frame_w as u32, height / frame_h as u32)
+}
@@ -2,18 +2,23 @@ pub mod core;
 pub mod engine;
 pub mod utils;
 
-pub use crate::core::world::{World, WorldData};
+pub use crate::core::world::{World, WorldData, WorldStats, SortMode, RayHit, RayTarget, Event, EventTarget, LightMap, Viewport};
 pub use crate::core::chunk::{Chunk, ChunkData};
-pub use crate::core::tile::{Tile, TileData, TileRegistry, SerializableTile};
-pub use crate::core::object::{Object, ObjectData, ObjectRegistry, SerializableObject, Direction};
-pub use crate::core::biome::{Biome, BiomeRegistry};
-pub use crate::core::save::{Vec2Save};
-pub use crate::core::ui::{Button, Label, MenuAction, Menu, Element, ButtonState};
+pub use crate::core::tile::{Tile, TileData, TileRegistry, SerializableTile, TileAnimation, animated_source_rect,
+    NEIGHBOR_UP, NEIGHBOR_UP_RIGHT, NEIGHBOR_RIGHT, NEIGHBOR_DOWN_RIGHT, NEIGHBOR_DOWN, NEIGHBOR_DOWN_LEFT, NEIGHBOR_LEFT, NEIGHBOR_UP_LEFT};
+pub use crate::core::object::{Object, ObjectData, ObjectRegistry, SerializableObject, ObjectId, Direction, Facing, PhysicsBody, resolve_collision};
+pub use crate::core::biome::{Biome, BiomeRegistry, BiomeBounds};
+pub use crate::core::generator::{WorldGenerator, GenContext};
+pub use crate::core::save::{Vec2Save, RectSave, ColorSave, SaveFormat, SAVE_VERSION};
+pub use crate::core::ui::{Button, Checkbox, FocusGroup, Label, Alignment, Panel, LayoutDirection, ScrollList, MenuAction, Menu, Element, ButtonState, ButtonStyle};
 
-pub use crate::engine::texture::{load_file_sync, load_texture_sync};
+pub use crate::engine::cache::TextureCache;
+pub use crate::engine::ldtk::LdtkMapping;
+pub use crate::engine::texture::{load_file_sync, load_texture_sync, load_texture, load_texture_or_placeholder, placeholder_texture, split_sheet, sheet_frame_count};
 
-pub use crate::utils::draw::DrawBatch;
-pub use crate::utils::logger::GameLogger;
+pub use crate::utils::draw::{DrawBatch, DrawParams};
+pub use crate::utils::logger::{GameLogger, LogBuffer, LogFormat, LogLevels, LogOutput, LogThrottle};
+pub use crate::utils::rand::{hash_to_unit, SeededRng};
 
-pub use crate::utils::settings::{TILE_SIZE, CHUNK_SIZE, CHUNK_PIXELS, OBJECT_ACTIVATION_MARGIN};
+pub use crate::utils::settings::{WorldConfig, TILE_SIZE, CHUNK_SIZE, CHUNK_PIXELS, OBJECT_ACTIVATION_MARGIN, OBJECT_INDEX_CELL_SIZE, OBJECT_INDEX_THRESHOLD, COLLISION_CELL_SIZE, COLLISION_BUFFER, LIGHT_FALLOFF};
 
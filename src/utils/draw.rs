@@ -1,11 +1,185 @@
-use macroquad::{color, math::Vec2, texture::{draw_texture_ex, DrawTextureParams, Texture2D}};
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use macroquad::{
+    color::{self, Color},
+    miniquad::TextureId,
+    math::{Rect, Vec2},
+    shapes::{draw_line, draw_rectangle, draw_rectangle_lines},
+    text::{draw_text_ex, Font, TextParams},
+    texture::{draw_texture_ex, DrawTextureParams, Texture2D},
+    window::{get_internal_gl, screen_dpi_scale},
+};
 use crate::log_render;
 
+/// Per-instance drawing parameters for `DrawBatch::add_ex`.
+#[derive(Clone, Debug)]
+pub struct DrawParams {
+    /// The size scale factor for the texture.
+    pub size: f32,
+    /// Optional destination size for the texture.
+    pub dest_size: Option<Vec2>,
+    /// Color the texture is tinted with. White draws the texture unchanged.
+    pub color: Color,
+    /// Rotation of the texture in radians.
+    pub rotation: f32,
+    /// Whether the texture is mirrored horizontally.
+    pub flip_x: bool,
+    /// Whether the texture is mirrored vertically.
+    pub flip_y: bool,
+    /// Point to rotate around in world coordinates. Defaults to the center of the texture.
+    pub pivot: Option<Vec2>,
+    /// Region of the texture to draw, in texture pixels. Draws the whole texture when `None`.
+    pub source: Option<Rect>,
+    /// Draw-order layer. Instances on lower layers are drawn under those on higher ones.
+    pub layer: i32,
+}
+
+impl Default for DrawParams {
+    fn default() -> Self {
+        Self {
+            size: 1.0,
+            dest_size: None,
+            color: color::WHITE,
+            rotation: 0.0,
+            flip_x: false,
+            flip_y: false,
+            pivot: None,
+            source: None,
+            layer: 0,
+        }
+    }
+}
+
+/// A single queued texture instance: position and drawing parameters.
+type Instance = (Vec2, DrawParams);
+
+/// A single queued untextured draw.
+#[derive(Clone)]
+enum Primitive {
+    /// A solid rectangle.
+    Rect { rect: Rect, color: Color },
+    /// The outline of a rectangle.
+    RectLines { rect: Rect, thickness: f32, color: Color },
+    /// A straight line between two points.
+    Line { a: Vec2, b: Vec2, thickness: f32, color: Color },
+    /// A line of text, positioned at its baseline.
+    Text { text: String, pos: Vec2, font_size: u16, font: Option<Font>, color: Color },
+    /// A change of the clipping rectangle, `None` to stop clipping.
+    Clip { rect: Option<Rect> },
+}
+
+/// Hasher for texture ids in the group lookup.
+///
+/// Texture ids are a few small integers, so they are mixed with a multiply and
+/// rotate instead of the default hasher, which is built to resist crafted keys
+/// and costs about as much as searching 50 groups one by one.
+#[derive(Default)]
+struct TextureIdHasher(u64);
+
+impl TextureIdHasher {
+    fn mix(&mut self, value: u64) {
+        self.0 = (self.0.rotate_left(5) ^ value).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+}
+
+impl Hasher for TextureIdHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.mix(byte as u64);
+        }
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.mix(value as u64);
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.mix(value);
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.mix(value as u64);
+    }
+
+    fn write_isize(&mut self, value: isize) {
+        self.mix(value as u64);
+    }
+}
+
+/// Texture instances of one run, grouped by texture.
+#[derive(Default)]
+struct TextureRun {
+    /// Groups in order of the first instance of each texture.
+    groups: Vec<(Texture2D, Vec<Instance>)>,
+    /// Index into `groups` for each texture's `raw_miniquad_id`.
+    lookup: HashMap<TextureId, usize, BuildHasherDefault<TextureIdHasher>>,
+    /// Distinct layers of the queued instances, in ascending order.
+    layers: Vec<i32>,
+    /// Texture of the most recently queued instance.
+    last: Option<TextureId>,
+}
+
+impl TextureRun {
+    /// Returns whether an instance of the given texture can join this run.
+    ///
+    /// - `texture`: The texture of the instance.
+    /// - `ordered`: Whether the batch keeps instances in insertion order.
+    ///
+    /// In ordered mode only an instance of the same texture as the last one joins,
+    /// since grouping it with an earlier instance would draw it out of order.
+    fn accepts(&self, texture: TextureId, ordered: bool) -> bool {
+        !ordered || self.last.is_none_or(|last| last == texture)
+    }
+}
+
+/// A run of consecutively queued draws of the same kind.
+enum Segment {
+    /// Texture instances grouped by texture.
+    Textures(TextureRun),
+    /// Untextured draws in insertion order.
+    Primitives(Vec<Primitive>),
+}
+
 /// A batch for efficient drawing of multiple instances of textures.
 ///
 /// This struct groups draw calls by texture to minimize state changes and improve rendering performance.
+/// Solid rectangles, outlines, lines and text can be queued as well, so UI can be drawn through the same batch.
+///
+/// Draws are flushed in insertion order. Consecutively added texture instances form one
+/// run that is grouped by texture, so a primitive added between two textures splits
+/// the grouping there.
+///
+/// Within such a run, instances can be ordered with `DrawParams::layer`: lower
+/// layers are drawn first, and instances on the same layer keep the usual order.
+/// Sorting by layer only happens when a run holds more than one layer, so a batch
+/// that leaves every instance on the default layer 0 draws exactly as before.
+///
+/// In ordered mode (`set_ordered`), texture instances are drawn in exactly the order
+/// they were added: consecutive instances of the same texture still share a group,
+/// and a change of texture starts a new run.
+///
+/// Allocations are kept across `draw` and `clear`, so a batch that is reused every
+/// frame stops allocating once it has grown to the size of a typical frame. Only
+/// queued text still allocates, for its copy of the string.
 pub struct DrawBatch {
-    textures: Vec<(Texture2D, Vec<(Vec2, f32, Option<Vec2>)>)>,
+    segments: Vec<Segment>,
+    /// Number of segments in use; the rest are kept for their allocations
+    active: usize,
+    /// Cleared instance lists kept for reuse by new texture groups
+    spare: Vec<Vec<Instance>>,
+    /// Whether a change of texture starts a new run
+    ordered: bool,
+}
+
+impl Default for DrawBatch {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DrawBatch {
@@ -13,8 +187,67 @@ impl DrawBatch {
     pub fn new() -> Self {
         log_render!(log::Level::Trace, "Creating new DrawBatch");
         Self {
-            textures: Vec::new(),
+            segments: Vec::new(),
+            active: 0,
+            spare: Vec::new(),
+            ordered: false,
+        }
+    }
+
+    /// Reserves room for a texture group of the given size.
+    ///
+    /// - `additional`: Number of instances the next new texture group is expected to hold.
+    ///
+    /// Use it as a hint before queueing many instances of one texture, such as the
+    /// visible tiles of a tileset, to avoid growing the group's list step by step.
+    pub fn reserve(&mut self, additional: usize) {
+        match self.spare.last_mut() {
+            Some(instances) => instances.reserve(additional),
+            None => self.spare.push(Vec::with_capacity(additional)),
+        }
+    }
+
+    /// Sets whether texture instances are drawn in insertion order.
+    ///
+    /// - `ordered`: Whether a change of texture starts a new run.
+    ///
+    /// Use it for draws that are already sorted, such as objects ordered by depth:
+    /// instances are only regrouped between changes of texture, so a sorted list
+    /// with long stretches of one texture still draws in few groups.
+    pub fn set_ordered(&mut self, ordered: bool) {
+        self.ordered = ordered;
+    }
+
+    /// Returns the segment new draws of the given kind are added to.
+    ///
+    /// - `texture`: The texture of the draw, or `None` for a primitive.
+    ///
+    /// Extends the last segment in use if it has the same kind and, for textures,
+    /// accepts the texture, otherwise starts a new one, reusing a kept segment of
+    /// that kind when possible.
+    fn segment(&mut self, texture: Option<TextureId>) -> &mut Segment {
+        let textures = texture.is_some();
+        let extends = self.active > 0
+            && match (&self.segments[self.active - 1], texture) {
+                (Segment::Textures(run), Some(texture)) => run.accepts(texture, self.ordered),
+                (Segment::Textures(_), None) => false,
+                (Segment::Primitives(_), _) => !textures,
+            };
+        if !extends {
+            let reusable = self.segments
+                .get(self.active)
+                .is_some_and(|segment| matches!(segment, Segment::Textures(_)) == textures);
+            if !reusable {
+                let segment = if textures { Segment::Textures(TextureRun::default()) } else { Segment::Primitives(Vec::new()) };
+                if self.active < self.segments.len() {
+                    self.segments[self.active] = segment;
+                } else {
+                    self.segments.push(segment);
+                }
+            }
+            self.active += 1;
         }
+        &mut self.segments[self.active - 1]
     }
 
     /// Adds a texture instance to the batch.
@@ -23,49 +256,383 @@ impl DrawBatch {
     /// - `pos`: The position to draw the texture at.
     /// - `size`: The size scale factor for the texture.
     /// - `dest_size`: Optional destination size for the texture.
+    ///
+    /// The drawn size is `dest_size * size` when a destination size is given, and the
+    /// texture's own size multiplied by `size` otherwise. Pass `1.0` to draw unscaled.
+    ///
+    /// Without a destination size, `size` is a scale factor and not a size in pixels:
+    /// `Vec2::splat(size)` would draw every texture 1 pixel wide at a scale of `1.0`.
     pub fn add(&mut self, texture: Texture2D, pos: Vec2, size: f32, dest_size: Option<Vec2>) {
-        let texture_id = texture.raw_miniquad_id();
-        
-        if let Some((_, instances)) = self.textures.iter_mut().find(|(t, _)| t.raw_miniquad_id() == texture_id) {
-            instances.push((pos, size, dest_size));
-            log_render!(log::Level::Trace, "Added to existing texture batch");
-        } else {
-            self.textures.push((texture, vec![(pos, size, dest_size)]));
-            log_render!(log::Level::Trace, "Created new texture batch");
+        self.add_ex(texture, pos, DrawParams { size, dest_size, ..Default::default() });
+    }
+
+    /// Adds a texture instance with extended drawing parameters to the batch.
+    ///
+    /// - `texture`: The texture to draw.
+    /// - `pos`: The position to draw the texture at.
+    /// - `params`: Scale, destination size, tint, rotation, flips, source region and layer of the instance.
+    ///
+    /// Instances drawing different source regions of the same texture share one
+    /// texture group, so a whole sprite sheet or tileset is drawn as a single batch.
+    /// Finding the group takes constant time no matter how many textures are queued.
+    /// Without a destination size, an instance with a source region is drawn at the
+    /// size of that region.
+    pub fn add_ex(&mut self, texture: Texture2D, pos: Vec2, params: DrawParams) {
+        self.add_ex_with_id(texture.raw_miniquad_id(), texture, pos, params);
+    }
+
+    /// Adds a texture instance whose `raw_miniquad_id` is already known.
+    ///
+    /// - `texture_id`: The `raw_miniquad_id` of `texture`, used to find its group.
+    /// - `texture`: The texture to draw.
+    /// - `pos`: The position to draw the texture at.
+    /// - `params`: The drawing parameters of the instance.
+    ///
+    /// `add_ex` asks the graphics context for the id of every instance. When queueing
+    /// many instances of one texture, the id can be looked up once and passed here.
+    /// Passing the id of another texture groups the instance with that texture.
+    pub fn add_ex_with_id(&mut self, texture_id: TextureId, texture: Texture2D, pos: Vec2, params: DrawParams) {
+        let mut spare = std::mem::take(&mut self.spare);
+
+        if let Segment::Textures(run) = self.segment(Some(texture_id)) {
+            run.last = Some(texture_id);
+            if let Err(index) = run.layers.binary_search(&params.layer) {
+                run.layers.insert(index, params.layer);
+            }
+            if let Some(&index) = run.lookup.get(&texture_id) {
+                run.groups[index].1.push((pos, params));
+                log_render!(log::Level::Trace, "Added to existing texture batch");
+            } else {
+                let mut instances = spare.pop().unwrap_or_default();
+                instances.push((pos, params));
+                run.lookup.insert(texture_id, run.groups.len());
+                run.groups.push((texture, instances));
+                log_render!(log::Level::Trace, "Created new texture batch");
+            }
         }
+        self.spare = spare;
     }
 
-    /// Draws all texture instances in the batch.
+    /// Queues a primitive, extending the last run of primitives if there is one.
+    fn push_primitive(&mut self, primitive: Primitive) {
+        if let Segment::Primitives(primitives) = self.segment(None) {
+            primitives.push(primitive);
+        }
+    }
+
+    /// Adds a solid rectangle to the batch.
+    ///
+    /// - `rect`: The rectangle to fill.
+    /// - `color`: The fill color.
+    pub fn add_rect(&mut self, rect: Rect, color: Color) {
+        self.push_primitive(Primitive::Rect { rect, color });
+    }
+
+    /// Adds the outline of a rectangle to the batch.
+    ///
+    /// - `rect`: The rectangle to outline.
+    /// - `thickness`: The thickness of the outline in pixels.
+    /// - `color`: The outline color.
+    pub fn add_rect_lines(&mut self, rect: Rect, thickness: f32, color: Color) {
+        self.push_primitive(Primitive::RectLines { rect, thickness, color });
+    }
+
+    /// Adds a straight line to the batch.
+    ///
+    /// - `a`: The start point of the line.
+    /// - `b`: The end point of the line.
+    /// - `thickness`: The thickness of the line in pixels.
+    /// - `color`: The line color.
+    pub fn add_line(&mut self, a: Vec2, b: Vec2, thickness: f32, color: Color) {
+        self.push_primitive(Primitive::Line { a, b, thickness, color });
+    }
+
+    /// Adds a line of text to the batch.
+    ///
+    /// - `text`: The text to draw.
+    /// - `pos`: The position of the start of the text's baseline.
+    /// - `font_size`: The size of the text in pixels.
+    /// - `font`: The font to draw with, `None` for the default font.
+    /// - `color`: The text color.
+    pub fn add_text(&mut self, text: &str, pos: Vec2, font_size: u16, font: Option<&Font>, color: Color) {
+        self.push_primitive(Primitive::Text {
+            text: text.to_string(),
+            pos,
+            font_size,
+            font: font.cloned(),
+            color,
+        });
+    }
+
+    /// Clips everything queued after this call to a rectangle.
+    ///
+    /// - `rect`: The rectangle in screen coordinates to clip to, or `None` to stop clipping.
+    ///
+    /// The clip stays active until the next call, including for later batches, so
+    /// queue `add_clip(None)` once the clipped draws are queued.
+    pub fn add_clip(&mut self, rect: Option<Rect>) {
+        self.push_primitive(Primitive::Clip { rect });
+    }
+
+    /// Draws all queued texture instances and primitives in the batch.
+    ///
+    /// Texture instances and primitives are drawn in the order they were added, with
+    /// each run of consecutive texture instances drawn grouped by texture.
     pub fn draw(&mut self) {
-        log_render!(log::Level::Debug, "Drawing batch with {} texture groups", self.textures.len());
+        log_render!(log::Level::Debug, "Drawing batch with {} segments", self.active);
         
-        for (texture, instances) in &self.textures {
-            log_render!(log::Level::Trace, "Drawing {} instances of texture", instances.len());
-            
-            for (pos, _size, dest_size) in instances {
-                draw_texture_ex(
-                    texture,
-                    pos.x,
-                    pos.y,
-                    color::WHITE,
-                    DrawTextureParams {
-                        dest_size: *dest_size,
-                        source: None,
-                        rotation: 0.0,
-                        flip_x: false,
-                        flip_y: false,
-                        pivot: None,
-                    }
-                );
+        for segment in &self.segments[..self.active] {
+            match segment {
+                Segment::Textures(run) => Self::draw_textures(run),
+                Segment::Primitives(primitives) => Self::draw_primitives(primitives),
             }
         }
         
-        self.textures.clear();
+        self.clear();
         log_render!(log::Level::Trace, "Batch cleared");
     }
 
-    /// Clears the batch, removing all queued texture instances.
+    /// Draws a run of texture instances grouped by texture.
+    ///
+    /// With more than one layer in the run, each layer is drawn in turn from the
+    /// lowest up, keeping the group and insertion order within a layer.
+    fn draw_textures(run: &TextureRun) {
+        if run.layers.len() <= 1 {
+            for (texture, instances) in &run.groups {
+                log_render!(log::Level::Trace, "Drawing {} instances of texture", instances.len());
+                for (pos, params) in instances {
+                    Self::draw_instance(texture, *pos, params);
+                }
+            }
+            return;
+        }
+
+        for &layer in &run.layers {
+            log_render!(log::Level::Trace, "Drawing texture layer {}", layer);
+            for (texture, instances) in &run.groups {
+                for (pos, params) in instances.iter().filter(|(_, params)| params.layer == layer) {
+                    Self::draw_instance(texture, *pos, params);
+                }
+            }
+        }
+    }
+
+    /// Draws a single texture instance.
+    fn draw_instance(texture: &Texture2D, pos: Vec2, params: &DrawParams) {
+        draw_texture_ex(texture, pos.x, pos.y, params.color, Self::texture_params(params, texture.size()));
+    }
+
+    /// Converts the parameters of an instance into macroquad's texture parameters.
+    ///
+    /// - `params`: The drawing parameters of the instance.
+    /// - `texture_size`: The size of the whole texture in pixels.
+    fn texture_params(params: &DrawParams, texture_size: Vec2) -> DrawTextureParams {
+        DrawTextureParams {
+            dest_size: Some(Self::dest_size(params, texture_size)),
+            source: params.source,
+            rotation: params.rotation,
+            flip_x: params.flip_x,
+            flip_y: params.flip_y,
+            pivot: params.pivot,
+        }
+    }
+
+    /// Computes the on-screen size of an instance.
+    ///
+    /// - `params`: The drawing parameters of the instance.
+    /// - `texture_size`: The size of the whole texture in pixels.
+    ///
+    /// Returns `dest_size * size` when a destination size is given, and the size of the
+    /// source region (or the whole texture) multiplied by `size` otherwise.
+    fn dest_size(params: &DrawParams, texture_size: Vec2) -> Vec2 {
+        params.dest_size.unwrap_or_else(|| {
+            params.source.map_or(texture_size, |source| source.size())
+        }) * params.size
+    }
+
+    /// Draws a run of primitives in insertion order.
+    fn draw_primitives(primitives: &[Primitive]) {
+        for primitive in primitives {
+            match primitive {
+                Primitive::Rect { rect, color } => {
+                    draw_rectangle(rect.x, rect.y, rect.w, rect.h, *color);
+                }
+                Primitive::RectLines { rect, thickness, color } => {
+                    draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, *thickness, *color);
+                }
+                Primitive::Line { a, b, thickness, color } => {
+                    draw_line(a.x, a.y, b.x, b.y, *thickness, *color);
+                }
+                Primitive::Clip { rect } => {
+                    let dpi = screen_dpi_scale();
+                    let clip = rect.map(|rect| {
+                        ((rect.x * dpi) as i32, (rect.y * dpi) as i32, (rect.w * dpi) as i32, (rect.h * dpi) as i32)
+                    });
+                    // SAFETY: only the scissor state of the quad context is changed,
+                    // on the thread that owns it, between draw calls.
+                    unsafe {
+                        get_internal_gl().quad_gl.scissor(clip);
+                    }
+                }
+                Primitive::Text { text, pos, font_size, font, color } => {
+                    draw_text_ex(
+                        text,
+                        pos.x,
+                        pos.y,
+                        TextParams {
+                            font: font.as_ref(),
+                            font_size: *font_size,
+                            color: *color,
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    /// Clears the batch, removing all queued texture instances and primitives.
+    ///
+    /// The memory used by the queued draws is kept for the next frame.
     pub fn clear(&mut self) {
-        self.textures.clear();
+        for segment in &mut self.segments[..self.active] {
+            match segment {
+                Segment::Textures(run) => {
+                    for (_, mut instances) in run.groups.drain(..) {
+                        instances.clear();
+                        self.spare.push(instances);
+                    }
+                    run.lookup.clear();
+                    run.layers.clear();
+                    run.last = None;
+                }
+                Segment::Primitives(primitives) => primitives.clear(),
+            }
+        }
+        self.active = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::miniquad::RawId;
+    use std::time::Instant;
+
+    fn texture(id: u32) -> TextureId {
+        TextureId::from_raw_id(RawId::OpenGl(id))
+    }
+
+    /// Queues an instance of the texture with the given id, without a graphics context.
+    fn push(batch: &mut DrawBatch, id: u32, params: DrawParams) {
+        batch.add_ex_with_id(texture(id), Texture2D::from_miniquad_texture(texture(id)), Vec2::ZERO, params);
+    }
+
+    /// Returns the queued texture groups of the batch's only segment.
+    fn groups(batch: &DrawBatch) -> &[(Texture2D, Vec<Instance>)] {
+        match &batch.segments[..batch.active] {
+            [Segment::Textures(run)] => &run.groups,
+            _ => panic!("expected a single run of textures"),
+        }
+    }
+
+    #[test]
+    fn rotation_and_flips_reach_the_draw() {
+        let mut batch = DrawBatch::new();
+        let pivot = Some(Vec2::new(4.0, 4.0));
+        push(&mut batch, 1, DrawParams { rotation: 1.5, flip_x: true, flip_y: true, pivot, ..Default::default() });
+
+        let (_, params) = &groups(&batch)[0].1[0];
+        let drawn = DrawBatch::texture_params(params, Vec2::new(16.0, 16.0));
+        assert_eq!(drawn.rotation, 1.5);
+        assert!(drawn.flip_x && drawn.flip_y);
+        assert_eq!(drawn.pivot, pivot);
+        assert_eq!(drawn.dest_size, Some(Vec2::new(16.0, 16.0)));
+    }
+
+    #[test]
+    fn source_regions_of_one_texture_share_a_group() {
+        let mut batch = DrawBatch::new();
+        let (left, right) = (Rect::new(0.0, 0.0, 8.0, 8.0), Rect::new(8.0, 0.0, 8.0, 8.0));
+        push(&mut batch, 1, DrawParams { source: Some(left), ..Default::default() });
+        push(&mut batch, 1, DrawParams { source: Some(right), ..Default::default() });
+
+        let groups = groups(&batch);
+        assert_eq!(groups.len(), 1);
+        let sources: Vec<_> = groups[0].1.iter().map(|(_, params)| params.source).collect();
+        assert_eq!(sources, vec![Some(left), Some(right)]);
+        assert_eq!(DrawBatch::texture_params(&groups[0].1[1].1, Vec2::new(16.0, 8.0)).source, Some(right));
+    }
+
+    /// Compares queueing 50,000 instances through the texture lookup with a linear
+    /// search of the groups, for 1, 50 and 500 distinct textures.
+    ///
+    /// Run with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore = "measurement"]
+    fn measure_grouping_of_50_000_instances() {
+        let rounds = 200;
+        for textures in [1, 50, 500] {
+            let mut batch = DrawBatch::new();
+            let start = Instant::now();
+            for _ in 0..rounds {
+                for i in 0..50_000 {
+                    push(&mut batch, i % textures, DrawParams::default());
+                }
+                assert_eq!(groups(&batch).len(), textures as usize);
+                batch.clear();
+            }
+            let lookup = start.elapsed() / rounds;
+
+            let mut groups: Vec<(TextureId, Vec<Instance>)> = Vec::new();
+            let start = Instant::now();
+            for _ in 0..rounds {
+                for i in 0..50_000 {
+                    let id = texture(i % textures);
+                    match groups.iter_mut().find(|(group, _)| *group == id) {
+                        Some((_, instances)) => instances.push((Vec2::ZERO, DrawParams::default())),
+                        None => groups.push((id, vec![(Vec2::ZERO, DrawParams::default())])),
+                    }
+                }
+                assert_eq!(groups.len(), textures as usize);
+                groups.iter_mut().for_each(|(_, instances)| instances.clear());
+            }
+            let scan = start.elapsed() / rounds;
+
+            println!("{} textures x {} instances: lookup {:?}, scan {:?} per frame", textures, 50_000 / textures, lookup, scan);
+        }
+    }
+
+    #[test]
+    fn unordered_run_accepts_any_texture() {
+        let run = TextureRun { last: Some(texture(1)), ..Default::default() };
+        assert!(run.accepts(texture(2), false));
+    }
+
+    #[test]
+    fn ordered_run_splits_on_texture_change() {
+        let mut run = TextureRun::default();
+        assert!(run.accepts(texture(1), true));
+        run.last = Some(texture(1));
+        assert!(run.accepts(texture(1), true));
+        assert!(!run.accepts(texture(2), true));
+    }
+
+    #[test]
+    fn dest_size_scales_texture_size() {
+        let params = DrawParams { size: 0.5, ..Default::default() };
+        assert_eq!(DrawBatch::dest_size(&params, Vec2::new(32.0, 16.0)), Vec2::new(16.0, 8.0));
+    }
+
+    #[test]
+    fn dest_size_scales_explicit_size() {
+        let params = DrawParams { size: 2.0, dest_size: Some(Vec2::new(10.0, 20.0)), ..Default::default() };
+        assert_eq!(DrawBatch::dest_size(&params, Vec2::new(32.0, 16.0)), Vec2::new(20.0, 40.0));
+    }
+
+    #[test]
+    fn dest_size_uses_source_region() {
+        let params = DrawParams { size: 0.5, source: Some(Rect::new(0.0, 0.0, 8.0, 4.0)), ..Default::default() };
+        assert_eq!(DrawBatch::dest_size(&params, Vec2::new(32.0, 16.0)), Vec2::new(4.0, 2.0));
     }
 }
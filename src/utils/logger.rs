@@ -1,5 +1,11 @@
 use log::{LevelFilter, Log, Metadata, Record};
-use std::sync::Once;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Macro for logging messages with the "world" target.
 /// 
@@ -32,6 +38,8 @@ macro_rules! log_render {
 }
 
 /// Macro for logging messages with the "entity" target.
+/// Used for messages about `Object`s, the target keeps its old name so existing
+/// log filters continue to work.
 /// 
 /// Usage: `log_entity!(log::Level::Warn, "message")`
 #[macro_export]
@@ -41,31 +49,408 @@ macro_rules! log_entity {
     };
 }
 
+/// Level filters for each log target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogLevels {
+    /// Filter for the "world" target.
+    pub world: LevelFilter,
+    /// Filter for the "chunk" target.
+    pub chunk: LevelFilter,
+    /// Filter for the "render" target.
+    pub render: LevelFilter,
+    /// Filter for the "entity" target.
+    pub entity: LevelFilter,
+    /// Filter for any other target.
+    pub other: LevelFilter,
+}
+
+impl Default for LogLevels {
+    fn default() -> Self {
+        Self {
+            world: LevelFilter::Info,
+            chunk: LevelFilter::Info,
+            render: LevelFilter::Info,
+            entity: LevelFilter::Info,
+            other: LevelFilter::Info,
+        }
+    }
+}
+
+/// Format of each written log line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable `[LEVEL][target] message` lines.
+    #[default]
+    Text,
+    /// One JSON object per line with `level`, `target`, `msg` and `ts` fields.
+    /// `ts` is the time in milliseconds since the Unix epoch.
+    Ndjson,
+}
+
+/// Rate limiting applied to log lines before they are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogThrottle {
+    /// Every record is written.
+    #[default]
+    Off,
+    /// Identical consecutive records (same level, target and message) are written
+    /// once. When a different record arrives, or the logger is flushed, a single
+    /// line with the message and an `(xN)` suffix reports the total count.
+    CollapseRepeats,
+    /// At most this many records are written per target each second. The rest are
+    /// dropped, and the number dropped is reported once the second is over or the
+    /// logger is flushed.
+    MaxPerSecond(u32),
+}
+
+/// Bookkeeping of `LogThrottle`, guarded together with the output.
+#[derive(Default)]
+struct ThrottleState {
+    /// The active rate limiting mode.
+    mode: LogThrottle,
+    /// Level, target and message of the last written record.
+    last: Option<(log::Level, String, String)>,
+    /// Number of times the last record was repeated without being written.
+    repeats: usize,
+    /// Reused buffer the message of each record is formatted into for comparison.
+    scratch: String,
+    /// Start of the current one second window.
+    window_start: Option<Instant>,
+    /// Records written and dropped per target in the current window.
+    counts: HashMap<String, (u32, u32)>,
+}
+
+/// Destination and format of log output.
+pub struct LogOutput {
+    writer: Box<dyn Write + Send>,
+    colors: bool,
+    format: LogFormat,
+}
+
+impl LogOutput {
+    /// Creates an output writing to standard output.
+    ///
+    /// Colors are enabled only if standard output is a terminal.
+    pub fn stdout() -> Self {
+        Self {
+            writer: Box::new(io::stdout()),
+            colors: io::stdout().is_terminal(),
+            format: LogFormat::Text,
+        }
+    }
+
+    /// Creates an output appending to a file, creating it if it does not exist.
+    ///
+    /// - `path`: Path of the log file.
+    ///
+    /// Returns the output with colors disabled, or an error if the file could not be opened.
+    pub fn file(path: &str) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open log file {}: {}", path, e))?;
+        Ok(Self::writer(file))
+    }
+
+    /// Creates an output collecting log lines in memory.
+    ///
+    /// Useful in tests to assert on what was logged without touching the terminal.
+    ///
+    /// Returns the output with colors disabled and a handle to read the collected lines.
+    pub fn buffer() -> (Self, LogBuffer) {
+        let buffer = LogBuffer::default();
+        (Self::writer(buffer.clone()), buffer)
+    }
+
+    /// Creates an output discarding every log line.
+    pub fn sink() -> Self {
+        Self::writer(io::sink())
+    }
+
+    /// Creates an output writing to any writer.
+    ///
+    /// - `writer`: The writer to write log lines to.
+    ///
+    /// Returns the output with colors disabled.
+    pub fn writer<W: Write + Send + 'static>(writer: W) -> Self {
+        Self {
+            writer: Box::new(writer),
+            colors: false,
+            format: LogFormat::Text,
+        }
+    }
+
+    /// Sets whether text lines are colored with ANSI escape codes.
+    ///
+    /// - `colors`: `true` to color lines by level. Ignored for `LogFormat::Ndjson`.
+    pub fn with_colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Sets the format of each written line.
+    ///
+    /// - `format`: The line format.
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Writes a single record as one line.
+    fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        match self.format {
+            LogFormat::Text if self.colors => {
+                let color = match record.level() {
+                    log::Level::Error => "\x1b[31m",
+                    log::Level::Warn => "\x1b[33m",
+                    log::Level::Info => "\x1b[32m",
+                    log::Level::Debug => "\x1b[36m",
+                    log::Level::Trace => "\x1b[90m",
+                };
+                writeln!(
+                    self.writer,
+                    "{}[{:5}][{}] {}\x1b[0m",
+                    color,
+                    record.level(),
+                    record.target(),
+                    record.args()
+                )
+            }
+            LogFormat::Text => {
+                writeln!(self.writer, "[{:5}][{}] {}", record.level(), record.target(), record.args())
+            }
+            LogFormat::Ndjson => {
+                let ts = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |elapsed| elapsed.as_millis() as u64);
+                let line = serde_json::json!({
+                    "level": record.level().as_str(),
+                    "target": record.target(),
+                    "msg": record.args().to_string(),
+                    "ts": ts,
+                });
+                writeln!(self.writer, "{}", line)
+            }
+        }
+    }
+}
+
+/// In-memory log output created with `LogOutput::buffer`.
+///
+/// Clones share the same lines, so the handle can be kept while the output is
+/// owned by a logger.
+#[derive(Clone, Default)]
+pub struct LogBuffer {
+    bytes: Arc<Mutex<Vec<u8>>>,
+}
+
+impl LogBuffer {
+    /// Gets everything written so far as a string.
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.bytes.lock().unwrap_or_else(|e| e.into_inner())).into_owned()
+    }
+
+    /// Gets the lines written so far, without line endings.
+    pub fn lines(&self) -> Vec<String> {
+        self.contents().lines().map(str::to_string).collect()
+    }
+
+    /// Removes everything written so far.
+    pub fn clear(&self) {
+        self.bytes.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+impl Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes.lock().unwrap_or_else(|e| e.into_inner()).extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Logger implementation for the game, supporting different log levels for different targets.
+///
+/// Levels are stored atomically, so they can be changed at any time through
+/// `GameLogger::global()` without reinstalling the logger.
+/// Output can be rate limited with `set_throttle`, e.g. to keep `Trace` logging of
+/// the "render" target readable.
 pub struct GameLogger {
-    world_level: LevelFilter,
-    chunk_level: LevelFilter,
-    render_level: LevelFilter,
-    entity_level: LevelFilter,
+    world_level: AtomicUsize,
+    chunk_level: AtomicUsize,
+    render_level: AtomicUsize,
+    entity_level: AtomicUsize,
+    other_level: AtomicUsize,
+    output: Mutex<LogOutput>,
+    throttle: Mutex<ThrottleState>,
+    /// Whether `throttle` is in a mode other than `LogThrottle::Off`, so unthrottled
+    /// records skip its lock.
+    throttled: AtomicBool,
 }
 
 impl GameLogger {
-    /// Initializes the global logger instance.
+    /// Initializes the global logger instance with every target at `Info`.
     /// This should be called once at the start of the program.
     pub fn init() {
-        INIT.call_once(|| {
-            let logger = GameLogger {
-                world_level: LevelFilter::Info,
-                chunk_level: LevelFilter::Info,
-                render_level: LevelFilter::Info,
-                entity_level: LevelFilter::Info,
-            };
-            log::set_boxed_logger(Box::new(logger))
+        Self::init_with(LogLevels::default());
+    }
+
+    /// Initializes the global logger instance with the given level filters, writing to standard output.
+    /// This should be called once at the start of the program. Later calls have no effect.
+    ///
+    /// - `levels`: The initial level filter of each target.
+    pub fn init_with(levels: LogLevels) {
+        Self::init_with_output(levels, LogOutput::stdout());
+    }
+
+    /// Initializes the global logger instance with the given level filters and output.
+    /// This should be called once at the start of the program. Later calls have no effect.
+    ///
+    /// - `levels`: The initial level filter of each target.
+    /// - `output`: Where and in which format log lines are written.
+    pub fn init_with_output(levels: LogLevels, output: LogOutput) {
+        LOGGER.get_or_init(|| {
+            let logger: &'static GameLogger = Box::leak(Box::new(GameLogger::new(levels, output)));
+            log::set_logger(logger)
                 .map(|()| log::set_max_level(LevelFilter::Trace))
                 .expect("Failed to set logger");
+            logger
         });
     }
 
+    /// Gets the globally installed logger.
+    ///
+    /// Returns `None` if `init` or `init_with` has not been called yet.
+    pub fn global() -> Option<&'static GameLogger> {
+        LOGGER.get().copied()
+    }
+
+    /// Creates a logger with the given level filters and output without installing it.
+    ///
+    /// Records are passed to it through `Log::log`, which lets tests capture output
+    /// with `LogOutput::buffer` while the global logger stays untouched. Use
+    /// `init_with_output` to install a logger for the application.
+    ///
+    /// - `levels`: The initial level filter of each target.
+    /// - `output`: Where and in which format log lines are written.
+    pub fn new(levels: LogLevels, output: LogOutput) -> Self {
+        Self {
+            world_level: AtomicUsize::new(levels.world as usize),
+            chunk_level: AtomicUsize::new(levels.chunk as usize),
+            render_level: AtomicUsize::new(levels.render as usize),
+            entity_level: AtomicUsize::new(levels.entity as usize),
+            other_level: AtomicUsize::new(levels.other as usize),
+            output: Mutex::new(output),
+            throttle: Mutex::new(ThrottleState::default()),
+            throttled: AtomicBool::new(false),
+        }
+    }
+
+    /// Creates a logger that discards everything, without installing it.
+    ///
+    /// Every target is filtered out and the output is `LogOutput::sink`.
+    pub fn silent() -> Self {
+        let off = LogLevels {
+            world: LevelFilter::Off,
+            chunk: LevelFilter::Off,
+            render: LevelFilter::Off,
+            entity: LevelFilter::Off,
+            other: LevelFilter::Off,
+        };
+        Self::new(off, LogOutput::sink())
+    }
+
+    /// Sets the rate limiting applied to log lines.
+    ///
+    /// Pending repeat counts and per-second counters are reported and reset.
+    /// Defaults to `LogThrottle::Off`.
+    ///
+    /// - `mode`: The rate limiting to apply.
+    pub fn set_throttle(&self, mode: LogThrottle) {
+        let mut throttle = self.throttle.lock().unwrap_or_else(|e| e.into_inner());
+        let mut output = self.output.lock().unwrap_or_else(|e| e.into_inner());
+        write_pending(&mut throttle, &mut output);
+        *throttle = ThrottleState { mode, ..ThrottleState::default() };
+        self.throttled.store(mode != LogThrottle::Off, Ordering::Relaxed);
+    }
+
+    /// Gets the rate limiting applied to log lines.
+    pub fn throttle(&self) -> LogThrottle {
+        self.throttle.lock().unwrap_or_else(|e| e.into_inner()).mode
+    }
+
+    /// Replaces the output log lines are written to.
+    ///
+    /// The previous output is flushed before it is replaced.
+    ///
+    /// - `output`: Where and in which format log lines are written.
+    pub fn set_output(&self, output: LogOutput) {
+        let mut current = self.output.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = current.writer.flush();
+        *current = output;
+    }
+
+    /// Gets the current level filter of each target.
+    pub fn levels(&self) -> LogLevels {
+        LogLevels {
+            world: load_level(&self.world_level),
+            chunk: load_level(&self.chunk_level),
+            render: load_level(&self.render_level),
+            entity: load_level(&self.entity_level),
+            other: load_level(&self.other_level),
+        }
+    }
+
+    /// Replaces the level filter of every target.
+    ///
+    /// - `levels`: The new level filters.
+    pub fn set_levels(&self, levels: LogLevels) {
+        self.set_world_level(levels.world);
+        self.set_chunk_level(levels.chunk);
+        self.set_render_level(levels.render);
+        self.set_entity_level(levels.entity);
+        self.set_other_level(levels.other);
+    }
+
+    /// Sets the level filter of the "world" target.
+    ///
+    /// - `level`: The new level filter.
+    pub fn set_world_level(&self, level: LevelFilter) {
+        self.world_level.store(level as usize, Ordering::Relaxed);
+    }
+
+    /// Sets the level filter of the "chunk" target.
+    ///
+    /// - `level`: The new level filter.
+    pub fn set_chunk_level(&self, level: LevelFilter) {
+        self.chunk_level.store(level as usize, Ordering::Relaxed);
+    }
+
+    /// Sets the level filter of the "render" target.
+    ///
+    /// - `level`: The new level filter.
+    pub fn set_render_level(&self, level: LevelFilter) {
+        self.render_level.store(level as usize, Ordering::Relaxed);
+    }
+
+    /// Sets the level filter of the "entity" target.
+    ///
+    /// - `level`: The new level filter.
+    pub fn set_entity_level(&self, level: LevelFilter) {
+        self.entity_level.store(level as usize, Ordering::Relaxed);
+    }
+
+    /// Sets the level filter of targets other than the built-in ones.
+    ///
+    /// - `level`: The new level filter.
+    pub fn set_other_level(&self, level: LevelFilter) {
+        self.other_level.store(level as usize, Ordering::Relaxed);
+    }
+
     /// Determines if a log message should be logged based on the target and level.
     ///
     /// - `target`: The log target string.
@@ -74,13 +459,99 @@ impl GameLogger {
     /// Returns `true` if the message should be logged, `false` otherwise.
     fn should_log(&self, target: &str, level: log::Level) -> bool {
         let filter = match target {
-            "world" => self.world_level,
-            "chunk" => self.chunk_level,
-            "render" => self.render_level,
-            "entity" => self.entity_level,
-            _ => LevelFilter::Info,
+            "world" => &self.world_level,
+            "chunk" => &self.chunk_level,
+            "render" => &self.render_level,
+            "entity" => &self.entity_level,
+            _ => &self.other_level,
         };
-        level <= filter
+        level <= load_level(filter)
+    }
+}
+
+/// Writes the repeat count and dropped counts that have not been reported yet.
+fn write_pending(throttle: &mut ThrottleState, output: &mut LogOutput) {
+    if throttle.repeats > 0 {
+        if let Some((level, target, message)) = &throttle.last {
+            write_line(output, *level, target, format_args!("{} (x{})", message, throttle.repeats + 1));
+        }
+        throttle.repeats = 0;
+    }
+    let mut dropped: Vec<(&String, u32)> = throttle.counts
+        .iter_mut()
+        .filter(|(_, (_, dropped))| *dropped > 0)
+        .map(|(target, (_, dropped))| (target, std::mem::take(dropped)))
+        .collect();
+    dropped.sort();
+    for (target, dropped) in dropped {
+        write_line(output, log::Level::Warn, target, format_args!("Dropped {} log messages in the last second", dropped));
+    }
+}
+
+/// Writes a line that does not come from a `log` call, such as a throttling summary.
+fn write_line(output: &mut LogOutput, level: log::Level, target: &str, args: std::fmt::Arguments) {
+    let _ = output.write_record(&Record::builder().level(level).target(target).args(args).build());
+}
+
+/// Decides whether a record passes the rate limiting, reporting what was held back before it.
+///
+/// Returns `true` if the record should be written.
+fn admit(throttle: &mut ThrottleState, output: &mut LogOutput, record: &Record) -> bool {
+    match throttle.mode {
+        LogThrottle::Off => true,
+        LogThrottle::CollapseRepeats => {
+            throttle.scratch.clear();
+            let _ = fmt::write(&mut throttle.scratch, *record.args());
+            let repeated = throttle.last.as_ref().is_some_and(|(level, target, last)| {
+                *level == record.level() && target == record.target() && *last == throttle.scratch
+            });
+            if repeated {
+                throttle.repeats += 1;
+                return false;
+            }
+            write_pending(throttle, output);
+            match &mut throttle.last {
+                Some((level, target, message)) => {
+                    *level = record.level();
+                    target.clear();
+                    target.push_str(record.target());
+                    std::mem::swap(message, &mut throttle.scratch);
+                }
+                None => {
+                    let message = std::mem::take(&mut throttle.scratch);
+                    throttle.last = Some((record.level(), record.target().to_string(), message));
+                }
+            }
+            true
+        }
+        LogThrottle::MaxPerSecond(limit) => {
+            let now = Instant::now();
+            if throttle.window_start.is_none_or(|start| now.duration_since(start) >= Duration::from_secs(1)) {
+                write_pending(throttle, output);
+                throttle.counts.clear();
+                throttle.window_start = Some(now);
+            }
+            let (written, dropped) = throttle.counts.entry(record.target().to_string()).or_default();
+            if *written < limit {
+                *written += 1;
+                true
+            } else {
+                *dropped += 1;
+                false
+            }
+        }
+    }
+}
+
+/// Reads a level filter stored as its numeric value.
+fn load_level(level: &AtomicUsize) -> LevelFilter {
+    match level.load(Ordering::Relaxed) {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
     }
 }
 
@@ -91,25 +562,131 @@ impl Log for GameLogger {
     }
 
     fn log(&self, record: &Record) {
-        if self.enabled(record.metadata()) {
-            let color = match record.level() {
-                log::Level::Error => "\x1b[31m",
-                log::Level::Warn => "\x1b[33m",  
-                log::Level::Info => "\x1b[32m",  
-                log::Level::Debug => "\x1b[36m",
-                log::Level::Trace => "\x1b[90m", 
-            };
-            println!(
-                "{}[{:5}][{}] {}\x1b[0m",
-                color,
-                record.level(),
-                record.target(),
-                record.args()
-            );
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        if self.throttled.load(Ordering::Relaxed) {
+            let mut throttle = self.throttle.lock().unwrap_or_else(|e| e.into_inner());
+            let mut output = self.output.lock().unwrap_or_else(|e| e.into_inner());
+            if admit(&mut throttle, &mut output, record) {
+                let _ = output.write_record(record);
+            }
+        } else {
+            let mut output = self.output.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = output.write_record(record);
         }
     }
 
-    fn flush(&self) {}
+    fn flush(&self) {
+        let mut throttle = self.throttle.lock().unwrap_or_else(|e| e.into_inner());
+        let mut output = self.output.lock().unwrap_or_else(|e| e.into_inner());
+        write_pending(&mut throttle, &mut output);
+        let _ = output.writer.flush();
+    }
 }
 
-static INIT: Once = Once::new();
\ No newline at end of file
+static LOGGER: OnceLock<&'static GameLogger> = OnceLock::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a logger writing NDJSON lines to memory, with every target at `Trace`.
+    fn ndjson_logger() -> (GameLogger, LogBuffer) {
+        let (output, buffer) = LogOutput::buffer();
+        let levels = LogLevels {
+            world: LevelFilter::Trace,
+            chunk: LevelFilter::Trace,
+            render: LevelFilter::Trace,
+            entity: LevelFilter::Trace,
+            other: LevelFilter::Trace,
+        };
+        (GameLogger::new(levels, output.with_format(LogFormat::Ndjson)), buffer)
+    }
+
+    /// Passes a record to the logger as the `log` macros would.
+    fn log(logger: &GameLogger, level: log::Level, target: &str, args: std::fmt::Arguments) {
+        logger.log(&Record::builder().level(level).target(target).args(args).build());
+    }
+
+    /// Parses every written line as JSON.
+    fn parsed(buffer: &LogBuffer) -> Vec<serde_json::Value> {
+        buffer.lines().iter().map(|line| serde_json::from_str(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn ndjson_lines_carry_level_target_and_message() {
+        let (logger, buffer) = ndjson_logger();
+        log(&logger, log::Level::Warn, "chunk", format_args!("chunk {} is full", 3));
+        log(&logger, log::Level::Debug, "mygame", format_args!("custom"));
+
+        let lines = parsed(&buffer);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["level"], "WARN");
+        assert_eq!(lines[0]["target"], "chunk");
+        assert_eq!(lines[0]["msg"], "chunk 3 is full");
+        assert!(lines[0]["ts"].as_u64().is_some_and(|ts| ts > 0));
+        assert_eq!(lines[1]["target"], "mygame");
+        assert_eq!(lines[1]["msg"], "custom");
+    }
+
+    #[test]
+    fn records_below_the_target_level_are_not_written() {
+        let (logger, buffer) = ndjson_logger();
+        logger.set_render_level(LevelFilter::Warn);
+        log(&logger, log::Level::Info, "render", format_args!("hidden"));
+        log(&logger, log::Level::Info, "world", format_args!("shown"));
+
+        let lines = parsed(&buffer);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["msg"], "shown");
+    }
+
+    #[test]
+    fn records_over_the_per_second_limit_are_dropped_and_reported() {
+        let (logger, buffer) = ndjson_logger();
+        logger.set_throttle(LogThrottle::MaxPerSecond(2));
+        for i in 0..5 {
+            log(&logger, log::Level::Trace, "render", format_args!("frame {}", i));
+        }
+        logger.flush();
+
+        let lines = parsed(&buffer);
+        let messages: Vec<&str> = lines.iter().map(|line| line["msg"].as_str().unwrap()).collect();
+        assert_eq!(messages, ["frame 0", "frame 1", "Dropped 3 log messages in the last second"]);
+        assert_eq!(lines[2]["level"], "WARN");
+
+        logger.set_throttle(LogThrottle::Off);
+        buffer.clear();
+        for _ in 0..3 {
+            log(&logger, log::Level::Trace, "render", format_args!("frame"));
+        }
+        assert_eq!(buffer.lines().len(), 3);
+    }
+
+    #[test]
+    fn repeated_records_are_collapsed_into_one_count() {
+        let (logger, buffer) = ndjson_logger();
+        logger.set_throttle(LogThrottle::CollapseRepeats);
+        for _ in 0..4 {
+            log(&logger, log::Level::Info, "chunk", format_args!("chunk {} loaded", 7));
+        }
+        log(&logger, log::Level::Info, "world", format_args!("chunk {} loaded", 7));
+        log(&logger, log::Level::Info, "world", format_args!("chunk {} loaded", 7));
+        logger.flush();
+
+        let lines = parsed(&buffer);
+        let written: Vec<(&str, &str)> = lines.iter()
+            .map(|line| (line["target"].as_str().unwrap(), line["msg"].as_str().unwrap()))
+            .collect();
+        assert_eq!(written, [
+            ("chunk", "chunk 7 loaded"),
+            ("chunk", "chunk 7 loaded (x4)"),
+            ("world", "chunk 7 loaded"),
+            ("world", "chunk 7 loaded (x2)"),
+        ]);
+
+        buffer.clear();
+        logger.flush();
+        assert!(buffer.lines().is_empty());
+    }
+}
@@ -1,3 +1,6 @@
 pub mod draw;
+pub mod hash;
 pub mod logger;
+pub mod noise;
+pub mod rand;
 pub mod settings;
\ No newline at end of file
@@ -0,0 +1,105 @@
+use crate::utils::hash::hash2;
+
+/// Samples seeded 2D value noise at the given coordinates.
+///
+/// Random values are placed on an integer lattice and smoothly interpolated
+/// between, so nearby coordinates give similar results. One lattice cell is one
+/// unit, scale the coordinates to change the feature size.
+///
+/// - `seed`: Seed of the noise, the same seed always gives the same output.
+/// - `x`: The x-coordinate to sample at.
+/// - `y`: The y-coordinate to sample at.
+///
+/// Returns a value in the range 0.0 to 1.0.
+pub fn sample(seed: u64, x: f64, y: f64) -> f64 {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let tx = smoothstep(x - x0);
+    let ty = smoothstep(y - y0);
+    let (ix, iy) = (x0 as i32, y0 as i32);
+
+    let v00 = lattice(seed, ix, iy);
+    let v10 = lattice(seed, ix.wrapping_add(1), iy);
+    let v01 = lattice(seed, ix, iy.wrapping_add(1));
+    let v11 = lattice(seed, ix.wrapping_add(1), iy.wrapping_add(1));
+
+    let top = lerp(v00, v10, tx);
+    let bottom = lerp(v01, v11, tx);
+    lerp(top, bottom, ty)
+}
+
+/// Samples fractal Brownian motion built from several octaves of value noise.
+///
+/// Each octave samples `sample` at `lacunarity` times the frequency and
+/// `persistence` times the amplitude of the previous one, adding finer detail.
+///
+/// - `seed`: Seed of the noise, the same seed always gives the same output.
+/// - `x`: The x-coordinate to sample at.
+/// - `y`: The y-coordinate to sample at.
+/// - `octaves`: Number of noise layers to combine, at least one is always used.
+/// - `lacunarity`: Frequency multiplier between octaves, typically `2.0`.
+/// - `persistence`: Amplitude multiplier between octaves, typically `0.5`.
+///
+/// Returns a value in the range 0.0 to 1.0.
+pub fn fbm(seed: u64, x: f64, y: f64, octaves: u32, lacunarity: f64, persistence: f64) -> f64 {
+    let mut total = 0.0;
+    let mut max_amplitude = 0.0;
+    let mut amplitude = 1.0;
+    let mut frequency = 1.0;
+
+    for octave in 0..octaves.max(1) {
+        let octave_seed = seed.wrapping_add(octave as u64);
+        total += sample(octave_seed, x * frequency, y * frequency) * amplitude;
+        max_amplitude += amplitude;
+        amplitude *= persistence;
+        frequency *= lacunarity;
+    }
+
+    if max_amplitude > 0.0 {
+        total / max_amplitude
+    } else {
+        0.0
+    }
+}
+
+/// Returns the random value in the range 0.0 to 1.0 at a lattice point.
+fn lattice(seed: u64, x: i32, y: i32) -> f64 {
+    hash2(seed, x, y) as f64 / u32::MAX as f64
+}
+
+/// Eases an interpolation factor so the noise has no visible grid creases.
+fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Linearly interpolates between two values.
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sample points spread over positive and negative coordinates.
+    fn points() -> impl Iterator<Item = (f64, f64)> {
+        (-40..40).flat_map(|x| (-40..40).map(move |y| (x as f64 * 0.37, y as f64 * 0.53)))
+    }
+
+    #[test]
+    fn same_seed_gives_same_noise() {
+        for (x, y) in points() {
+            assert_eq!(sample(42, x, y), sample(42, x, y));
+            assert_eq!(fbm(42, x, y, 4, 2.0, 0.5), fbm(42, x, y, 4, 2.0, 0.5));
+        }
+        assert!(points().any(|(x, y)| sample(42, x, y) != sample(43, x, y)));
+    }
+
+    #[test]
+    fn noise_stays_in_unit_range() {
+        for (x, y) in points() {
+            assert!((0.0..=1.0).contains(&sample(7, x, y)));
+            assert!((0.0..=1.0).contains(&fbm(7, x, y, 5, 2.0, 0.5)));
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 /// Size of a single tile in world units (pixels).
 pub const TILE_SIZE: f32 = 16.0;
 
@@ -9,3 +11,87 @@ pub const CHUNK_PIXELS: f32 = TILE_SIZE * CHUNK_SIZE as f32;
 
 /// Margin around the viewport in which objects become active.
 pub const OBJECT_ACTIVATION_MARGIN: f32 = 100.0;
+
+/// Size of a cell in the per-chunk grid used to find objects to activate,
+/// for the default tile size; see `WorldConfig::object_index_cell_size`.
+pub const OBJECT_INDEX_CELL_SIZE: f32 = TILE_SIZE * 4.0;
+
+/// Number of objects a chunk needs before activation uses its grid instead of checking every object.
+pub const OBJECT_INDEX_THRESHOLD: usize = 64;
+
+/// Size of a cell in the spatial grid used for the object collision broad phase,
+/// for the default tile size; see `WorldConfig::collision_cell_size`.
+pub const COLLISION_CELL_SIZE: f32 = TILE_SIZE * 2.0;
+
+/// Default skin width used when resolving object collisions, in world units (pixels).
+pub const COLLISION_BUFFER: f32 = 1.0;
+
+/// Brightness lost per tile as light spreads away from an emitter in `World::compute_light`.
+pub const LIGHT_FALLOFF: f32 = 0.125;
+
+/// Grid and activation settings of a world, chosen at runtime.
+///
+/// The constants in this module are the defaults. A world and its chunks use the
+/// values of their config instead, so different worlds can use different tile and
+/// chunk sizes. The config is saved with the world and restored on load.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorldConfig {
+    /// Size of a single tile in world units (pixels).
+    pub tile_size: f32,
+    /// Number of tiles along one edge of a chunk.
+    pub chunk_size: usize,
+    /// Margin around the viewport in which objects become active.
+    pub object_activation_margin: f32,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            tile_size: TILE_SIZE,
+            chunk_size: CHUNK_SIZE,
+            object_activation_margin: OBJECT_ACTIVATION_MARGIN,
+        }
+    }
+}
+
+impl WorldConfig {
+    /// Returns the size of a chunk in world units (pixels).
+    pub fn chunk_pixels(&self) -> f32 {
+        self.tile_size * self.chunk_size as f32
+    }
+
+    /// Returns the size of a cell in the per-chunk object grid.
+    ///
+    /// Spans as many tiles as `OBJECT_INDEX_CELL_SIZE` does with the default tile size.
+    pub fn object_index_cell_size(&self) -> f32 {
+        self.tile_size * (OBJECT_INDEX_CELL_SIZE / TILE_SIZE)
+    }
+
+    /// Returns the size of a cell in the collision broad-phase grid.
+    ///
+    /// Spans as many tiles as `COLLISION_CELL_SIZE` does with the default tile size.
+    pub fn collision_cell_size(&self) -> f32 {
+        self.tile_size * (COLLISION_CELL_SIZE / TILE_SIZE)
+    }
+
+    /// Checks that the values can be used by a world.
+    ///
+    /// Returns `Ok(())` if the tile size is positive and finite, the chunk size is
+    /// between 1 and 256 and the margin is not negative, or an error message otherwise.
+    /// Larger chunks could hold more distinct tiles than a saved chunk can index.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.tile_size > 0.0 && self.tile_size.is_finite()) {
+            return Err(format!("Invalid tile size {}", self.tile_size));
+        }
+        if self.chunk_size == 0 {
+            return Err("Chunk size must not be zero".to_string());
+        }
+        if self.chunk_size > 256 {
+            return Err(format!("Chunk size {} is larger than 256", self.chunk_size));
+        }
+        if !(self.object_activation_margin >= 0.0 && self.object_activation_margin.is_finite()) {
+            return Err(format!("Invalid object activation margin {}", self.object_activation_margin));
+        }
+        Ok(())
+    }
+}
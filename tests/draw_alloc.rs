@@ -0,0 +1,90 @@
+//! Allocation test of `DrawBatch`.
+//!
+//! It lives in its own test binary because it installs a counting global
+//! allocator, which would otherwise apply to every unit test of the library.
+
+use gaymwtf_core::{DrawBatch, DrawParams};
+use macroquad::color::RED;
+use macroquad::math::{Rect, Vec2};
+use macroquad::miniquad::{RawId, TextureId};
+use macroquad::texture::Texture2D;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+thread_local! {
+    /// Heap allocations made by the current thread, so parallel tests don't interfere.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Passes allocations to the system allocator, counting them per thread.
+struct CountingAlloc;
+
+impl CountingAlloc {
+    fn count() {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::count();
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::count();
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+/// Returns the number of heap allocations the current thread made while running `f`.
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/// Queues an instance of the texture with the given id, without a graphics context.
+fn push(batch: &mut DrawBatch, id: u32, params: DrawParams) {
+    let texture = TextureId::from_raw_id(RawId::OpenGl(id));
+    batch.add_ex_with_id(texture, Texture2D::from_miniquad_texture(texture), Vec2::ZERO, params);
+}
+
+/// Queues a frame of 20 textures with 50 instances each, split by a primitive.
+fn queue_frame(batch: &mut DrawBatch) {
+    for i in 0..1000 {
+        if i == 500 {
+            batch.add_rect(Rect::new(0.0, 0.0, 8.0, 8.0), RED);
+        }
+        push(batch, i % 20, DrawParams { layer: (i % 3) as i32, ..Default::default() });
+    }
+}
+
+#[test]
+fn reused_batch_stops_allocating() {
+    let fresh = allocations(|| {
+        let mut batch = DrawBatch::new();
+        queue_frame(&mut batch);
+        batch.clear();
+    });
+    assert!(fresh > 0);
+
+    let mut batch = DrawBatch::new();
+    for _ in 0..3 {
+        queue_frame(&mut batch);
+        batch.clear();
+    }
+    for _ in 0..10 {
+        assert_eq!(allocations(|| {
+            queue_frame(&mut batch);
+            batch.clear();
+        }), 0);
+    }
+}